@@ -0,0 +1,159 @@
+import asyncio
+import logging
+from datetime import datetime, timedelta, timezone
+
+import httpx
+from supabase import Client
+
+from app.calendar.constants import WEBHOOK_CHANNEL_BUFFER_HOURS
+from app.calendar.helpers import ProviderError
+from app.calendar.providers import CalendarProvider, provider_for_account
+from app.core.db_utils import Row, all_rows
+
+logger = logging.getLogger(__name__)
+
+ACCOUNT_SYNC_STATE_TABLE = "google_account_sync_state"
+
+CLEARED_CHANNEL = {
+    "webhook_channel_id": None,
+    "webhook_resource_id": None,
+    "webhook_expires_at": None,
+    "webhook_channel_token": None,
+}
+
+
+def channel_needs_renewal(expires_at: str | None) -> bool:
+    if not expires_at:
+        return True
+    parsed = datetime.fromisoformat(str(expires_at))
+    if parsed.tzinfo is None:
+        parsed = parsed.replace(tzinfo=timezone.utc)
+    return parsed <= datetime.now(timezone.utc) + timedelta(hours=WEBHOOK_CHANNEL_BUFFER_HOURS)
+
+
+def clear_watch_channel(supabase: Client, calendar_id: str, channel_id: str) -> None:
+    (
+        supabase.table("calendar_sync_state")
+        .update(CLEARED_CHANNEL)
+        .eq("google_calendar_id", calendar_id)
+        .eq("webhook_channel_id", channel_id)
+        .execute()
+    )
+
+
+async def stop_watch_channel(client: CalendarProvider, channel_id: str | None, resource_id: str | None) -> None:
+    if not channel_id or not resource_id:
+        return
+    try:
+        await client.stop_watch_channel(channel_id, resource_id)
+    except ProviderError as e:
+        if e.status_code != 404:
+            logger.warning("Failed to stop watch channel %s: %s", channel_id, e.message)
+
+
+async def _stop_and_clear(client: CalendarProvider, supabase: Client, channels: list[Row]) -> None:
+    for row in channels:
+        channel_id = str(row["webhook_channel_id"])
+        await stop_watch_channel(client, channel_id, row.get("webhook_resource_id"))
+        await asyncio.to_thread(clear_watch_channel, supabase, str(row["google_calendar_id"]), channel_id)
+
+
+def get_account_channels(supabase: Client, google_account_id: str) -> list[Row]:
+    return all_rows(
+        supabase.table("calendar_sync_state")
+        .select("google_calendar_id, webhook_channel_id, webhook_resource_id, google_calendars!inner(google_account_id)")
+        .eq("google_calendars.google_account_id", google_account_id)
+        .not_.is_("webhook_channel_id", "null")
+        .execute()
+        .data
+    )
+
+
+def get_calendar_channels(supabase: Client, calendar_ids: list[str]) -> list[Row]:
+    return all_rows(
+        supabase.table("calendar_sync_state")
+        .select("google_calendar_id, webhook_channel_id, webhook_resource_id")
+        .in_("google_calendar_id", calendar_ids)
+        .not_.is_("webhook_channel_id", "null")
+        .execute()
+        .data
+    )
+
+
+def get_calendar_list_channel(supabase: Client, google_account_id: str) -> Row | None:
+    result = (
+        supabase.table(ACCOUNT_SYNC_STATE_TABLE)
+        .select("webhook_channel_id, webhook_resource_id")
+        .eq("google_account_id", google_account_id)
+        .not_.is_("webhook_channel_id", "null")
+        .limit(1)
+        .execute()
+    )
+    return result.data[0] if result.data else None
+
+
+def clear_calendar_list_channel(supabase: Client, google_account_id: str, channel_id: str) -> None:
+    (
+        supabase.table(ACCOUNT_SYNC_STATE_TABLE)
+        .update(CLEARED_CHANNEL)
+        .eq("google_account_id", google_account_id)
+        .eq("webhook_channel_id", channel_id)
+        .execute()
+    )
+
+
+async def stop_account_channels(supabase: Client, http: httpx.AsyncClient, user_id: str, google_account_id: str) -> None:
+    channels, list_channel = await asyncio.gather(
+        asyncio.to_thread(get_account_channels, supabase, google_account_id),
+        asyncio.to_thread(get_calendar_list_channel, supabase, google_account_id),
+    )
+    if not channels and not list_channel:
+        return
+    client = await asyncio.to_thread(provider_for_account, supabase, http, user_id, google_account_id)
+    await _stop_and_clear(client, supabase, channels)
+    if list_channel:
+        channel_id = str(list_channel["webhook_channel_id"])
+        await stop_watch_channel(client, channel_id, list_channel.get("webhook_resource_id"))
+        await asyncio.to_thread(clear_calendar_list_channel, supabase, google_account_id, channel_id)
+
+
+async def stop_calendar_channels(client: CalendarProvider, supabase: Client, calendar_ids: list[str]) -> None:
+    if not calendar_ids:
+        return
+    channels = await asyncio.to_thread(get_calendar_channels, supabase, calendar_ids)
+    await _stop_and_clear(client, supabase, channels)
+
+
+def sweep_watch_channels(supabase: Client) -> int:
+    now = datetime.now(timezone.utc).isoformat()
+    expired = [
+        *all_rows(
+            supabase.table("calendar_sync_state")
+            .update(CLEARED_CHANNEL)
+            .lt("webhook_expires_at", now)
+            .execute()
+            .data
+        ),
+        *all_rows(
+            supabase.table(ACCOUNT_SYNC_STATE_TABLE)
+            .update(CLEARED_CHANNEL)
+            .lt("webhook_expires_at", now)
+            .execute()
+            .data
+        ),
+    ]
+    orphaned = all_rows(
+        supabase.table("calendar_sync_state")
+        .select("google_calendar_id, google_calendars(id)")
+        .is_("google_calendars", "null")
+        .execute()
+        .data
+    )
+    if orphaned:
+        (
+            supabase.table("calendar_sync_state")
+            .delete()
+            .in_("google_calendar_id", [row["google_calendar_id"] for row in orphaned])
+            .execute()
+        )
+    return len(expired) + len(orphaned)
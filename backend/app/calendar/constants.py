@@ -10,10 +10,60 @@ class GoogleCalendarConfig:
     TOKEN_REFRESH_BUFFER = timedelta(minutes=5)
     MAX_RETRIES = 5
     BASE_DELAY_SECONDS = 1.0
+    MAX_DELAY_SECONDS = 32.0
+    REQUEST_DEADLINE_SECONDS = 60.0
+    CIRCUIT_FAILURE_THRESHOLD = 5
+    CIRCUIT_COOLDOWN_SECONDS = 120
+    CIRCUIT_FAILURE_WINDOW_SECONDS = 60
     MAX_CONCURRENT_PER_ACCOUNT = 3
+    REQUEST_LEASE_SECONDS = 300
+    REFRESH_LEASE_SECONDS = 60
     REQUEST_TIMEOUT = httpx.Timeout(30.0, connect=10.0)
     QUOTA_ERROR_REASONS = frozenset({
         "userRateLimitExceeded",
         "rateLimitExceeded",
         "quotaExceeded",
     })
+
+
+class CalDAVConfig:
+    REQUEST_TIMEOUT = httpx.Timeout(30.0, connect=10.0)
+    MULTIGET_BATCH_SIZE = 100
+    MAX_REDIRECTS = 3
+
+
+class MicrosoftGraphConfig:
+    SCOPES = "offline_access User.Read Calendars.ReadWrite"
+    TOKEN_REFRESH_BUFFER = timedelta(minutes=5)
+    REFRESH_LEASE_SECONDS = 60
+    MAX_RETRIES = 4
+    BASE_DELAY_SECONDS = 1.0
+    MAX_DELAY_SECONDS = 30.0
+    REQUEST_DEADLINE_SECONDS = 60.0
+    PAGE_SIZE = 100
+    SUBSCRIPTION_TTL = timedelta(days=6)
+    OAUTH_STATE_TTL_SECONDS = 600
+    UNBOUNDED_WINDOW = timedelta(days=3650)
+    REQUEST_TIMEOUT = httpx.Timeout(30.0, connect=10.0)
+
+
+class BackfillConfig:
+    PAGES_PER_RUN = 5
+
+
+class SyncHealthConfig:
+    FAILING_AFTER_FAILURES = 3
+    STALE_AFTER = timedelta(hours=1)
+
+
+class SyncSchedulerConfig:
+    STARTUP_DELAY_SECONDS = 10
+    TICK_SECONDS = 30
+    SEED_INTERVAL_SECONDS = 600
+    CHANNEL_SWEEP_INTERVAL_SECONDS = 3600
+    CALENDAR_LIST_SYNC_INTERVAL_SECONDS = 3600
+    MAX_CONCURRENT_CALENDAR_LIST_SYNCS = 3
+    MAX_CONCURRENT_JOBS = 5
+    JOB_TIMEOUT_SECONDS = 300
+    JOB_LEASE_SECONDS = 600
+    RETRY_BASE_SECONDS = 60
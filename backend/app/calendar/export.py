@@ -0,0 +1,30 @@
+from app.calendar.helpers import row_to_event
+from app.calendar.ical import events_to_ical, write_time
+from app.core.db_utils import Row
+
+
+def _original_start(event: dict) -> str | None:
+    original = event.get("originalStartTime") or {}
+    return original.get("dateTime") or original.get("date")
+
+
+def export_ical(rows: list[Row], calendar_name: str | None = None) -> str:
+    """Serialize events rows to iCalendar, folding cancelled occurrences into their master's EXDATEs."""
+    events: dict[tuple[str, str | None], dict] = {}
+    by_row: dict[tuple[str, str], dict] = {}
+    cancelled: list[Row] = []
+    for row in sorted(rows, key=lambda row: (row.get("recurringEventId") is not None, str(row["id"]))):
+        if row.get("recurringEventId") and row.get("status") == "cancelled":
+            cancelled.append(row)
+            continue
+        event = row_to_event(row)
+        uid = event.get("iCalUID") or event.get("recurringEventId") or event["id"]
+        # The same invitation can sit in several calendars; a file may only hold it once.
+        by_row[(row["googleCalendarId"], row["googleEventId"])] = events.setdefault((uid, _original_start(event)), event)
+
+    for row in cancelled:
+        master = by_row.get((row["googleCalendarId"], row["recurringEventId"]))
+        if master is not None and master.get("recurrence") and row.get("originalStartTime"):
+            exdate = write_time("EXDATE", row["originalStartTime"]).line()
+            master["recurrence"] = [*master["recurrence"], exdate]
+    return events_to_ical(list(events.values()), calendar_name)
@@ -1,7 +1,9 @@
 import asyncio
+import json
 import logging
-from collections import OrderedDict
-from datetime import datetime, timedelta, timezone
+import time
+import uuid
+from datetime import datetime, timezone
 from enum import Enum
 from urllib.parse import quote
 
@@ -10,125 +12,84 @@ from supabase import Client
 
 from app.calendar.constants import GoogleCalendarConfig
 from app.calendar.helpers import GoogleAPIError
+from app.calendar.oauth_client import OAuthClient
+from app.calendar.quota import RequestPriority, get_quota_manager
+from app.calendar.retry import DEFAULT_RETRY_POLICY, RetryPolicy, google_circuit_breaker, parse_retry_after
 from app.config import get_settings
-from app.models.event import Event, EventPatch
+from app.core.coordination import get_coordination
+from app.models.event import BatchOperation, Event, EventPatch
 
 logger = logging.getLogger(__name__)
 settings = get_settings()
 
-MAX_CACHED_ACCOUNTS = 100
-CACHE_CLEANUP_THRESHOLD = 150
 
-_account_semaphores: OrderedDict[str, asyncio.Semaphore] = OrderedDict()
-_refresh_locks: OrderedDict[str, asyncio.Lock] = OrderedDict()
-_dict_lock: asyncio.Lock = asyncio.Lock()
-
-
-def _cleanup_cache(cache: OrderedDict, max_size: int, check_active=None):
-    to_remove = []
-    for key, obj in cache.items():
-        if len(cache) - len(to_remove) <= max_size:
-            break
-        if check_active and check_active(obj):
-            continue
-        to_remove.append(key)
-    for key in to_remove:
-        del cache[key]
-
-
-async def _get_cached(cache: OrderedDict, key: str, factory, check_active=None):
-    async with _dict_lock:
-        if key in cache:
-            cache.move_to_end(key)
-            return cache[key]
-        if len(cache) >= CACHE_CLEANUP_THRESHOLD:
-            _cleanup_cache(cache, MAX_CACHED_ACCOUNTS, check_active=check_active)
-        obj = factory()
-        cache[key] = obj
-        return obj
-
-
-async def get_account_semaphore(google_account_id: str) -> asyncio.Semaphore:
-    return await _get_cached(
-        _account_semaphores, google_account_id,
-        lambda: asyncio.Semaphore(GoogleCalendarConfig.MAX_CONCURRENT_PER_ACCOUNT),
-        check_active=lambda sem: sem._value < GoogleCalendarConfig.MAX_CONCURRENT_PER_ACCOUNT,
-    )
-
-
-async def get_refresh_lock(google_account_id: str) -> asyncio.Lock:
-    return await _get_cached(
-        _refresh_locks, google_account_id,
-        lambda: asyncio.Lock(),
-        check_active=lambda lock: lock.locked(),
-    )
-
-
-def get_tokens(supabase: Client, user_id: str, google_account_id: str) -> dict[str, str]:
-    row = (
-        supabase
-        .table("google_account_tokens")
-        .select("access_token, refresh_token, expires_at, google_accounts!inner(user_id)")
-        .eq("google_account_id", google_account_id)
-        .eq("google_accounts.user_id", user_id)
-        .maybe_single()
-        .execute()
-        .data
-    )
-    if row is not None:
-        return {
-            "access_token": str(row["access_token"]),
-            "refresh_token": row["refresh_token"],
-            "expires_at": str(row["expires_at"]),
-        }
-    raise ValueError("Google account tokens not found")
+class APIBaseURL(Enum):
+    CALENDAR = "GOOGLE_CALENDAR_API_URL"
+    CALENDAR_BATCH = "GOOGLE_CALENDAR_BATCH_URL"
+    PEOPLE = "GOOGLE_PEOPLE_API_URL"
+    CLOUD_IDENTITY = "GOOGLE_CLOUD_IDENTITY_API_URL"
+    OAUTH = "GOOGLE_OAUTH_URL"
 
+    @property
+    def url(self) -> str:
+        return str(getattr(get_settings(), self.value)).rstrip("/")
 
-class APIBaseURL(Enum):
-    CALENDAR = "https://www.googleapis.com/calendar/v3"
-    PEOPLE = "https://people.googleapis.com/v1"
-    CLOUD_IDENTITY = "https://cloudidentity.googleapis.com/v1"
-    OAUTH = "https://oauth2.googleapis.com"
 
+class GoogleAPIClient(OAuthClient):
+    error_class = GoogleAPIError
+    refresh_lock_prefix = "google-token-refresh"
+    token_refresh_buffer = GoogleCalendarConfig.TOKEN_REFRESH_BUFFER
+    refresh_lease_seconds = GoogleCalendarConfig.REFRESH_LEASE_SECONDS
 
-class GoogleAPIClient:
-    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, google_account_id: str):
+    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, google_account_id: str, retry_policy: RetryPolicy = DEFAULT_RETRY_POLICY, priority: RequestPriority = RequestPriority.INTERACTIVE):
         self.supabase = supabase
         self.http = http
         self.user_id = user_id
         self.google_account_id = google_account_id
+        self.retry_policy = retry_policy
+        self.priority = priority
+
+    async def _request(self, method: str, base_url: APIBaseURL, endpoint: str, params: dict | None = None, json: dict | None = None, extra_headers: dict | None = None, content: str | None = None, quota_cost: int = 1):
+        if await google_circuit_breaker.is_open(self.google_account_id):
+            raise GoogleAPIError(503, "Google API paused for this account after repeated failures", retryable=True)
 
-    async def _request(self, method: str, base_url: APIBaseURL, endpoint: str, params: dict | None = None, json: dict | None = None):
-        semaphore = await get_account_semaphore(self.google_account_id)
+        policy = self.retry_policy
+        deadline = time.monotonic() + policy.deadline_seconds
         had_401 = False
         last_error: GoogleAPIError | None = None
-        async with semaphore:
-            for attempt in range(GoogleCalendarConfig.MAX_RETRIES):
+        async with get_coordination().semaphore(
+            f"google-account:{self.google_account_id}",
+            GoogleCalendarConfig.MAX_CONCURRENT_PER_ACCOUNT,
+            GoogleCalendarConfig.REQUEST_LEASE_SECONDS,
+        ):
+            for attempt in range(policy.max_attempts):
                 token = await self._get_valid_access_token()
-                headers = {"Authorization": f"Bearer {token}"}
+                headers = {"Authorization": f"Bearer {token}", **(extra_headers or {})}
+                retry_after: float | None = None
+                await get_quota_manager().acquire(self.priority, quota_cost)
 
                 try:
-                    response = await self.http.request(method, base_url.value + endpoint, params=params, json=json, headers=headers)
+                    response = await self.http.request(method, base_url.url + endpoint, params=params, json=json, content=content, headers=headers)
                 except httpx.TimeoutException:
-                    last_error = GoogleAPIError(504, "Request timed out")
-                    await asyncio.sleep(2 ** attempt)
+                    last_error = GoogleAPIError(504, "Request timed out", retryable=True)
+                    if not await self._wait_before_retry(attempt, None, deadline):
+                        break
                     continue
                 except httpx.NetworkError:
-                    last_error = GoogleAPIError(503, "Network error")
-                    await asyncio.sleep(2 ** attempt)
+                    last_error = GoogleAPIError(503, "Network error", retryable=True)
+                    if not await self._wait_before_retry(attempt, None, deadline):
+                        break
                     continue
 
                 status = response.status_code
 
                 if 200 <= status < 300:
+                    if content is not None:
+                        return response
                     return {} if status == 204 else response.json()
 
                 if status == 401:
-                    if had_401:
-                        self._clear_refresh_token()
-                        raise GoogleAPIError(401, "Token expired or revoked")
-                    tokens = await asyncio.to_thread(get_tokens, self.supabase, self.user_id, self.google_account_id)
-                    await self._refresh_access_token(tokens["refresh_token"])
+                    await self._reauthorize(had_401)
                     had_401 = True
                     continue
 
@@ -136,22 +97,28 @@ class GoogleAPIClient:
                     errors = response.json().get("error", {}).get("errors", [])
                     reason = errors[0].get("reason") if errors else None
                     if reason in GoogleCalendarConfig.QUOTA_ERROR_REASONS:
-                        last_error = GoogleAPIError(403, f"Quota exceeded: {reason}")
-                        await asyncio.sleep(2 ** attempt)
+                        last_error = GoogleAPIError(403, f"Quota exceeded: {reason}", retryable=True)
+                        retry_after = parse_retry_after(response.headers.get("Retry-After"))
+                        if await google_circuit_breaker.record_failure(self.google_account_id):
+                            break
+                        if not await self._wait_before_retry(attempt, retry_after, deadline):
+                            break
                         continue
                     raise GoogleAPIError(403, f"Access forbidden: {reason}" if reason else "Access forbidden")
 
                 if status == 410:
                     raise GoogleAPIError(410, "Sync token expired")
 
-                if status == 429:
-                    last_error = GoogleAPIError(429, "Rate limited")
-                    await asyncio.sleep(2 ** attempt)
-                    continue
+                if status == 412:
+                    raise GoogleAPIError(412, "Event was modified by another client")
 
-                if status >= 500:
-                    last_error = GoogleAPIError(status, "Google server error")
-                    await asyncio.sleep(2 ** attempt)
+                if status == 429 or status >= 500:
+                    last_error = GoogleAPIError(status, "Rate limited" if status == 429 else "Google server error", retryable=True)
+                    retry_after = parse_retry_after(response.headers.get("Retry-After"))
+                    if await google_circuit_breaker.record_failure(self.google_account_id):
+                        break
+                    if not await self._wait_before_retry(attempt, retry_after, deadline):
+                        break
                     continue
 
                 raise GoogleAPIError(status, response.text[:200])
@@ -159,23 +126,57 @@ class GoogleAPIClient:
             assert last_error is not None
             raise last_error
 
-    async def fetch_calendars(self):
-        return await self._request("GET", APIBaseURL.CALENDAR, "/users/me/calendarList")
+    @property
+    def token_account_id(self) -> str:
+        return self.google_account_id
+
+    def _token_request(self, refresh_token: str) -> tuple[str, dict]:
+        return APIBaseURL.OAUTH.url + "/token", {
+            "client_id": settings.GOOGLE_CLIENT_ID,
+            "client_secret": settings.GOOGLE_CLIENT_SECRET,
+            "refresh_token": refresh_token,
+            "grant_type": "refresh_token",
+        }
+
+    async def fetch_calendar_list(self, sync_token: str | None = None):
+        params = {"showDeleted": "true", "showHidden": "true"}
+        if sync_token:
+            params["syncToken"] = sync_token
+        while True:
+            response = await self._request("GET", APIBaseURL.CALENDAR, "/users/me/calendarList", params=params)
+            page_token = response.get("nextPageToken")
+
+            yield {
+                "items": response.get("items", []),
+                "next_sync_token": response.get("nextSyncToken") if not page_token else None,
+            }
+
+            if not page_token:
+                break
+            params["pageToken"] = page_token
 
     async def create_event(self, calendar_id: str, event: Event):
         encoded = quote(calendar_id, safe="")
-        body = event.model_dump(exclude_none=True, exclude={"color", "calendarId", "completed"})
-        if "colorId" in body and body["colorId"] not in {str(i) for i in range(1, 13)}:
-            del body["colorId"]
-        return await self._request("POST", APIBaseURL.CALENDAR, f"/calendars/{encoded}/events", json=body)
-
-    async def fetch_events(self, calendar_id: str, page_token: str | None = None, sync_token: str | None = None):
+        return await self._request("POST", APIBaseURL.CALENDAR, f"/calendars/{encoded}/events", json=_event_body(event))
+
+    async def fetch_events(
+        self,
+        calendar_id: str,
+        page_token: str | None = None,
+        sync_token: str | None = None,
+        time_min: datetime | None = None,
+        time_max: datetime | None = None,
+    ):
         encoded = quote(calendar_id, safe="")
         params = {
             "singleEvents": "false",
             "showDeleted": "true",
             "maxResults": 1000,
         }
+        if time_min:
+            params["timeMin"] = time_min.isoformat()
+        if time_max:
+            params["timeMax"] = time_max.isoformat()
         while True:
             if page_token:
                 params["pageToken"] = page_token
@@ -194,22 +195,69 @@ class GoogleAPIClient:
             if not page_token:
                 break
 
-    async def edit_event(self, calendar_id: str, event_id: str, event: EventPatch):
-        body = event.model_dump(exclude_none=True, exclude={"color", "calendarId", "completed"})
-        if "colorId" in body and body["colorId"] not in {str(i) for i in range(1, 13)}:
-            del body["colorId"]
-        for field in ("start", "end"):
-            if field in body:
-                if "dateTime" in body[field]:
-                    body[field]["date"] = None
-                elif "date" in body[field]:
-                    body[field]["dateTime"] = None
+    async def get_event(self, calendar_id: str, event_id: str):
+        encoded = quote(calendar_id, safe="")
+        return await self._request("GET", APIBaseURL.CALENDAR, f"/calendars/{encoded}/events/{quote(event_id, safe='')}")
+
+    async def insert_event(self, calendar_id: str, body: dict):
+        encoded = quote(calendar_id, safe="")
+        return await self._request(
+            "POST", APIBaseURL.CALENDAR, f"/calendars/{encoded}/events",
+            params={"conferenceDataVersion": 1}, json=body,
+        )
+
+    async def move_event(self, calendar_id: str, event_id: str, destination_calendar_id: str):
         encoded = quote(calendar_id, safe="")
-        return await self._request("PATCH", APIBaseURL.CALENDAR, f"/calendars/{encoded}/events/{quote(event_id, safe='')}", json=body)
+        return await self._request(
+            "POST", APIBaseURL.CALENDAR, f"/calendars/{encoded}/events/{quote(event_id, safe='')}/move",
+            params={"destination": destination_calendar_id},
+        )
+
+    async def edit_event(self, calendar_id: str, event_id: str, event: EventPatch, etag: str | None = None):
+        encoded = quote(calendar_id, safe="")
+        return await self._request(
+            "PATCH", APIBaseURL.CALENDAR, f"/calendars/{encoded}/events/{quote(event_id, safe='')}",
+            json=_patch_body(event), extra_headers={"If-Match": etag} if etag else None,
+        )
 
-    async def delete_event(self, calendar_id: str, event_id: str):
+    async def delete_event(self, calendar_id: str, event_id: str, etag: str | None = None):
         encoded_calendar_id = quote(calendar_id, safe="")
-        await self._request("DELETE", APIBaseURL.CALENDAR, f"/calendars/{encoded_calendar_id}/events/{quote(event_id, safe='')}")
+        await self._request(
+            "DELETE", APIBaseURL.CALENDAR, f"/calendars/{encoded_calendar_id}/events/{quote(event_id, safe='')}",
+            extra_headers={"If-Match": etag} if etag else None,
+        )
+
+    async def batch_events(self, calendar_id: str, operations: list[BatchOperation]) -> list[dict]:
+        boundary = f"batch_{uuid.uuid4().hex}"
+        encoded = quote(calendar_id, safe="")
+        parts = []
+        for index, operation in enumerate(operations):
+            if operation.method == "create":
+                method, path, body = "POST", f"/calendars/{encoded}/events", _event_body(operation.event)
+            elif operation.method == "update":
+                method, path, body = "PATCH", f"/calendars/{encoded}/events/{quote(operation.eventId, safe='')}", _patch_body(operation.patch)
+            else:
+                method, path, body = "DELETE", f"/calendars/{encoded}/events/{quote(operation.eventId, safe='')}", None
+            lines = [
+                f"--{boundary}",
+                "Content-Type: application/http",
+                f"Content-ID: <item-{index}>",
+                "",
+                f"{method} /calendar/v3{path}",
+            ]
+            if operation.etag:
+                lines.append(f"If-Match: {operation.etag}")
+            if body is not None:
+                lines += ["Content-Type: application/json", "", json.dumps(body)]
+            parts.append("\r\n".join(lines) + "\r\n")
+        payload = "\r\n".join(parts) + f"\r\n--{boundary}--\r\n"
+
+        response = await self._request(
+            "POST", APIBaseURL.CALENDAR_BATCH, "",
+            content=payload, extra_headers={"Content-Type": f"multipart/mixed; boundary={boundary}"},
+            quota_cost=len(operations),
+        )
+        return parse_batch_response(response.headers.get("Content-Type", ""), response.text, len(operations))
 
     async def fetch_contacts(self):
         contacts = []
@@ -280,10 +328,16 @@ class GoogleAPIClient:
 
     async def create_watch_channel(self, calendar_external_id: str, webhook_url: str, channel_id: str, channel_token: str):
         encoded = quote(calendar_external_id, safe="")
+        return await self._watch(f"/calendars/{encoded}/events/watch", webhook_url, channel_id, channel_token)
+
+    async def create_calendar_list_watch_channel(self, webhook_url: str, channel_id: str, channel_token: str):
+        return await self._watch("/users/me/calendarList/watch", webhook_url, channel_id, channel_token)
+
+    async def _watch(self, endpoint: str, webhook_url: str, channel_id: str, channel_token: str):
         response = await self._request(
             "POST",
             APIBaseURL.CALENDAR,
-            f"/calendars/{encoded}/events/watch",
+            endpoint,
             json={
                 "id": channel_id,
                 "type": "web_hook",
@@ -297,53 +351,61 @@ class GoogleAPIClient:
             "expires_at": datetime.fromtimestamp(expiration_ms / 1000, tz=timezone.utc),
         }
 
-    def _clear_refresh_token(self):
-        self.supabase.table("google_account_tokens").update({"refresh_token": None}).eq("google_account_id", self.google_account_id).execute()
-
-    async def _get_valid_access_token(self):
-        tokens = await asyncio.to_thread(get_tokens, self.supabase, self.user_id, self.google_account_id)
-        expires_at = datetime.fromisoformat(tokens["expires_at"].replace("Z", "+00:00"))
-
-        if expires_at >= datetime.now(timezone.utc) + GoogleCalendarConfig.TOKEN_REFRESH_BUFFER:
-            return tokens["access_token"]
-
-        lock = await get_refresh_lock(self.google_account_id)
-        async with lock:
-            tokens = await asyncio.to_thread(get_tokens, self.supabase, self.user_id, self.google_account_id)
-            expires_at = datetime.fromisoformat(tokens["expires_at"].replace("Z", "+00:00"))
-            if expires_at < datetime.now(timezone.utc) + GoogleCalendarConfig.TOKEN_REFRESH_BUFFER:
-                if not tokens["refresh_token"]:
-                    self._clear_refresh_token()
-                    raise GoogleAPIError(401, "Missing refresh token")
-                return await self._refresh_access_token(tokens["refresh_token"])
-            return tokens["access_token"]
-
-    async def _refresh_access_token(self, refresh_token: str):
-        data = {
-            "client_id": settings.GOOGLE_CLIENT_ID,
-            "client_secret": settings.GOOGLE_CLIENT_SECRET,
-            "refresh_token": refresh_token,
-            "grant_type": "refresh_token",
-        }
-        response = await self.http.post(APIBaseURL.OAUTH.value + "/token", data=data)
-
-        if response.status_code != 200:
-            self._clear_refresh_token()
-            raise GoogleAPIError(401, "Failed to refresh token")
-
-        token_data = response.json()
-        access_token = token_data["access_token"]
-        new_refresh_token = token_data.get("refresh_token")
-        expires_in = token_data.get("expires_in", 3600)
-        expires_at = datetime.now(timezone.utc) + timedelta(seconds=expires_in)
-        
-        self.supabase.table("google_account_tokens").update({
-            "access_token": access_token,
-            "expires_at": expires_at.isoformat(),
-            **({"refresh_token": new_refresh_token} if new_refresh_token else {}),
-        }).eq("google_account_id", self.google_account_id).execute()
-
-        return access_token
+    async def stop_watch_channel(self, channel_id: str, resource_id: str):
+        await self._request(
+            "POST",
+            APIBaseURL.CALENDAR,
+            "/channels/stop",
+            json={"id": channel_id, "resourceId": resource_id},
+        )
+
+
+def _event_body(event: Event | EventPatch) -> dict:
+    body = event.model_dump(exclude_none=True, exclude={"color", "calendarId", "completed"})
+    if "colorId" in body and body["colorId"] not in {str(i) for i in range(1, 13)}:
+        del body["colorId"]
+    return body
+
+
+def _patch_body(event: EventPatch) -> dict:
+    body = _event_body(event)
+    for field in ("start", "end"):
+        if field in body:
+            if "dateTime" in body[field]:
+                body[field]["date"] = None
+            elif "date" in body[field]:
+                body[field]["dateTime"] = None
+    return body
+
+
+def parse_batch_response(content_type: str, text: str, expected: int) -> list[dict]:
+    boundary = content_type.partition("boundary=")[2].strip().strip('"')
+    if not boundary:
+        raise GoogleAPIError(502, "Malformed batch response")
+
+    results: list[dict] = [{"status": 500, "body": None} for _ in range(expected)]
+    for part in text.replace("\r\n", "\n").split(f"--{boundary}"):
+        part = part.strip()
+        if not part or part == "--":
+            continue
+        outer_headers, _, http_response = part.partition("\n\n")
+        content_id = next(
+            (line.split(":", 1)[1].strip() for line in outer_headers.split("\n") if line.lower().startswith("content-id:")),
+            "",
+        )
+        index_text = content_id.strip("<>").rpartition("item-")[2]
+        if not index_text.isdigit() or int(index_text) >= expected:
+            continue
+
+        head, _, body = http_response.partition("\n\n")
+        status_line = head.split("\n", 1)[0].split(" ")
+        status = int(status_line[1]) if len(status_line) > 1 and status_line[1].isdigit() else 500
+        try:
+            parsed = json.loads(body) if body.strip() else None
+        except ValueError:
+            parsed = None
+        results[int(index_text)] = {"status": status, "body": parsed}
+    return results
 
 
 async def proxy_photo(http: httpx.AsyncClient, url: str) -> tuple[bytes, str]:
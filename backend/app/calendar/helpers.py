@@ -1,5 +1,6 @@
 import json
 import logging
+from datetime import datetime, timedelta, timezone
 
 from supabase import Client
 
@@ -8,19 +9,25 @@ from app.core.db_utils import Row, all_rows, first_row
 logger = logging.getLogger(__name__)
 
 
-class GoogleAPIError(Exception):
+class ProviderError(Exception):
+    label = "Calendar provider"
+
     def __init__(self, status_code: int, message: str, retryable: bool = False):
         self.status_code = status_code
         self.message = message
         self.retryable = retryable
-        super().__init__(f"Google API Error {status_code}: {message}")
+        super().__init__(f"{self.label} Error {status_code}: {message}")
+
+
+class GoogleAPIError(ProviderError):
+    label = "Google API"
 
 
 def get_google_calendar(supabase: Client, calendar_id: str, user_id: str) -> Row | None:
     query = (
         supabase
         .table("google_calendars")
-        .select("*, google_accounts!inner(user_id)")
+        .select("*, google_accounts!inner(user_id, provider)")
         .eq("id", calendar_id)
     )
     query = query.eq("google_accounts.user_id", user_id)
@@ -44,34 +51,97 @@ def get_google_accounts_for_user(supabase: Client, user_id: str) -> list[Row]:
     result = (
         supabase
         .table("google_accounts")
-        .select("id, email, name, created_at, google_account_tokens(refresh_token)")
+        .select("id, email, name, provider, created_at, google_account_tokens(refresh_token)")
         .eq("user_id", user_id)
         .execute()
     )
     accounts = all_rows(result.data)
     for account in accounts:
         tokens = account.pop("google_account_tokens", None)
-        account["needs_reauth"] = not tokens or tokens.get("refresh_token") is None
+        uses_oauth = account.get("provider") in (None, "google", "microsoft")
+        account["needs_reauth"] = uses_oauth and (not tokens or tokens.get("refresh_token") is None)
     return accounts
 
 
+def calendar_list_row(google_account_id: str, calendar: dict) -> Row:
+    return {
+        "google_account_id": google_account_id,
+        "google_calendar_id": calendar["id"],
+        "name": calendar.get("summary", ""),
+        "color": calendar.get("backgroundColor"),
+        "is_primary": calendar.get("primary", False),
+        "access_role": calendar.get("accessRole", "reader"),
+    }
+
+
+def parse_event_time(value: dict | None) -> datetime | None:
+    if not value:
+        return None
+    if value.get("dateTime"):
+        parsed = datetime.fromisoformat(str(value["dateTime"]).replace("Z", "+00:00"))
+        return parsed if parsed.tzinfo else parsed.replace(tzinfo=timezone.utc)
+    if value.get("date"):
+        return datetime.fromisoformat(str(value["date"])).replace(tzinfo=timezone.utc)
+    return None
+
+
+def parse_ical_datetime(raw: str) -> datetime | None:
+    raw = raw.strip()
+    for fmt in ("%Y%m%dT%H%M%SZ", "%Y%m%dT%H%M%S", "%Y%m%d"):
+        try:
+            return datetime.strptime(raw, fmt).replace(tzinfo=timezone.utc)
+        except ValueError:
+            continue
+    return None
+
+
+def recurrence_end(recurrence: list[str] | None, start: datetime | None, end: datetime | None) -> datetime | None:
+    if not recurrence or start is None:
+        return None
+    last_start: datetime | None = None
+    for line in recurrence:
+        if line.startswith("RRULE:"):
+            parts = dict(p.split("=", 1) for p in line[6:].split(";") if "=" in p)
+            until = parse_ical_datetime(parts["UNTIL"]) if "UNTIL" in parts else None
+            if until is None:
+                return None
+            last_start = max(last_start, until) if last_start else until
+        elif line.startswith("RDATE"):
+            for raw in line.split(":", 1)[-1].split(","):
+                rdate = parse_ical_datetime(raw)
+                if rdate:
+                    last_start = max(last_start, rdate) if last_start else rdate
+    if last_start is None:
+        return None
+    duration = (end - start) if end else timedelta(0)
+    return last_start + duration
+
+
+def _isoformat(value: datetime | None) -> str | None:
+    return value.isoformat() if value else None
+
+
 def transform_events(
     events: list[dict],
     google_calendar_id: str,
     google_account_id: str,
     calendar_color: str | None = None,
+    source: str = "google",
 ) -> list[dict]:
     transformed = []
+    synced_at = datetime.now(timezone.utc).isoformat()
 
     for event in events:
         start = event.get("start") or event.get("originalStartTime") or {}
         end = event.get("end") or {}
+        start_at = parse_event_time(start)
+        end_at = parse_event_time(end) or start_at
 
         transformed.append({
             "googleEventId": event["id"],
             "googleCalendarId": google_calendar_id,
             "googleAccountId": google_account_id,
-            "source": "google",
+            "source": source,
             "summary": event.get("summary", "(No title)"),
             "description": event.get("description"),
             "location": event.get("location"),
@@ -93,10 +163,30 @@ def transform_events(
             "etag": event.get("etag"),
             "createdAt": event.get("created"),
             "updatedAt": event.get("updated"),
+            "startAt": _isoformat(start_at),
+            "endAt": _isoformat(end_at),
+            "originalStartAt": _isoformat(parse_event_time(event.get("originalStartTime"))),
+            "recurrenceEndAt": _isoformat(recurrence_end(event.get("recurrence"), start_at, end_at)),
+            "syncedAt": synced_at,
         })
 
     return transformed
 
 
-def format_sse(event: str, data: dict) -> str:
-    return f"event: {event}\ndata: {json.dumps(data)}\n\n"
+EVENT_RESOURCE_FIELDS = (
+    "summary", "description", "location", "start", "end", "recurrence", "recurringEventId",
+    "originalStartTime", "status", "visibility", "transparency", "attendees", "organizer",
+    "colorId", "reminders", "conferenceData", "htmlLink", "iCalUID", "etag",
+)
+
+
+def row_to_event(row: Row) -> dict:
+    """The inverse of transform_events: an events row back in Google Calendar API resource shape."""
+    event = {"id": row["googleEventId"], "created": row.get("createdAt"), "updated": row.get("updatedAt")}
+    event.update({field: row.get(field) for field in EVENT_RESOURCE_FIELDS})
+    return {key: value for key, value in event.items() if value is not None}
+
+
+def format_sse(event: str, data: dict, event_id: int | None = None) -> str:
+    prefix = f"id: {event_id}\n" if event_id is not None else ""
+    return f"{prefix}event: {event}\ndata: {json.dumps(data)}\n\n"
@@ -0,0 +1,422 @@
+import re
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta, timezone
+from zoneinfo import ZoneInfo, ZoneInfoNotFoundError
+
+from app.calendar.helpers import parse_event_time
+from app.calendar.recurrence import google_instance_id, shift_time
+
+PRODID = "-//Chronos//Calendar//EN"
+MAX_LINE_OCTETS = 75
+RECURRENCE_PROPERTIES = frozenset({"RRULE", "RDATE", "EXRULE", "EXDATE"})
+ZONED_PROPERTIES = frozenset({"DTSTART", "DTEND", "RECURRENCE-ID", "RDATE", "EXDATE"})
+TIMEZONE_YEARS_AHEAD = 2
+PARTSTAT_TO_RESPONSE = {
+    "NEEDS-ACTION": "needsAction",
+    "ACCEPTED": "accepted",
+    "DECLINED": "declined",
+    "TENTATIVE": "tentative",
+}
+RESPONSE_TO_PARTSTAT = {response: partstat for partstat, response in PARTSTAT_TO_RESPONSE.items()}
+CLASS_TO_VISIBILITY = {"PUBLIC": "public", "PRIVATE": "private", "CONFIDENTIAL": "confidential"}
+STATUSES = frozenset({"confirmed", "tentative", "cancelled"})
+
+_DURATION_PATTERN = re.compile(r"^([+-])?P(?:(\d+)W)?(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:(\d+)S)?)?$")
+_ESCAPED = re.compile(r"\\([\\;,nN])")
+
+
+@dataclass
+class Property:
+    name: str
+    params: dict[str, str]
+    value: str
+
+    def line(self) -> str:
+        params = "".join(f";{key}={_quote_param(value)}" for key, value in self.params.items())
+        return f"{self.name}{params}:{self.value}"
+
+
+@dataclass
+class Component:
+    name: str
+    properties: list[Property] = field(default_factory=list)
+    components: list["Component"] = field(default_factory=list)
+
+    def get(self, name: str) -> Property | None:
+        return next((prop for prop in self.properties if prop.name == name), None)
+
+    def value(self, name: str) -> str | None:
+        prop = self.get(name)
+        return prop.value if prop else None
+
+    def add(self, name: str, value: str, params: dict[str, str] | None = None) -> None:
+        self.properties.append(Property(name, params or {}, value))
+
+    def lines(self) -> list[str]:
+        return [
+            f"BEGIN:{self.name}",
+            *(prop.line() for prop in self.properties),
+            *(line for child in self.components for line in child.lines()),
+            f"END:{self.name}",
+        ]
+
+
+def _quote_param(value: str) -> str:
+    return f'"{value}"' if any(ch in value for ch in ":;,") else value
+
+
+def _split_unquoted(text: str, separator: str, limit: int = -1) -> list[str]:
+    parts, current, quoted = [], [], False
+    for ch in text:
+        if ch == '"':
+            quoted = not quoted
+        if ch == separator and not quoted and limit != 0:
+            parts.append("".join(current))
+            current = []
+            limit -= 1
+            continue
+        current.append(ch)
+    parts.append("".join(current))
+    return parts
+
+
+def parse_property(line: str) -> Property:
+    parts = _split_unquoted(line, ":", limit=1)
+    if len(parts) != 2:
+        raise ValueError(f"Malformed content line: {line[:40]}")
+    head, value = parts
+    name, *raw_params = _split_unquoted(head, ";")
+    params = {}
+    for raw in raw_params:
+        key, separator, param = raw.partition("=")
+        if separator:
+            params[key.upper()] = param.strip('"')
+    return Property(name.upper(), params, value)
+
+
+def _unfold(text: str) -> list[str]:
+    lines: list[str] = []
+    for raw in text.replace("\r\n", "\n").replace("\r", "\n").split("\n"):
+        if raw[:1] in (" ", "\t") and lines:
+            lines[-1] += raw[1:]
+        elif raw:
+            lines.append(raw)
+    return lines
+
+
+def parse_ical(text: str) -> Component:
+    root = Component("ROOT")
+    stack = [root]
+    for line in _unfold(text):
+        try:
+            prop = parse_property(line)
+        except ValueError:
+            continue
+        if prop.name == "BEGIN":
+            component = Component(prop.value.upper())
+            stack[-1].components.append(component)
+            stack.append(component)
+        elif prop.name == "END":
+            if len(stack) > 1:
+                stack.pop()
+        else:
+            stack[-1].properties.append(prop)
+    return root
+
+
+def _vevents(component: Component) -> list[Component]:
+    found = []
+    for child in component.components:
+        if child.name == "VEVENT":
+            found.append(child)
+        elif child.name == "VCALENDAR":
+            found.extend(_vevents(child))
+    return found
+
+
+def unescape_text(value: str) -> str:
+    return _ESCAPED.sub(lambda match: "\n" if match.group(1) in "nN" else match.group(1), value)
+
+
+def escape_text(value: str) -> str:
+    return value.replace("\\", "\\\\").replace(";", "\\;").replace(",", "\\,").replace("\r\n", "\\n").replace("\n", "\\n")
+
+
+def _zone(name: str | None) -> ZoneInfo | None:
+    if not name:
+        return None
+    try:
+        return ZoneInfo(name)
+    except (ZoneInfoNotFoundError, ValueError):
+        return None
+
+
+def _read_time(prop: Property | None) -> dict | None:
+    if prop is None:
+        return None
+    value = prop.value.strip()
+    if prop.params.get("VALUE") == "DATE" or len(value) == 8:
+        return {"date": f"{value[:4]}-{value[4:6]}-{value[6:8]}"}
+    parsed = datetime.strptime(value.rstrip("Z"), "%Y%m%dT%H%M%S")
+    zone = None if value.endswith("Z") else _zone(prop.params.get("TZID"))
+    if zone is None:
+        return {"dateTime": parsed.replace(tzinfo=timezone.utc).isoformat()}
+    return {"dateTime": parsed.replace(tzinfo=zone).isoformat(), "timeZone": prop.params["TZID"]}
+
+
+def _read_stamp(value: str | None) -> str | None:
+    if not value:
+        return None
+    try:
+        return datetime.strptime(value.rstrip("Z"), "%Y%m%dT%H%M%S").replace(tzinfo=timezone.utc).isoformat()
+    except ValueError:
+        return None
+
+
+def write_time(name: str, value: dict) -> Property:
+    if value.get("date"):
+        return Property(name, {"VALUE": "DATE"}, str(value["date"]).replace("-", ""))
+    parsed = datetime.fromisoformat(str(value["dateTime"]).replace("Z", "+00:00"))
+    zone = _zone(value.get("timeZone"))
+    if parsed.tzinfo is None:
+        parsed = parsed.replace(tzinfo=zone or timezone.utc)
+    if zone is not None:
+        return Property(name, {"TZID": value["timeZone"]}, parsed.astimezone(zone).strftime("%Y%m%dT%H%M%S"))
+    return Property(name, {}, parsed.astimezone(timezone.utc).strftime("%Y%m%dT%H%M%SZ"))
+
+
+def _parse_duration(value: str | None) -> timedelta | None:
+    match = _DURATION_PATTERN.match(value.strip()) if value else None
+    if not match:
+        return None
+    sign, weeks, days, hours, minutes, seconds = match.groups()
+    duration = timedelta(
+        weeks=int(weeks or 0), days=int(days or 0),
+        hours=int(hours or 0), minutes=int(minutes or 0), seconds=int(seconds or 0),
+    )
+    return -duration if sign == "-" else duration
+
+
+def _mailto(value: str) -> str:
+    return value[7:] if value.lower().startswith("mailto:") else value
+
+
+def _person(prop: Property) -> dict:
+    person = {"email": _mailto(prop.value)}
+    if prop.params.get("CN"):
+        person["displayName"] = prop.params["CN"]
+    return person
+
+
+def _reminders(vevent: Component) -> dict | None:
+    overrides = []
+    for alarm in vevent.components:
+        if alarm.name != "VALARM":
+            continue
+        trigger = _parse_duration(alarm.value("TRIGGER"))
+        if trigger is not None and trigger <= timedelta(0):
+            overrides.append({"method": "popup", "minutes": int(-trigger.total_seconds() // 60)})
+    return {"useDefault": False, "overrides": overrides} if overrides else None
+
+
+def vevent_to_event(vevent: Component, event_id: str) -> dict:
+    start = _read_time(vevent.get("DTSTART"))
+    end = _read_time(vevent.get("DTEND"))
+    if end is None and start is not None:
+        duration = _parse_duration(vevent.value("DURATION"))
+        end = shift_time(start, duration or (timedelta(days=1) if start.get("date") else timedelta(0)))
+
+    attendees = []
+    for prop in vevent.properties:
+        if prop.name != "ATTENDEE":
+            continue
+        attendee = _person(prop)
+        attendee["responseStatus"] = PARTSTAT_TO_RESPONSE.get(prop.params.get("PARTSTAT", "").upper(), "needsAction")
+        if prop.params.get("ROLE", "").upper() == "OPT-PARTICIPANT":
+            attendee["optional"] = True
+        attendees.append(attendee)
+
+    organizer = vevent.get("ORGANIZER")
+    status = (vevent.value("STATUS") or "").lower()
+    event = {
+        "id": event_id,
+        "iCalUID": vevent.value("UID"),
+        "summary": unescape_text(vevent.value("SUMMARY") or "") or None,
+        "description": unescape_text(vevent.value("DESCRIPTION") or "") or None,
+        "location": unescape_text(vevent.value("LOCATION") or "") or None,
+        "start": start,
+        "end": end,
+        "recurrence": [prop.line() for prop in vevent.properties if prop.name in RECURRENCE_PROPERTIES] or None,
+        "status": status if status in STATUSES else "confirmed",
+        "transparency": "transparent" if (vevent.value("TRANSP") or "").upper() == "TRANSPARENT" else "opaque",
+        "visibility": CLASS_TO_VISIBILITY.get((vevent.value("CLASS") or "").upper(), "default"),
+        "attendees": attendees or None,
+        "organizer": _person(organizer) if organizer else None,
+        "reminders": _reminders(vevent),
+        "created": _read_stamp(vevent.value("CREATED")),
+        "updated": _read_stamp(vevent.value("LAST-MODIFIED") or vevent.value("DTSTAMP")),
+    }
+    sequence = vevent.value("SEQUENCE")
+    if sequence and sequence.isdigit():
+        event["sequence"] = int(sequence)
+
+    original = _read_time(vevent.get("RECURRENCE-ID"))
+    original_start = parse_event_time(original)
+    if original and original_start:
+        event["id"] = google_instance_id(event_id, original_start, "date" in original)
+        event["recurringEventId"] = event_id
+        event["originalStartTime"] = original
+    return {key: value for key, value in event.items() if value is not None}
+
+
+def ical_to_events(text: str, event_id: str) -> list[dict]:
+    return [vevent_to_event(vevent, event_id) for vevent in _vevents(parse_ical(text))]
+
+
+def event_to_vevent(event: dict, uid: str, stamp: datetime | None = None) -> Component:
+    vevent = Component("VEVENT")
+    vevent.add("UID", uid)
+    vevent.add("DTSTAMP", (stamp or datetime.now(timezone.utc)).strftime("%Y%m%dT%H%M%SZ"))
+    if event.get("originalStartTime"):
+        vevent.properties.append(write_time("RECURRENCE-ID", event["originalStartTime"]))
+    vevent.properties.append(write_time("DTSTART", event["start"]))
+    if event.get("end"):
+        vevent.properties.append(write_time("DTEND", event["end"]))
+    for name, key in (("SUMMARY", "summary"), ("DESCRIPTION", "description"), ("LOCATION", "location")):
+        if event.get(key):
+            vevent.add(name, escape_text(str(event[key])))
+    for line in event.get("recurrence") or []:
+        vevent.properties.append(parse_property(line))
+    if event.get("status") in STATUSES:
+        vevent.add("STATUS", event["status"].upper())
+    vevent.add("TRANSP", "TRANSPARENT" if event.get("transparency") == "transparent" else "OPAQUE")
+    if event.get("visibility") in CLASS_TO_VISIBILITY.values():
+        vevent.add("CLASS", event["visibility"].upper())
+    if event.get("sequence") is not None:
+        vevent.add("SEQUENCE", str(event["sequence"]))
+
+    organizer = event.get("organizer") or {}
+    if organizer.get("email"):
+        params = {"CN": organizer["displayName"]} if organizer.get("displayName") else {}
+        vevent.add("ORGANIZER", f"mailto:{organizer['email']}", params)
+    for attendee in event.get("attendees") or []:
+        if not attendee.get("email"):
+            continue
+        params = {"PARTSTAT": RESPONSE_TO_PARTSTAT.get(attendee.get("responseStatus", ""), "NEEDS-ACTION")}
+        if attendee.get("displayName"):
+            params["CN"] = attendee["displayName"]
+        if attendee.get("optional"):
+            params["ROLE"] = "OPT-PARTICIPANT"
+        vevent.add("ATTENDEE", f"mailto:{attendee['email']}", params)
+
+    for reminder in (event.get("reminders") or {}).get("overrides") or []:
+        alarm = Component("VALARM")
+        alarm.add("ACTION", "DISPLAY")
+        alarm.add("DESCRIPTION", "Reminder")
+        alarm.add("TRIGGER", f"-PT{int(reminder.get('minutes', 0))}M")
+        vevent.components.append(alarm)
+    return vevent
+
+
+def _fold(line: str) -> str:
+    chunks, current, size = [], "", 0
+    for ch in line:
+        width = len(ch.encode("utf-8"))
+        if size + width > MAX_LINE_OCTETS:
+            chunks.append(current)
+            current, size = " ", 1
+        current += ch
+        size += width
+    chunks.append(current)
+    return "\r\n".join(chunks)
+
+
+def _utc_offset(offset: timedelta) -> str:
+    minutes = int(offset.total_seconds()) // 60
+    sign = "-" if minutes < 0 else "+"
+    return f"{sign}{abs(minutes) // 60:02d}{abs(minutes) % 60:02d}"
+
+
+def _transitions(zone: ZoneInfo, start: datetime, end: datetime) -> list[datetime]:
+    """UTC instants at which the zone's offset changes, found day by day and narrowed to the minute."""
+    found = []
+    previous = start
+    while previous < end:
+        current = previous + timedelta(days=1)
+        if previous.astimezone(zone).utcoffset() != current.astimezone(zone).utcoffset():
+            low, high = previous, current
+            while high - low > timedelta(minutes=1):
+                middle = low + (high - low) / 2
+                if middle.astimezone(zone).utcoffset() == low.astimezone(zone).utcoffset():
+                    low = middle
+                else:
+                    high = middle
+            found.append(high.replace(second=0, microsecond=0))
+        previous = current
+    return found
+
+
+def vtimezone(name: str, start: datetime, end: datetime) -> Component | None:
+    """A VTIMEZONE spelling out every offset change between start and end as its own observance."""
+    zone = _zone(name)
+    if zone is None:
+        return None
+    component = Component("VTIMEZONE")
+    component.add("TZID", name)
+    transitions = _transitions(zone, start, end)
+    if not transitions:
+        local = start.astimezone(zone)
+        observance = Component("STANDARD")
+        observance.add("DTSTART", "19700101T000000")
+        observance.add("TZOFFSETFROM", _utc_offset(local.utcoffset()))
+        observance.add("TZOFFSETTO", _utc_offset(local.utcoffset()))
+        observance.add("TZNAME", local.tzname() or name)
+        component.components.append(observance)
+        return component
+    for instant in transitions:
+        before, after = (instant - timedelta(minutes=1)).astimezone(zone), instant.astimezone(zone)
+        observance = Component("DAYLIGHT" if after.dst() else "STANDARD")
+        observance.add("DTSTART", (instant + before.utcoffset()).strftime("%Y%m%dT%H%M%S"))
+        observance.add("TZOFFSETFROM", _utc_offset(before.utcoffset()))
+        observance.add("TZOFFSETTO", _utc_offset(after.utcoffset()))
+        observance.add("TZNAME", after.tzname() or name)
+        component.components.append(observance)
+    return component
+
+
+def _timezone_span(events: list[dict]) -> tuple[datetime, datetime]:
+    times = [
+        moment for event in events for key in ("start", "end", "originalStartTime")
+        if (moment := parse_event_time(event.get(key))) is not None
+    ]
+    now = datetime.now(timezone.utc)
+    first, last = min(times, default=now), max([*times, now])
+    # Start a year early so the observance in force on the first event is included.
+    return (
+        datetime(first.year - 1, 1, 1, tzinfo=timezone.utc),
+        datetime(last.year + TIMEZONE_YEARS_AHEAD, 1, 1, tzinfo=timezone.utc),
+    )
+
+
+def events_to_ical(events: list[dict], calendar_name: str | None = None) -> str:
+    calendar = Component("VCALENDAR")
+    calendar.add("VERSION", "2.0")
+    calendar.add("PRODID", PRODID)
+    calendar.add("CALSCALE", "GREGORIAN")
+    if calendar_name:
+        calendar.add("X-WR-CALNAME", escape_text(calendar_name))
+    stamp = datetime.now(timezone.utc)
+    vevents = []
+    for event in events:
+        uid = event.get("iCalUID") or event.get("recurringEventId") or event["id"]
+        vevents.append(event_to_vevent(event, uid, stamp))
+
+    zones = sorted({
+        prop.params["TZID"] for vevent in vevents for prop in vevent.properties
+        if prop.name in ZONED_PROPERTIES and "TZID" in prop.params
+    })
+    if zones:
+        start, end = _timezone_span(events)
+        calendar.components.extend(filter(None, (vtimezone(zone, start, end) for zone in zones)))
+    calendar.components.extend(vevents)
+    return "\r\n".join(_fold(line) for line in calendar.lines()) + "\r\n"
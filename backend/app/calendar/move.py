@@ -0,0 +1,96 @@
+import asyncio
+import logging
+
+from supabase import Client
+
+from app.calendar.helpers import ProviderError, parse_event_time
+from app.calendar.ical import write_time
+from app.calendar.providers import CalendarProvider
+from app.calendar.recurrence import google_instance_id, is_all_day
+from app.core.db_utils import Row, all_rows
+from app.models.event import EventPatch
+
+logger = logging.getLogger(__name__)
+
+WRITABLE_ROLES = ("owner", "writer")
+COPY_EXCLUDED_FIELDS = frozenset({
+    "id", "etag", "kind", "htmlLink", "iCalUID", "created", "updated",
+    "creator", "organizer", "sequence", "hangoutLink", "recurringEventId", "originalStartTime",
+})
+# Rows carry the source calendar's color when the event has none, so colorId is left behind.
+EXCEPTION_FIELDS = (
+    "summary", "description", "location", "attendees", "reminders",
+    "visibility", "transparency", "start", "end", "status",
+)
+
+
+class EventMoveError(ValueError):
+    pass
+
+
+def copy_body(event: dict) -> dict:
+    return {key: value for key, value in event.items() if key not in COPY_EXCLUDED_FIELDS}
+
+
+def _series_exceptions(supabase: Client, calendar: dict, source: str, master_id: str) -> list[Row]:
+    return all_rows(
+        supabase.table("events")
+        .select("*")
+        .eq("googleCalendarId", calendar["id"])
+        .eq("recurringEventId", master_id)
+        .eq("source", source)
+        .execute()
+        .data
+    )
+
+
+async def move_event(
+    source_client: CalendarProvider,
+    destination_client: CalendarProvider,
+    supabase: Client,
+    source: dict,
+    destination: dict,
+    event_id: str,
+    etag: str | None = None,
+) -> dict:
+    if source["id"] == destination["id"]:
+        raise EventMoveError("Event is already in this calendar")
+    if destination.get("access_role") not in WRITABLE_ROLES:
+        raise EventMoveError("Destination calendar is read-only")
+
+    event = await source_client.get_event(source["google_calendar_id"], event_id)
+    if event.get("recurringEventId"):
+        raise EventMoveError("Single occurrences cannot be moved; move the whole series instead")
+    if etag and event.get("etag") != etag:
+        raise ProviderError(412, "Event was modified by another client")
+
+    # Providers without a native move are copied and deleted like a move between accounts.
+    if source["google_account_id"] == destination["google_account_id"] and source_client.supports_move:
+        return await source_client.move_event(source["google_calendar_id"], event_id, destination["google_calendar_id"])
+
+    body = copy_body(event)
+    exceptions = []
+    if event.get("recurrence"):
+        exceptions = await asyncio.to_thread(_series_exceptions, supabase, source, source_client.source, event_id)
+        exceptions = [exc for exc in exceptions if exc.get("originalStartTime")]
+        cancelled = [exc for exc in exceptions if exc.get("status") == "cancelled"]
+        body["recurrence"] = [*body["recurrence"], *(write_time("EXDATE", exc["originalStartTime"]).line() for exc in cancelled)]
+        exceptions = [exc for exc in exceptions if exc.get("status") != "cancelled"]
+
+    copy = await destination_client.insert_event(destination["google_calendar_id"], body)
+    try:
+        for exc in exceptions:
+            original = parse_event_time(exc["originalStartTime"])
+            if original is None:
+                continue
+            fields = {field: exc[field] for field in EXCEPTION_FIELDS if exc.get(field) is not None}
+            instance_id = google_instance_id(copy["id"], original, is_all_day(event))
+            await destination_client.edit_event(destination["google_calendar_id"], instance_id, EventPatch(**fields))
+        await source_client.delete_event(source["google_calendar_id"], event_id, etag=event.get("etag"))
+    except ProviderError:
+        try:
+            await destination_client.delete_event(destination["google_calendar_id"], copy["id"])
+        except ProviderError as e:
+            logger.error("Failed to roll back copied event %s in calendar %s: %s", copy["id"], destination["id"], e.message)
+        raise
+    return copy
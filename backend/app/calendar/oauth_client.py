@@ -0,0 +1,110 @@
+import asyncio
+import time
+from datetime import datetime, timedelta, timezone
+
+import httpx
+from supabase import Client
+
+from app.calendar.helpers import ProviderError
+from app.calendar.retry import RetryPolicy
+from app.core.coordination import get_coordination
+
+
+def get_tokens(supabase: Client, user_id: str, google_account_id: str) -> dict[str, str]:
+    row = (
+        supabase
+        .table("google_account_tokens")
+        .select("access_token, refresh_token, expires_at, google_accounts!inner(user_id)")
+        .eq("google_account_id", google_account_id)
+        .eq("google_accounts.user_id", user_id)
+        .maybe_single()
+        .execute()
+        .data
+    )
+    if row is not None:
+        return {
+            "access_token": str(row["access_token"]),
+            "refresh_token": row["refresh_token"],
+            "expires_at": str(row["expires_at"]),
+        }
+    raise ValueError("Google account tokens not found")
+
+
+class OAuthClient:
+    """Token refresh and retry backoff for clients whose tokens live in google_account_tokens."""
+
+    error_class: type[ProviderError] = ProviderError
+    refresh_lock_prefix: str
+    token_refresh_buffer: timedelta
+    refresh_lease_seconds: int
+
+    supabase: Client
+    http: httpx.AsyncClient
+    user_id: str
+    retry_policy: RetryPolicy
+
+    @property
+    def token_account_id(self) -> str:
+        raise NotImplementedError
+
+    def _token_request(self, refresh_token: str) -> tuple[str, dict]:
+        """The token endpoint and form body for a refresh_token grant."""
+        raise NotImplementedError
+
+    async def _wait_before_retry(self, attempt: int, retry_after: float | None, deadline: float) -> bool:
+        if attempt + 1 >= self.retry_policy.max_attempts:
+            return False
+        delay = self.retry_policy.backoff(attempt, retry_after)
+        if time.monotonic() + delay > deadline:
+            return False
+        await asyncio.sleep(delay)
+        return True
+
+    def _clear_refresh_token(self):
+        self.supabase.table("google_account_tokens").update({"refresh_token": None}).eq("google_account_id", self.token_account_id).execute()
+
+    async def _get_valid_access_token(self) -> str:
+        tokens = await asyncio.to_thread(get_tokens, self.supabase, self.user_id, self.token_account_id)
+        expires_at = datetime.fromisoformat(tokens["expires_at"].replace("Z", "+00:00"))
+
+        if expires_at >= datetime.now(timezone.utc) + self.token_refresh_buffer:
+            return tokens["access_token"]
+
+        async with get_coordination().lock(f"{self.refresh_lock_prefix}:{self.token_account_id}", self.refresh_lease_seconds):
+            tokens = await asyncio.to_thread(get_tokens, self.supabase, self.user_id, self.token_account_id)
+            expires_at = datetime.fromisoformat(tokens["expires_at"].replace("Z", "+00:00"))
+            if expires_at < datetime.now(timezone.utc) + self.token_refresh_buffer:
+                if not tokens["refresh_token"]:
+                    self._clear_refresh_token()
+                    raise self.error_class(401, "Missing refresh token")
+                return await self._refresh_access_token(tokens["refresh_token"])
+            return tokens["access_token"]
+
+    async def _refresh_access_token(self, refresh_token: str) -> str:
+        url, data = self._token_request(refresh_token)
+        response = await self.http.post(url, data=data)
+
+        if response.status_code != 200:
+            self._clear_refresh_token()
+            raise self.error_class(401, "Failed to refresh token")
+
+        token_data = response.json()
+        access_token = token_data["access_token"]
+        new_refresh_token = token_data.get("refresh_token")
+        expires_at = datetime.now(timezone.utc) + timedelta(seconds=token_data.get("expires_in", 3600))
+
+        self.supabase.table("google_account_tokens").update({
+            "access_token": access_token,
+            "expires_at": expires_at.isoformat(),
+            **({"refresh_token": new_refresh_token} if new_refresh_token else {}),
+        }).eq("google_account_id", self.token_account_id).execute()
+
+        return access_token
+
+    async def _reauthorize(self, had_401: bool) -> None:
+        """Refresh after a 401, or give up if the freshly refreshed token was rejected as well."""
+        if had_401:
+            self._clear_refresh_token()
+            raise self.error_class(401, "Token expired or revoked")
+        tokens = await asyncio.to_thread(get_tokens, self.supabase, self.user_id, self.token_account_id)
+        await self._refresh_access_token(tokens["refresh_token"])
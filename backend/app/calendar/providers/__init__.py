@@ -0,0 +1,73 @@
+import httpx
+from supabase import Client
+
+from app.calendar.helpers import ProviderError, get_google_account
+from app.calendar.providers.base import CalendarProvider
+from app.calendar.providers.caldav import CalDAVProvider
+from app.calendar.providers.chronos import ChronosProvider
+from app.calendar.providers.google import GoogleProvider
+from app.calendar.providers.microsoft import MicrosoftProvider
+from app.calendar.quota import RequestPriority
+from app.core.db_utils import Row
+
+DEFAULT_PROVIDER = GoogleProvider.source
+
+PROVIDERS: dict[str, type[CalendarProvider]] = {}
+
+
+def register_provider(provider: type[CalendarProvider]) -> type[CalendarProvider]:
+    PROVIDERS[provider.source] = provider
+    return provider
+
+
+register_provider(GoogleProvider)
+register_provider(CalDAVProvider)
+register_provider(MicrosoftProvider)
+register_provider(ChronosProvider)
+
+
+def provider_sources() -> list[str]:
+    return list(PROVIDERS)
+
+
+def provider_source(row: Row | None) -> str:
+    if not row:
+        return DEFAULT_PROVIDER
+    account = row.get("google_accounts") or {}
+    return row.get("provider") or account.get("provider") or DEFAULT_PROVIDER
+
+
+def get_provider(
+    supabase: Client,
+    http: httpx.AsyncClient,
+    user_id: str,
+    account_id: str,
+    source: str = DEFAULT_PROVIDER,
+    priority: RequestPriority = RequestPriority.INTERACTIVE,
+) -> CalendarProvider:
+    provider = PROVIDERS.get(source)
+    if provider is None:
+        raise ProviderError(400, f"Unsupported calendar provider: {source}")
+    return provider(supabase, provider.http_client(http), user_id, account_id, priority=priority)
+
+
+def provider_for_calendar(
+    supabase: Client,
+    http: httpx.AsyncClient,
+    user_id: str,
+    calendar: Row,
+    priority: RequestPriority = RequestPriority.INTERACTIVE,
+) -> CalendarProvider:
+    return get_provider(supabase, http, user_id, calendar["google_account_id"], provider_source(calendar), priority)
+
+
+def provider_for_account(
+    supabase: Client,
+    http: httpx.AsyncClient,
+    user_id: str,
+    account_id: str,
+    priority: RequestPriority = RequestPriority.INTERACTIVE,
+) -> CalendarProvider:
+    account = get_google_account(supabase, account_id)
+    return get_provider(supabase, http, user_id, account_id, provider_source(account), priority)
+
@@ -0,0 +1,96 @@
+from abc import ABC, abstractmethod
+from collections.abc import AsyncIterator
+from datetime import datetime
+
+import httpx
+from supabase import Client
+
+from app.calendar.helpers import ProviderError, transform_events
+from app.calendar.quota import RequestPriority
+from app.models.event import BatchOperation, Event, EventPatch
+
+
+class CalendarProvider(ABC):
+    """A calendar backend. Calendars and events are exchanged in Google Calendar API resource shape."""
+
+    source: str
+    supports_push = False
+    supports_calendar_list_push = False
+    supports_move = False
+    webhook_path = "/calendar/webhook"
+
+    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, account_id: str, priority: RequestPriority = RequestPriority.INTERACTIVE):
+        self.supabase = supabase
+        self.http = http
+        self.user_id = user_id
+        self.account_id = account_id
+        self.priority = priority
+
+    @classmethod
+    def http_client(cls, http: httpx.AsyncClient) -> httpx.AsyncClient:
+        """The client this provider's requests go through, given the shared one."""
+        return http
+
+    def _unsupported(self, operation: str) -> ProviderError:
+        return ProviderError(501, f"{operation} is not supported by {self.source} calendars")
+
+    @abstractmethod
+    def fetch_calendar_list(self, sync_token: str | None = None) -> AsyncIterator[dict]:
+        ...
+
+    @abstractmethod
+    def fetch_events(
+        self,
+        calendar_id: str,
+        page_token: str | None = None,
+        sync_token: str | None = None,
+        time_min: datetime | None = None,
+        time_max: datetime | None = None,
+    ) -> AsyncIterator[dict]:
+        ...
+
+    @abstractmethod
+    async def get_event(self, calendar_id: str, event_id: str) -> dict:
+        ...
+
+    @abstractmethod
+    async def create_event(self, calendar_id: str, event: Event) -> dict:
+        ...
+
+    @abstractmethod
+    async def edit_event(self, calendar_id: str, event_id: str, event: EventPatch, etag: str | None = None) -> dict:
+        ...
+
+    @abstractmethod
+    async def delete_event(self, calendar_id: str, event_id: str, etag: str | None = None) -> None:
+        ...
+
+    async def insert_event(self, calendar_id: str, body: dict) -> dict:
+        raise self._unsupported("Copying events")
+
+    async def move_event(self, calendar_id: str, event_id: str, destination_calendar_id: str) -> dict:
+        raise self._unsupported("Moving events")
+
+    async def batch_events(self, calendar_id: str, operations: list[BatchOperation]) -> list[dict]:
+        raise self._unsupported("Batch requests")
+
+    async def create_watch_channel(self, calendar_external_id: str, webhook_url: str, channel_id: str, channel_token: str) -> dict:
+        raise self._unsupported("Push notifications")
+
+    async def create_calendar_list_watch_channel(self, webhook_url: str, channel_id: str, channel_token: str) -> dict:
+        raise self._unsupported("Push notifications")
+
+    async def stop_watch_channel(self, channel_id: str, resource_id: str) -> None:
+        raise self._unsupported("Push notifications")
+
+    async def fetch_contacts(self) -> list[dict]:
+        return []
+
+    async def search_workspace(self, query: str) -> list[dict]:
+        return []
+
+    async def list_group_members(self, group_email: str) -> list[dict]:
+        return []
+
+    def transform_events(self, events: list[dict], calendar: dict) -> list[dict]:
+        return transform_events(events, calendar["id"], calendar["google_account_id"], calendar.get("color"), source=self.source)
@@ -0,0 +1,458 @@
+"""CalDAV calendars (iCloud, Fastmail, Nextcloud, Radicale, ...).
+
+For local testing run `pip install radicale && python -m radicale --storage-filesystem-folder=/tmp/radicale --auth-type=none`
+and connect an account with POST /calendar/accounts/caldav using server_url http://localhost:5232/ and any username.
+"""
+import asyncio
+import ipaddress
+import re
+import socket
+import uuid
+from datetime import datetime, timezone
+from functools import lru_cache
+from urllib.parse import quote, unquote, urljoin, urlparse
+from xml.etree import ElementTree
+from xml.sax.saxutils import escape
+
+import httpcore
+import httpx
+from supabase import Client
+
+from app.calendar.constants import CalDAVConfig
+from app.calendar.helpers import ProviderError
+from app.calendar.ical import events_to_ical, ical_to_events, write_time
+from app.calendar.providers.base import CalendarProvider
+from app.calendar.quota import RequestPriority
+from app.calendar.recurrence import instance_original_start, materialize_instance, split_instance_id
+from app.config import get_settings
+from app.core.crypto import decrypt_secret
+from app.models.event import Event, EventPatch
+
+CREDENTIALS_TABLE = "caldav_account_credentials"
+CTAG_TOKEN_PREFIX = "ctag:"
+WRITE_PRIVILEGES = frozenset({"write", "write-content", "all"})
+NAMESPACES = {
+    "d": "DAV:",
+    "c": "urn:ietf:params:xml:ns:caldav",
+    "cs": "http://calendarserver.org/ns/",
+    "ical": "http://apple.com/ns/ical/",
+}
+XML_HEADER = '<?xml version="1.0" encoding="utf-8"?>'
+
+
+def get_caldav_credentials(supabase: Client, user_id: str, account_id: str) -> dict[str, str]:
+    row = (
+        supabase
+        .table(CREDENTIALS_TABLE)
+        .select("server_url, username, password, calendar_home_url, google_accounts!inner(user_id)")
+        .eq("google_account_id", account_id)
+        .eq("google_accounts.user_id", user_id)
+        .maybe_single()
+        .execute()
+        .data
+    )
+    if row is None:
+        raise ProviderError(401, "CalDAV credentials not found")
+    return {**row, "password": decrypt_secret(row["password"])}
+
+
+def caldav_account_key(server_url: str, username: str) -> str:
+    return f"caldav:{username}@{urlparse(server_url).netloc}"
+
+
+def _propfind(props: str) -> str:
+    return f'{XML_HEADER}<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:cs="http://calendarserver.org/ns/" xmlns:ical="http://apple.com/ns/ical/"><d:prop>{props}</d:prop></d:propfind>'
+
+
+def _responses(text: str) -> list[ElementTree.Element]:
+    try:
+        root = ElementTree.fromstring(text)
+    except ElementTree.ParseError:
+        raise ProviderError(502, "Malformed CalDAV response")
+    return root.findall("d:response", NAMESPACES)
+
+
+def _href(response: ElementTree.Element) -> str:
+    return unquote((response.findtext("d:href", "", NAMESPACES)).strip())
+
+
+def _ok_prop(response: ElementTree.Element) -> ElementTree.Element | None:
+    for propstat in response.findall("d:propstat", NAMESPACES):
+        if " 200 " in f"{propstat.findtext('d:status', '', NAMESPACES)} ":
+            return propstat.find("d:prop", NAMESPACES)
+    return None
+
+
+def _status(response: ElementTree.Element) -> int:
+    match = re.search(r"\s(\d{3})", response.findtext("d:status", "", NAMESPACES))
+    return int(match.group(1)) if match else 200
+
+
+def _ts(value: datetime) -> str:
+    return value.astimezone(timezone.utc).strftime("%Y%m%dT%H%M%SZ")
+
+
+def resource_id(href: str) -> str:
+    name = href.rstrip("/").rsplit("/", 1)[-1]
+    return name[:-4] if name.endswith(".ics") else name
+
+
+def _origin(url: str) -> tuple[str, str]:
+    parsed = urlparse(url)
+    return parsed.scheme, parsed.netloc.lower()
+
+
+async def resolve_public_address(host: str, port: int) -> str:
+    """Resolve the host and return an address to connect to, rejecting hosts with any non-public address."""
+    if get_settings().CALDAV_ALLOW_PRIVATE_HOSTS:
+        return host
+    try:
+        addresses = await asyncio.get_running_loop().getaddrinfo(host, port, type=socket.SOCK_STREAM)
+    except socket.gaierror:
+        raise ProviderError(400, "CalDAV server could not be resolved")
+    for *_, sockaddr in addresses:
+        address = ipaddress.ip_address(sockaddr[0].split("%", 1)[0])
+        if isinstance(address, ipaddress.IPv6Address) and address.ipv4_mapped:
+            address = address.ipv4_mapped
+        if not address.is_global:
+            raise ProviderError(400, "CalDAV server address is not allowed")
+    return addresses[0][4][0]
+
+
+async def ensure_public_url(url: str) -> None:
+    """Reject CalDAV URLs that resolve to loopback, private, link-local or otherwise non-public addresses."""
+    parsed = urlparse(url)
+    if parsed.scheme not in ("http", "https") or not parsed.hostname:
+        raise ProviderError(400, "Invalid CalDAV server URL")
+    if get_settings().CALDAV_ALLOW_PRIVATE_HOSTS:
+        return
+    if parsed.scheme != "https":
+        raise ProviderError(400, "CalDAV servers must use HTTPS")
+    await resolve_public_address(parsed.hostname, parsed.port or 443)
+
+
+class PublicAddressBackend(httpcore.AsyncNetworkBackend):
+    """Connects to the address that passed the public-address check instead of letting a second DNS answer pick one.
+    TLS is still negotiated and verified against the hostname, and the Host header is unchanged."""
+
+    def __init__(self):
+        self._backend = httpcore.AnyIOBackend()
+
+    async def connect_tcp(self, host: str, port: int, timeout: float | None = None, local_address: str | None = None, socket_options=None) -> httpcore.AsyncNetworkStream:
+        address = await resolve_public_address(host, port)
+        return await self._backend.connect_tcp(address, port, timeout=timeout, local_address=local_address, socket_options=socket_options)
+
+    async def connect_unix_socket(self, path: str, timeout: float | None = None, socket_options=None) -> httpcore.AsyncNetworkStream:
+        raise httpcore.ConnectError("CalDAV servers are only reached over TCP")
+
+    async def sleep(self, seconds: float) -> None:
+        await self._backend.sleep(seconds)
+
+
+class PublicAddressTransport(httpx.AsyncHTTPTransport):
+    def __init__(self):
+        super().__init__()
+        self._pool = httpcore.AsyncConnectionPool(ssl_context=httpx.create_ssl_context(), network_backend=PublicAddressBackend())
+
+
+@lru_cache
+def get_caldav_http_client() -> httpx.AsyncClient:
+    """CalDAV servers are user supplied, so they get a client of their own that only connects to public addresses."""
+    return httpx.AsyncClient(transport=PublicAddressTransport(), timeout=CalDAVConfig.REQUEST_TIMEOUT)
+
+
+async def discover_calendar_home(http: httpx.AsyncClient, server_url: str, username: str, password: str) -> dict[str, str | None]:
+    async def propfind(url: str, props: str) -> ElementTree.Element | None:
+        for _ in range(CalDAVConfig.MAX_REDIRECTS + 1):
+            await ensure_public_url(url)
+            try:
+                response = await http.request(
+                    "PROPFIND", url, content=_propfind(props), auth=(username, password),
+                    headers={"Depth": "0", "Content-Type": "application/xml; charset=utf-8"},
+                    timeout=CalDAVConfig.REQUEST_TIMEOUT, follow_redirects=False,
+                )
+            except httpx.HTTPError:
+                raise ProviderError(503, "CalDAV server unreachable", retryable=True)
+            if not response.is_redirect:
+                break
+            location = urljoin(url, response.headers.get("Location", ""))
+            # Credentials go with every request, so redirects may not leave the server they were given for.
+            if _origin(location) != _origin(url):
+                raise ProviderError(400, "CalDAV server redirected to another host")
+            url = location
+        else:
+            raise ProviderError(400, "CalDAV server redirected too many times")
+        if response.status_code == 401:
+            raise ProviderError(401, "CalDAV credentials rejected")
+        if response.status_code != 207:
+            raise ProviderError(400, "Server does not look like a CalDAV server")
+        responses = _responses(response.text)
+        return _ok_prop(responses[0]) if responses else None
+
+    prop = await propfind(server_url, "<d:current-user-principal/>")
+    principal = prop.findtext("d:current-user-principal/d:href", None, NAMESPACES) if prop is not None else None
+    principal_url = urljoin(server_url, principal.strip()) if principal else server_url
+    prop = await propfind(principal_url, "<c:calendar-home-set/><d:displayname/>")
+    home = prop.findtext("c:calendar-home-set/d:href", None, NAMESPACES) if prop is not None else None
+    if not home:
+        raise ProviderError(400, "No CalDAV calendar home found for this account")
+    display_name = prop.findtext("d:displayname", None, NAMESPACES) if prop is not None else None
+    calendar_home_url = urljoin(principal_url, home.strip())
+    await ensure_public_url(calendar_home_url)
+    return {"calendar_home_url": calendar_home_url, "display_name": display_name}
+
+
+class CalDAVProvider(CalendarProvider):
+    source = "caldav"
+    supports_move = True
+
+    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, account_id: str, priority: RequestPriority = RequestPriority.INTERACTIVE):
+        super().__init__(supabase, http, user_id, account_id, priority=priority)
+        self._credentials: dict[str, str] | None = None
+
+    @classmethod
+    def http_client(cls, http: httpx.AsyncClient) -> httpx.AsyncClient:
+        return get_caldav_http_client()
+
+    async def _get_credentials(self) -> dict[str, str]:
+        if self._credentials is None:
+            self._credentials = await asyncio.to_thread(get_caldav_credentials, self.supabase, self.user_id, self.account_id)
+        return self._credentials
+
+    async def _request(self, method: str, path: str, body: str | None = None, headers: dict | None = None) -> httpx.Response:
+        credentials = await self._get_credentials()
+        url = urljoin(credentials["calendar_home_url"], path)
+        # The calendar home was checked when the account was connected; hrefs the server hands back may point elsewhere.
+        if _origin(url) != _origin(credentials["calendar_home_url"]):
+            await ensure_public_url(url)
+        content_type = "text/calendar; charset=utf-8" if method == "PUT" else "application/xml; charset=utf-8"
+        try:
+            response = await self.http.request(
+                method, url, content=body,
+                auth=(credentials["username"], credentials["password"]),
+                headers={"Content-Type": content_type, **(headers or {})},
+                timeout=CalDAVConfig.REQUEST_TIMEOUT,
+            )
+        except httpx.TimeoutException:
+            raise ProviderError(504, "CalDAV request timed out", retryable=True)
+        except httpx.HTTPError:
+            raise ProviderError(503, "CalDAV server unreachable", retryable=True)
+
+        status = response.status_code
+        if status < 300:
+            return response
+        if status == 401:
+            raise ProviderError(401, "CalDAV credentials rejected")
+        if status == 412:
+            raise ProviderError(412, "Event was modified by another client")
+        if status in (404, 410):
+            raise ProviderError(404, "CalDAV resource not found")
+        if status == 429 or status >= 500:
+            raise ProviderError(status, "CalDAV server error", retryable=True)
+        raise ProviderError(status, response.text[:200])
+
+    def _object_path(self, calendar_id: str, event_id: str) -> str:
+        return f"{calendar_id.rstrip('/')}/{quote(event_id)}.ics"
+
+    async def fetch_calendar_list(self, sync_token: str | None = None):
+        credentials = await self._get_credentials()
+        response = await self._request(
+            "PROPFIND", credentials["calendar_home_url"],
+            _propfind("<d:resourcetype/><d:displayname/><ical:calendar-color/><c:supported-calendar-component-set/><d:current-user-privilege-set/>"),
+            headers={"Depth": "1"},
+        )
+        items = []
+        for entry in _responses(response.text):
+            prop = _ok_prop(entry)
+            if prop is None or prop.find("d:resourcetype/c:calendar", NAMESPACES) is None:
+                continue
+            components = prop.findall("c:supported-calendar-component-set/c:comp", NAMESPACES)
+            if components and not any(comp.get("name") == "VEVENT" for comp in components):
+                continue
+            privileges = {
+                privilege.tag.rsplit("}", 1)[-1]
+                for privilege in prop.findall("d:current-user-privilege-set/d:privilege/*", NAMESPACES)
+            }
+            href = _href(entry)
+            color = (prop.findtext("ical:calendar-color", "", NAMESPACES) or "").strip()
+            items.append({
+                "id": href,
+                "summary": prop.findtext("d:displayname", None, NAMESPACES) or resource_id(href),
+                "backgroundColor": color[:7] or None,
+                "primary": not items,
+                "accessRole": "writer" if not privileges or privileges & WRITE_PRIVILEGES else "reader",
+            })
+        yield {"items": items, "next_sync_token": None}
+
+    async def fetch_events(
+        self,
+        calendar_id: str,
+        page_token: str | None = None,
+        sync_token: str | None = None,
+        time_min: datetime | None = None,
+        time_max: datetime | None = None,
+    ):
+        if sync_token and sync_token.startswith(CTAG_TOKEN_PREFIX):
+            token = await self._collection_token(calendar_id)
+            if token != sync_token:
+                raise ProviderError(410, "Calendar changed since last sync")
+            yield {"items": [], "next_page_token": None, "next_sync_token": token}
+            return
+        if sync_token:
+            async for page in self._sync_collection(calendar_id, sync_token):
+                yield page
+            return
+
+        token = await self._collection_token(calendar_id)
+        time_range = ""
+        if time_min or time_max:
+            bounds = (f' start="{_ts(time_min)}"' if time_min else "") + (f' end="{_ts(time_max)}"' if time_max else "")
+            time_range = f"<c:time-range{bounds}/>"
+        response = await self._request(
+            "REPORT", calendar_id,
+            f'{XML_HEADER}<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">'
+            f'<d:prop><d:getetag/><c:calendar-data/></d:prop>'
+            f'<c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VEVENT">{time_range}</c:comp-filter></c:comp-filter></c:filter>'
+            f'</c:calendar-query>',
+            headers={"Depth": "1"},
+        )
+        items = self._events_from(_responses(response.text))
+        yield {"items": items, "next_page_token": None, "next_sync_token": token}
+
+    async def _collection_token(self, calendar_id: str) -> str | None:
+        response = await self._request("PROPFIND", calendar_id, _propfind("<d:sync-token/><cs:getctag/>"), headers={"Depth": "0"})
+        responses = _responses(response.text)
+        prop = _ok_prop(responses[0]) if responses else None
+        if prop is None:
+            return None
+        token = prop.findtext("d:sync-token", None, NAMESPACES)
+        if token:
+            return token.strip()
+        ctag = prop.findtext("cs:getctag", None, NAMESPACES)
+        return f"{CTAG_TOKEN_PREFIX}{ctag.strip()}" if ctag else None
+
+    async def _sync_collection(self, calendar_id: str, sync_token: str):
+        try:
+            response = await self._request(
+                "REPORT", calendar_id,
+                f'{XML_HEADER}<d:sync-collection xmlns:d="DAV:"><d:sync-token>{escape(sync_token)}</d:sync-token>'
+                f'<d:sync-level>1</d:sync-level><d:prop><d:getetag/></d:prop></d:sync-collection>',
+            )
+        except ProviderError as e:
+            if e.status_code in (403, 409):
+                raise ProviderError(410, "Sync token expired")
+            raise
+
+        changed, removed = [], []
+        for entry in _responses(response.text):
+            href = _href(entry)
+            if href.rstrip("/") == calendar_id.rstrip("/"):
+                continue
+            if _status(entry) == 404:
+                removed.append({"id": resource_id(href), "status": "cancelled"})
+            else:
+                changed.append(href)
+        next_token = (ElementTree.fromstring(response.text).findtext("d:sync-token", "", NAMESPACES) or "").strip() or sync_token
+
+        batch = CalDAVConfig.MULTIGET_BATCH_SIZE
+        chunks = [changed[i:i + batch] for i in range(0, len(changed), batch)] or [[]]
+        for index, hrefs in enumerate(chunks):
+            items = await self._multiget(calendar_id, hrefs) if hrefs else []
+            if index == 0:
+                items = removed + items
+            last = index == len(chunks) - 1
+            yield {"items": items, "next_page_token": None, "next_sync_token": next_token if last else None}
+
+    async def _multiget(self, calendar_id: str, hrefs: list[str]) -> list[dict]:
+        targets = "".join(f"<d:href>{escape(quote(href))}</d:href>" for href in hrefs)
+        response = await self._request(
+            "REPORT", calendar_id,
+            f'{XML_HEADER}<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">'
+            f'<d:prop><d:getetag/><c:calendar-data/></d:prop>{targets}</c:calendar-multiget>',
+            headers={"Depth": "1"},
+        )
+        return self._events_from(_responses(response.text))
+
+    def _events_from(self, responses: list[ElementTree.Element]) -> list[dict]:
+        items = []
+        for entry in responses:
+            prop = _ok_prop(entry)
+            data = prop.findtext("c:calendar-data", None, NAMESPACES) if prop is not None else None
+            if not data:
+                continue
+            etag = prop.findtext("d:getetag", None, NAMESPACES)
+            for event in ical_to_events(data, resource_id(_href(entry))):
+                items.append({**event, "etag": etag})
+        return items
+
+    async def _get_object(self, calendar_id: str, master_id: str) -> tuple[list[dict], str | None]:
+        response = await self._request("GET", self._object_path(calendar_id, master_id))
+        return ical_to_events(response.text, master_id), response.headers.get("ETag")
+
+    async def _put_object(self, calendar_id: str, master_id: str, events: list[dict], headers: dict) -> list[dict]:
+        text = events_to_ical(events)
+        response = await self._request("PUT", self._object_path(calendar_id, master_id), text, headers=headers)
+        etag = response.headers.get("ETag")
+        return [{**event, "etag": etag} for event in ical_to_events(text, master_id)]
+
+    async def get_event(self, calendar_id: str, event_id: str):
+        master_id, _ = split_instance_id(event_id)
+        events, etag = await self._get_object(calendar_id, master_id)
+        event = next((event for event in events if event["id"] == event_id), None)
+        if event is None:
+            raise ProviderError(404, "CalDAV resource not found")
+        return {**event, "etag": etag}
+
+    async def insert_event(self, calendar_id: str, body: dict):
+        event_id = uuid.uuid4().hex
+        event = {**body, "id": event_id, "iCalUID": f"{event_id}@chronos"}
+        saved = await self._put_object(calendar_id, event_id, [event], {"If-None-Match": "*"})
+        return saved[0]
+
+    async def create_event(self, calendar_id: str, event: Event):
+        body = event.model_dump(exclude_none=True, exclude={"id", "color", "colorId", "calendarId", "completed"})
+        return await self.insert_event(calendar_id, body)
+
+    async def edit_event(self, calendar_id: str, event_id: str, event: EventPatch, etag: str | None = None):
+        master_id, instance_key = split_instance_id(event_id)
+        events, current_etag = await self._get_object(calendar_id, master_id)
+        if etag and current_etag and etag != current_etag:
+            raise ProviderError(412, "Event was modified by another client")
+        master = next((item for item in events if item["id"] == master_id), None)
+        target = next((item for item in events if item["id"] == event_id), None)
+        if target is None:
+            if master is None or instance_key is None:
+                raise ProviderError(404, "CalDAV resource not found")
+            target = materialize_instance(master, event_id)
+            events.append(target)
+
+        target.update(event.model_dump(exclude_none=True, exclude={"completed", "colorId"}))
+        saved = await self._put_object(calendar_id, master_id, events, {"If-Match": current_etag} if current_etag else {})
+        return next(item for item in saved if item["id"] == event_id)
+
+    async def delete_event(self, calendar_id: str, event_id: str, etag: str | None = None):
+        master_id, instance_key = split_instance_id(event_id)
+        if instance_key is None:
+            await self._request("DELETE", self._object_path(calendar_id, event_id), headers={"If-Match": etag} if etag else None)
+            return
+
+        events, current_etag = await self._get_object(calendar_id, master_id)
+        if etag and current_etag and etag != current_etag:
+            raise ProviderError(412, "Event was modified by another client")
+        master = next((item for item in events if item["id"] == master_id), None)
+        if master is None:
+            raise ProviderError(404, "CalDAV resource not found")
+        exdate = write_time("EXDATE", instance_original_start(master, instance_key))
+        master["recurrence"] = [*(master.get("recurrence") or []), exdate.line()]
+        remaining = [item for item in events if item["id"] != event_id]
+        await self._put_object(calendar_id, master_id, remaining, {"If-Match": current_etag} if current_etag else {})
+
+    async def move_event(self, calendar_id: str, event_id: str, destination_calendar_id: str):
+        credentials = await self._get_credentials()
+        destination = urljoin(credentials["calendar_home_url"], self._object_path(destination_calendar_id, event_id))
+        await self._request(
+            "MOVE", self._object_path(calendar_id, event_id),
+            headers={"Destination": destination, "Overwrite": "F"},
+        )
+        return await self.get_event(destination_calendar_id, event_id)
+
@@ -0,0 +1,189 @@
+"""Native calendars stored directly in Supabase, with no external service behind them."""
+import asyncio
+import uuid
+from datetime import datetime, timezone
+
+from supabase import Client
+
+from app.calendar.helpers import ProviderError, row_to_event
+from app.calendar.providers.base import CalendarProvider
+from app.calendar.recurrence import materialize_instance, split_instance_id
+from app.core.db_utils import Row, all_rows, first_row
+from app.models.event import BatchOperation, Event, EventPatch
+
+ACCOUNT_NAME = "Chronos"
+CHRONOS_DEFAULT_COLOR = "#3b82f6"
+SYNC_TOKEN = "chronos"
+
+
+def chronos_account_key(user_id: str) -> str:
+    return f"chronos:{user_id}"
+
+
+def ensure_chronos_account(supabase: Client, user_id: str, email: str) -> Row:
+    account = first_row(
+        supabase.table("google_accounts")
+        .upsert({
+            "user_id": user_id,
+            "google_id": chronos_account_key(user_id),
+            "email": email,
+            "name": ACCOUNT_NAME,
+            "provider": ChronosProvider.source,
+        }, on_conflict="user_id,google_id")
+        .execute()
+        .data
+    )
+    if account is None:
+        raise ProviderError(500, "Failed to store Chronos account")
+    return account
+
+
+def _stamp(event: dict) -> dict:
+    now = datetime.now(timezone.utc).isoformat()
+    return {**event, "etag": f'"{uuid.uuid4().hex}"', "created": event.get("created") or now, "updated": now}
+
+
+class ChronosProvider(CalendarProvider):
+    """Events live in the events table itself, so every write goes straight to Supabase."""
+
+    source = "chronos"
+    supports_move = True
+
+    def _calendar(self, calendar_id: str) -> Row:
+        calendar = first_row(
+            self.supabase.table("google_calendars")
+            .select("*")
+            .eq("google_account_id", self.account_id)
+            .eq("google_calendar_id", calendar_id)
+            .limit(1)
+            .execute()
+            .data
+        )
+        if calendar is None:
+            raise ProviderError(404, "Calendar not found")
+        return calendar
+
+    def _rows(self, calendar: Row, column: str, value: str) -> list[Row]:
+        return all_rows(
+            self.supabase.table("events")
+            .select("*")
+            .eq("googleCalendarId", calendar["id"])
+            .eq("source", self.source)
+            .eq(column, value)
+            .execute()
+            .data
+        )
+
+    def _event(self, calendar: Row, row: Row) -> dict:
+        event = row_to_event(row)
+        # Rows inherit the calendar color when the event has none of its own; don't carry it elsewhere.
+        if event.get("colorId") == calendar.get("color"):
+            event.pop("colorId", None)
+        return event
+
+    def _find(self, calendar: Row, event_id: str) -> dict | None:
+        row = first_row(self._rows(calendar, "googleEventId", event_id))
+        return self._event(calendar, row) if row else None
+
+    def _save(self, calendar: Row, events: list[dict]) -> list[dict]:
+        rows = self.transform_events(events, calendar)
+        self.supabase.table("events").upsert(rows, on_conflict="googleCalendarId,googleEventId,source").execute()
+        return events
+
+    def _lookup(self, calendar: Row, event_id: str) -> tuple[dict | None, dict | None]:
+        """The stored event and, for instance ids, the series master it belongs to."""
+        master_id, instance_key = split_instance_id(event_id)
+        event = self._find(calendar, event_id)
+        master = self._find(calendar, master_id) if instance_key else None
+        if event is None and (master is None or master.get("status") == "cancelled"):
+            raise ProviderError(404, "Event not found")
+        if event is not None and event.get("status") == "cancelled":
+            raise ProviderError(410, "Event has been deleted")
+        return event, master
+
+    async def fetch_calendar_list(self, sync_token: str | None = None):
+        # An empty listing never prunes, so stored Chronos calendars are left alone.
+        yield {"items": [], "next_sync_token": None}
+
+    async def fetch_events(self, calendar_id, page_token=None, sync_token=None, time_min=None, time_max=None):
+        yield {"items": [], "next_page_token": None, "next_sync_token": SYNC_TOKEN}
+
+    def _get(self, calendar_id: str, event_id: str) -> dict:
+        event, master = self._lookup(self._calendar(calendar_id), event_id)
+        return event or materialize_instance(master, event_id)
+
+    async def get_event(self, calendar_id: str, event_id: str):
+        return await asyncio.to_thread(self._get, calendar_id, event_id)
+
+    def _insert(self, calendar_id: str, body: dict) -> dict:
+        calendar = self._calendar(calendar_id)
+        event_id = uuid.uuid4().hex
+        event = _stamp({**body, "id": event_id, "iCalUID": f"{event_id}@chronos", "status": body.get("status") or "confirmed"})
+        return self._save(calendar, [event])[0]
+
+    async def insert_event(self, calendar_id: str, body: dict):
+        return await asyncio.to_thread(self._insert, calendar_id, body)
+
+    async def create_event(self, calendar_id: str, event: Event):
+        body = event.model_dump(exclude_none=True, exclude={"id", "color", "calendarId", "completed"})
+        return await self.insert_event(calendar_id, body)
+
+    def _edit(self, calendar_id: str, event_id: str, patch: EventPatch, etag: str | None) -> dict:
+        calendar = self._calendar(calendar_id)
+        event, master = self._lookup(calendar, event_id)
+        current = event or master
+        if etag and current.get("etag") != etag:
+            raise ProviderError(412, "Event was modified by another client")
+        target = event or materialize_instance(master, event_id)
+        target.update(patch.model_dump(exclude_none=True, exclude={"completed"}))
+        return self._save(calendar, [_stamp(target)])[0]
+
+    async def edit_event(self, calendar_id: str, event_id: str, event: EventPatch, etag: str | None = None):
+        return await asyncio.to_thread(self._edit, calendar_id, event_id, event, etag)
+
+    def _delete(self, calendar_id: str, event_id: str, etag: str | None) -> None:
+        calendar = self._calendar(calendar_id)
+        event, master = self._lookup(calendar, event_id)
+        current = event or master
+        if etag and current.get("etag") != etag:
+            raise ProviderError(412, "Event was modified by another client")
+        cancelled = [_stamp({**(event or materialize_instance(master, event_id)), "status": "cancelled"})]
+        if event is not None and event.get("recurrence"):
+            cancelled += [
+                _stamp({**self._event(calendar, row), "status": "cancelled"})
+                for row in self._rows(calendar, "recurringEventId", event_id)
+            ]
+        self._save(calendar, cancelled)
+
+    async def delete_event(self, calendar_id: str, event_id: str, etag: str | None = None):
+        await asyncio.to_thread(self._delete, calendar_id, event_id, etag)
+
+    def _move(self, calendar_id: str, event_id: str, destination_calendar_id: str) -> dict:
+        source, destination = self._calendar(calendar_id), self._calendar(destination_calendar_id)
+        event = self._find(source, event_id)
+        if event is None or event.get("status") == "cancelled":
+            raise ProviderError(404, "Event not found")
+        # Exceptions move with their series; the source rows are cancelled by the caller.
+        exceptions = [self._event(source, row) for row in self._rows(source, "recurringEventId", event_id)] if event.get("recurrence") else []
+        moved = _stamp(event)
+        self._save(destination, [*exceptions, moved])
+        return moved
+
+    async def move_event(self, calendar_id: str, event_id: str, destination_calendar_id: str):
+        return await asyncio.to_thread(self._move, calendar_id, event_id, destination_calendar_id)
+
+    async def batch_events(self, calendar_id: str, operations: list[BatchOperation]) -> list[dict]:
+        responses = []
+        for operation in operations:
+            try:
+                if operation.method == "create":
+                    responses.append({"status": 200, "body": await self.create_event(calendar_id, operation.event)})
+                elif operation.method == "update":
+                    body = await self.edit_event(calendar_id, operation.eventId, operation.patch, etag=operation.etag)
+                    responses.append({"status": 200, "body": body})
+                else:
+                    await self.delete_event(calendar_id, operation.eventId, etag=operation.etag)
+                    responses.append({"status": 204, "body": None})
+            except ProviderError as e:
+                responses.append({"status": e.status_code, "body": {"error": {"message": e.message}}})
+        return responses
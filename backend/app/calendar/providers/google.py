@@ -0,0 +1,17 @@
+import httpx
+from supabase import Client
+
+from app.calendar.google_client import GoogleAPIClient
+from app.calendar.providers.base import CalendarProvider
+from app.calendar.quota import RequestPriority
+
+
+class GoogleProvider(GoogleAPIClient, CalendarProvider):
+    source = "google"
+    supports_push = True
+    supports_calendar_list_push = True
+    supports_move = True
+
+    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, account_id: str, priority: RequestPriority = RequestPriority.INTERACTIVE):
+        super().__init__(supabase, http, user_id, account_id, priority=priority)
+        self.account_id = account_id
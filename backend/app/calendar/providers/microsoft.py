@@ -0,0 +1,611 @@
+"""Microsoft 365 / Outlook calendars through Microsoft Graph.
+
+For local testing run `uvicorn app.fake_graph:app --port 8091` and set MICROSOFT_GRAPH_API_URL=http://localhost:8091/v1.0
+and MICROSOFT_OAUTH_URL=http://localhost:8091/oauth.
+"""
+import asyncio
+import re
+import time
+from datetime import date, datetime, time as day_time, timedelta, timezone
+from urllib.parse import quote
+from zoneinfo import ZoneInfo, ZoneInfoNotFoundError
+
+import httpx
+from supabase import Client
+
+from app.calendar.constants import MicrosoftGraphConfig
+from app.calendar.helpers import ProviderError, parse_event_time, parse_ical_datetime
+from app.calendar.oauth_client import OAuthClient
+from app.calendar.providers.base import CalendarProvider
+from app.calendar.quota import RequestPriority
+from app.calendar.recurrence import google_instance_id, split_instance_id
+from app.calendar.retry import RetryPolicy, parse_retry_after
+from app.config import get_settings
+from app.core.db_utils import all_rows, first_row
+from app.models.event import Event, EventPatch
+
+ACCOUNT_KEY_PREFIX = "microsoft:"
+DAYS = {
+    "sunday": "SU",
+    "monday": "MO",
+    "tuesday": "TU",
+    "wednesday": "WE",
+    "thursday": "TH",
+    "friday": "FR",
+    "saturday": "SA",
+}
+DAY_NAMES = {code: name for name, code in DAYS.items()}
+WEEK_INDEX = {"first": 1, "second": 2, "third": 3, "fourth": 4, "last": -1}
+INDEX_NAMES = {ordinal: name for name, ordinal in WEEK_INDEX.items()}
+RESPONSE_STATUS = {
+    "accepted": "accepted",
+    "organizer": "accepted",
+    "declined": "declined",
+    "tentativelyAccepted": "tentative",
+}
+SENSITIVITY = {"normal": "default", "personal": "private", "private": "private", "confidential": "confidential"}
+VISIBILITY = {"default": "normal", "public": "normal", "private": "private", "confidential": "confidential"}
+WINDOWS_ZONES = {
+    "UTC": "UTC",
+    "GMT Standard Time": "Europe/London",
+    "W. Europe Standard Time": "Europe/Berlin",
+    "Romance Standard Time": "Europe/Paris",
+    "Central Europe Standard Time": "Europe/Budapest",
+    "E. Europe Standard Time": "Europe/Chisinau",
+    "Eastern Standard Time": "America/New_York",
+    "Central Standard Time": "America/Chicago",
+    "Mountain Standard Time": "America/Denver",
+    "Pacific Standard Time": "America/Los_Angeles",
+    "India Standard Time": "Asia/Kolkata",
+    "China Standard Time": "Asia/Shanghai",
+    "Tokyo Standard Time": "Asia/Tokyo",
+    "AUS Eastern Standard Time": "Australia/Sydney",
+}
+# cancelledOccurrences is only returned when selected explicitly, which means naming every other field too.
+MASTER_FIELDS = ",".join((
+    "subject", "body", "location", "start", "end", "isAllDay", "isCancelled", "showAs", "sensitivity", "attendees",
+    "organizer", "isOrganizer", "isReminderOn", "reminderMinutesBeforeStart", "onlineMeeting", "webLink",
+    "createdDateTime", "lastModifiedDateTime", "iCalUId", "type", "recurrence", "originalStartTimeZone",
+    "cancelledOccurrences",
+))
+_BYDAY = re.compile(r"^([+-]?\d)?(SU|MO|TU|WE|TH|FR|SA)$")
+GRAPH_RETRY_POLICY = RetryPolicy(
+    max_attempts=MicrosoftGraphConfig.MAX_RETRIES,
+    base_delay=MicrosoftGraphConfig.BASE_DELAY_SECONDS,
+    max_delay=MicrosoftGraphConfig.MAX_DELAY_SECONDS,
+    deadline_seconds=MicrosoftGraphConfig.REQUEST_DEADLINE_SECONDS,
+)
+
+
+def microsoft_account_key(user_id: str) -> str:
+    return f"{ACCOUNT_KEY_PREFIX}{user_id}"
+
+
+def graph_url() -> str:
+    return get_settings().MICROSOFT_GRAPH_API_URL.rstrip("/")
+
+
+def oauth_url() -> str:
+    return get_settings().MICROSOFT_OAUTH_URL.rstrip("/")
+
+
+def _zone_name(name: str | None) -> str | None:
+    if not name:
+        return None
+    name = WINDOWS_ZONES.get(name, name)
+    try:
+        ZoneInfo(name)
+    except (ZoneInfoNotFoundError, ValueError):
+        return None
+    return name
+
+
+def _graph_datetime(raw: str) -> datetime:
+    return datetime.fromisoformat(raw.split(".")[0].replace("Z", ""))
+
+
+def _graph_instant(raw: str) -> datetime:
+    parsed = _graph_datetime(raw)
+    return parsed if parsed.tzinfo else parsed.replace(tzinfo=timezone.utc)
+
+
+def from_graph_time(value: dict, all_day: bool, zone_name: str | None) -> dict:
+    raw = value["dateTime"]
+    if all_day:
+        return {"date": raw[:10]}
+    source_zone = _zone_name(value.get("timeZone")) or "UTC"
+    parsed = _graph_datetime(raw).replace(tzinfo=ZoneInfo(source_zone))
+    if zone_name:
+        return {"dateTime": parsed.astimezone(ZoneInfo(zone_name)).isoformat(), "timeZone": zone_name}
+    return {"dateTime": parsed.astimezone(timezone.utc).isoformat()}
+
+
+def to_graph_time(value: dict) -> dict:
+    if value.get("date"):
+        return {"dateTime": f"{value['date']}T00:00:00", "timeZone": "UTC"}
+    parsed = datetime.fromisoformat(value["dateTime"].replace("Z", "+00:00"))
+    zone_name = _zone_name(value.get("timeZone"))
+    if parsed.tzinfo is None:
+        parsed = parsed.replace(tzinfo=ZoneInfo(zone_name) if zone_name else timezone.utc)
+    zone_name = zone_name or "UTC"
+    return {"dateTime": parsed.astimezone(ZoneInfo(zone_name)).replace(tzinfo=None).isoformat(), "timeZone": zone_name}
+
+
+def _local_start(start: dict) -> datetime:
+    parsed = parse_event_time(start)
+    if parsed is None:
+        raise ProviderError(400, "Recurring events need a start time")
+    zone_name = _zone_name(start.get("timeZone"))
+    return parsed.astimezone(ZoneInfo(zone_name)) if zone_name and not start.get("date") else parsed
+
+
+def graph_recurrence_to_rrule(recurrence: dict | None, start: dict) -> list[str] | None:
+    if not recurrence:
+        return None
+    pattern = recurrence.get("pattern") or {}
+    recurrence_range = recurrence.get("range") or {}
+    kind = pattern.get("type")
+    days = [DAYS[day] for day in pattern.get("daysOfWeek") or [] if day in DAYS]
+    ordinal = WEEK_INDEX.get(pattern.get("index") or "first", 1)
+    relative_days = ",".join(f"{ordinal}{day}" for day in days)
+
+    if kind == "daily":
+        parts = ["FREQ=DAILY"]
+    elif kind == "weekly":
+        parts = ["FREQ=WEEKLY"]
+        if days:
+            parts.append(f"BYDAY={','.join(days)}")
+        if pattern.get("firstDayOfWeek") in DAYS:
+            parts.append(f"WKST={DAYS[pattern['firstDayOfWeek']]}")
+    elif kind == "absoluteMonthly":
+        parts = ["FREQ=MONTHLY", f"BYMONTHDAY={pattern['dayOfMonth']}"]
+    elif kind == "relativeMonthly" and relative_days:
+        parts = ["FREQ=MONTHLY", f"BYDAY={relative_days}"]
+    elif kind == "absoluteYearly":
+        parts = ["FREQ=YEARLY", f"BYMONTH={pattern['month']}", f"BYMONTHDAY={pattern['dayOfMonth']}"]
+    elif kind == "relativeYearly" and relative_days:
+        parts = ["FREQ=YEARLY", f"BYMONTH={pattern['month']}", f"BYDAY={relative_days}"]
+    else:
+        return None
+
+    interval = int(pattern.get("interval") or 1)
+    if interval > 1:
+        parts.append(f"INTERVAL={interval}")
+    if recurrence_range.get("type") == "numbered" and recurrence_range.get("numberOfOccurrences"):
+        parts.append(f"COUNT={recurrence_range['numberOfOccurrences']}")
+    elif recurrence_range.get("type") == "endDate" and recurrence_range.get("endDate"):
+        end_date = date.fromisoformat(recurrence_range["endDate"])
+        if start.get("date"):
+            parts.append(f"UNTIL={end_date.strftime('%Y%m%d')}")
+        else:
+            zone_name = _zone_name(start.get("timeZone")) or "UTC"
+            until = datetime.combine(end_date, day_time(23, 59, 59), tzinfo=ZoneInfo(zone_name))
+            parts.append(f"UNTIL={until.astimezone(timezone.utc).strftime('%Y%m%dT%H%M%SZ')}")
+    return [f"RRULE:{';'.join(parts)}"]
+
+
+def rrule_to_graph_recurrence(recurrence: list[str] | None, start: dict) -> dict | None:
+    rule = next((line[len("RRULE:"):] for line in recurrence or [] if line.startswith("RRULE:")), None)
+    if rule is None:
+        return None
+    parts = dict(part.split("=", 1) for part in rule.split(";") if "=" in part)
+    local_start = _local_start(start)
+    by_day = [match for day in parts.get("BYDAY", "").split(",") if (match := _BYDAY.match(day.strip()))]
+    days = [DAY_NAMES[match.group(2)] for match in by_day]
+    ordinal = next((int(match.group(1)) for match in by_day if match.group(1)), None)
+    pattern: dict = {"interval": int(parts.get("INTERVAL", 1))}
+    month = int(parts.get("BYMONTH", local_start.month))
+    day_of_month = int(parts.get("BYMONTHDAY", local_start.day))
+
+    frequency = parts.get("FREQ")
+    if frequency == "DAILY":
+        pattern["type"] = "daily"
+    elif frequency == "WEEKLY":
+        pattern["type"] = "weekly"
+        pattern["daysOfWeek"] = days or [list(DAYS)[(local_start.weekday() + 1) % 7]]
+        pattern["firstDayOfWeek"] = DAY_NAMES.get(parts.get("WKST", ""), "sunday")
+    elif frequency in ("MONTHLY", "YEARLY") and ordinal is not None and ordinal in INDEX_NAMES:
+        pattern["type"] = "relativeMonthly" if frequency == "MONTHLY" else "relativeYearly"
+        pattern["daysOfWeek"] = days
+        pattern["index"] = INDEX_NAMES[ordinal]
+    elif frequency in ("MONTHLY", "YEARLY") and not by_day:
+        pattern["type"] = "absoluteMonthly" if frequency == "MONTHLY" else "absoluteYearly"
+        pattern["dayOfMonth"] = day_of_month
+    else:
+        raise ProviderError(400, "This recurrence rule is not supported by Microsoft calendars")
+    if frequency == "YEARLY":
+        pattern["month"] = month
+
+    recurrence_range: dict = {"type": "noEnd", "startDate": local_start.date().isoformat()}
+    if start.get("timeZone"):
+        recurrence_range["recurrenceTimeZone"] = start["timeZone"]
+    if parts.get("COUNT"):
+        recurrence_range["type"] = "numbered"
+        recurrence_range["numberOfOccurrences"] = int(parts["COUNT"])
+    elif parts.get("UNTIL"):
+        until = parse_ical_datetime(parts["UNTIL"])
+        if until is None:
+            raise ProviderError(400, "Invalid recurrence end")
+        recurrence_range["type"] = "endDate"
+        recurrence_range["endDate"] = (until.astimezone(local_start.tzinfo) if not start.get("date") else until).date().isoformat()
+    return {"pattern": pattern, "range": recurrence_range}
+
+
+def cancelled_occurrence_exdates(occurrence_ids: list[str] | None, start: dict) -> list[str]:
+    """EXDATEs for a master's cancelledOccurrences, whose ids end in the local date of the occurrence."""
+    days = []
+    for occurrence_id in occurrence_ids or []:
+        try:
+            days.append(date.fromisoformat(occurrence_id.rsplit(".", 1)[-1]))
+        except ValueError:
+            continue
+    if not days:
+        return []
+    if start.get("date"):
+        return [f"EXDATE;VALUE=DATE:{','.join(day.strftime('%Y%m%d') for day in sorted(set(days)))}"]
+    local_start = _local_start(start)
+    instants = sorted({datetime.combine(day, local_start.timetz()).astimezone(timezone.utc) for day in days})
+    return [f"EXDATE:{','.join(instant.strftime('%Y%m%dT%H%M%SZ') for instant in instants)}"]
+
+
+def _person(value: dict | None) -> dict | None:
+    address = (value or {}).get("emailAddress") or {}
+    if not address.get("address"):
+        return None
+    person = {"email": address["address"]}
+    if address.get("name"):
+        person["displayName"] = address["name"]
+    return person
+
+
+def graph_event_to_event(item: dict) -> dict:
+    if "@removed" in item:
+        return {"id": item["id"], "status": "cancelled"}
+
+    zone_name = _zone_name(item.get("originalStartTimeZone"))
+    all_day = bool(item.get("isAllDay"))
+    start = from_graph_time(item["start"], all_day, zone_name)
+    end = from_graph_time(item["end"], all_day, zone_name)
+    attendees = []
+    for attendee in item.get("attendees") or []:
+        person = _person(attendee)
+        if person is None:
+            continue
+        person["responseStatus"] = RESPONSE_STATUS.get((attendee.get("status") or {}).get("response"), "needsAction")
+        if attendee.get("type") == "optional":
+            person["optional"] = True
+        attendees.append(person)
+    organizer = _person(item.get("organizer"))
+    if organizer and item.get("isOrganizer"):
+        organizer["self"] = True
+    reminders = {"useDefault": False, "overrides": [{"method": "popup", "minutes": item["reminderMinutesBeforeStart"]}]} if item.get("isReminderOn") else None
+    join_url = (item.get("onlineMeeting") or {}).get("joinUrl")
+
+    event = {
+        "id": item["id"],
+        "iCalUID": item.get("iCalUId"),
+        "etag": item.get("@odata.etag"),
+        "summary": item.get("subject") or None,
+        "description": ((item.get("body") or {}).get("content") or "").strip() or None,
+        "location": ((item.get("location") or {}).get("displayName") or "").strip() or None,
+        "start": start,
+        "end": end,
+        "status": "cancelled" if item.get("isCancelled") else "tentative" if item.get("showAs") == "tentative" else "confirmed",
+        "transparency": "transparent" if item.get("showAs") == "free" else "opaque",
+        "visibility": SENSITIVITY.get(item.get("sensitivity") or "normal", "default"),
+        "attendees": attendees or None,
+        "organizer": organizer,
+        "reminders": reminders,
+        "conferenceData": {"entryPoints": [{"entryPointType": "video", "uri": join_url}]} if join_url else None,
+        "htmlLink": item.get("webLink"),
+        "created": item.get("createdDateTime"),
+        "updated": item.get("lastModifiedDateTime"),
+    }
+    if item.get("type") == "seriesMaster":
+        recurrence = graph_recurrence_to_rrule(item.get("recurrence"), start)
+        if recurrence:
+            event["recurrence"] = recurrence + cancelled_occurrence_exdates(item.get("cancelledOccurrences"), start)
+    if item.get("type") in ("exception", "occurrence") and item.get("seriesMasterId"):
+        event["recurringEventId"] = item["seriesMasterId"]
+        original = item.get("originalStart")
+        if original:
+            original_start = _graph_instant(original)
+            event["originalStartTime"] = {"date": original[:10]} if all_day else {"dateTime": original_start.isoformat()}
+            # Plain occurrences are never stored, so a cancelled one becomes an exception keyed by its original start.
+            if item["type"] == "occurrence":
+                event["id"] = google_instance_id(item["seriesMasterId"], original_start, all_day)
+    return {key: value for key, value in event.items() if value is not None}
+
+
+def event_to_graph(event: dict) -> dict:
+    body: dict = {}
+    if "summary" in event:
+        body["subject"] = event["summary"]
+    if "description" in event:
+        body["body"] = {"contentType": "text", "content": event["description"] or ""}
+    if "location" in event:
+        body["location"] = {"displayName": event["location"] or ""}
+    if event.get("start"):
+        body["start"] = to_graph_time(event["start"])
+        body["isAllDay"] = bool(event["start"].get("date"))
+    if event.get("end"):
+        body["end"] = to_graph_time(event["end"])
+    if "recurrence" in event and event.get("start"):
+        body["recurrence"] = rrule_to_graph_recurrence(event["recurrence"], event["start"])
+    if event.get("attendees") is not None:
+        body["attendees"] = [
+            {
+                "emailAddress": {"address": attendee["email"], **({"name": attendee["displayName"]} if attendee.get("displayName") else {})},
+                "type": "optional" if attendee.get("optional") else "required",
+            }
+            for attendee in event["attendees"] if attendee.get("email") and not attendee.get("self")
+        ]
+    if event.get("transparency"):
+        body["showAs"] = "free" if event["transparency"] == "transparent" else "busy"
+    if event.get("visibility"):
+        body["sensitivity"] = VISIBILITY.get(event["visibility"], "normal")
+    overrides = (event.get("reminders") or {}).get("overrides")
+    if overrides:
+        body["isReminderOn"] = True
+        body["reminderMinutesBeforeStart"] = int(overrides[0].get("minutes", 15))
+    return body
+
+
+class MicrosoftProvider(OAuthClient, CalendarProvider):
+    source = "microsoft"
+    supports_push = True
+    webhook_path = "/calendar/webhook/microsoft"
+    refresh_lock_prefix = "microsoft-token-refresh"
+    token_refresh_buffer = MicrosoftGraphConfig.TOKEN_REFRESH_BUFFER
+    refresh_lease_seconds = MicrosoftGraphConfig.REFRESH_LEASE_SECONDS
+
+    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, account_id: str, priority: RequestPriority = RequestPriority.INTERACTIVE, retry_policy: RetryPolicy = GRAPH_RETRY_POLICY):
+        super().__init__(supabase, http, user_id, account_id, priority=priority)
+        self.retry_policy = retry_policy
+
+    @property
+    def token_account_id(self) -> str:
+        return self.account_id
+
+    def _token_request(self, refresh_token: str) -> tuple[str, dict]:
+        settings = get_settings()
+        return oauth_url() + "/token", {
+            "client_id": settings.MICROSOFT_CLIENT_ID,
+            "client_secret": settings.MICROSOFT_CLIENT_SECRET,
+            "refresh_token": refresh_token,
+            "grant_type": "refresh_token",
+            "scope": MicrosoftGraphConfig.SCOPES,
+        }
+
+    async def _request(self, method: str, url: str, params: dict | None = None, json: dict | None = None, headers: dict | None = None) -> dict:
+        if not url.startswith("http"):
+            url = graph_url() + url
+        policy = self.retry_policy
+        deadline = time.monotonic() + policy.deadline_seconds
+        had_401 = False
+        last_error: ProviderError | None = None
+        for attempt in range(policy.max_attempts):
+            token = await self._get_valid_access_token()
+            try:
+                response = await self.http.request(
+                    method, url, params=params, json=json,
+                    headers={"Authorization": f"Bearer {token}", **(headers or {})},
+                    timeout=MicrosoftGraphConfig.REQUEST_TIMEOUT,
+                )
+            except httpx.TimeoutException:
+                last_error = ProviderError(504, "Microsoft Graph request timed out", retryable=True)
+                if not await self._wait_before_retry(attempt, None, deadline):
+                    break
+                continue
+            except httpx.HTTPError:
+                last_error = ProviderError(503, "Microsoft Graph unreachable", retryable=True)
+                if not await self._wait_before_retry(attempt, None, deadline):
+                    break
+                continue
+
+            status = response.status_code
+            if 200 <= status < 300:
+                return {} if status in (202, 204) or not response.content else response.json()
+            if status == 401:
+                await self._reauthorize(had_401)
+                had_401 = True
+                continue
+            if status == 403:
+                raise ProviderError(403, "Access forbidden")
+            if status == 404:
+                raise ProviderError(404, "Microsoft Graph resource not found")
+            if status == 410:
+                raise ProviderError(410, "Delta token expired")
+            if status == 412:
+                raise ProviderError(412, "Event was modified by another client")
+            if status == 429 or status >= 500:
+                last_error = ProviderError(status, "Rate limited" if status == 429 else "Microsoft Graph server error", retryable=True)
+                if not await self._wait_before_retry(attempt, parse_retry_after(response.headers.get("Retry-After")), deadline):
+                    break
+                continue
+            raise ProviderError(status, response.text[:200])
+
+        assert last_error is not None
+        raise last_error
+
+    def _masters_to_refresh(self, calendar_id: str, removed_ids: list[str]) -> list[str]:
+        """Stored series masters to re-read when a removal matches no stored event.
+
+        Deleting a single occurrence only shows up in the delta as an unknown removed id; the
+        cancellation itself is recorded on the master's cancelledOccurrences.
+        """
+        calendar = first_row(
+            self.supabase.table("google_calendars")
+            .select("id")
+            .eq("google_account_id", self.account_id)
+            .eq("google_calendar_id", calendar_id)
+            .limit(1)
+            .execute()
+            .data
+        )
+        if calendar is None:
+            return []
+        events = self.supabase.table("events")
+        known = {
+            row["googleEventId"] for row in all_rows(
+                events.select("googleEventId")
+                .eq("googleCalendarId", calendar["id"])
+                .eq("source", self.source)
+                .in_("googleEventId", removed_ids)
+                .execute()
+                .data
+            )
+        }
+        if known.issuperset(removed_ids):
+            return []
+        return [
+            row["googleEventId"] for row in all_rows(
+                events.select("googleEventId")
+                .eq("googleCalendarId", calendar["id"])
+                .eq("source", self.source)
+                .not_.is_("recurrence", "null")
+                .is_("recurringEventId", "null")
+                .neq("status", "cancelled")
+                .execute()
+                .data
+            )
+        ]
+
+    async def fetch_calendar_list(self, sync_token: str | None = None):
+        items = []
+        url: str | None = "/me/calendars"
+        while url:
+            response = await self._request("GET", url)
+            for calendar in response.get("value", []):
+                items.append({
+                    "id": calendar["id"],
+                    "summary": calendar.get("name") or calendar["id"],
+                    "backgroundColor": calendar.get("hexColor") or None,
+                    "primary": bool(calendar.get("isDefaultCalendar")),
+                    "accessRole": "owner" if calendar.get("canEdit") else "reader",
+                })
+            url = response.get("@odata.nextLink")
+        yield {"items": items, "next_sync_token": None}
+
+    async def fetch_events(
+        self,
+        calendar_id: str,
+        page_token: str | None = None,
+        sync_token: str | None = None,
+        time_min: datetime | None = None,
+        time_max: datetime | None = None,
+    ):
+        params = None
+        url = page_token or sync_token
+        if url is None:
+            settings = get_settings()
+            now = datetime.now(timezone.utc)
+            lower = time_min or (now - MicrosoftGraphConfig.UNBOUNDED_WINDOW if time_max else now - timedelta(days=settings.INITIAL_SYNC_DAYS_BACK))
+            upper = time_max or (now + MicrosoftGraphConfig.UNBOUNDED_WINDOW if time_min else now + timedelta(days=settings.INITIAL_SYNC_DAYS_FORWARD))
+            url = f"/me/calendars/{quote(calendar_id, safe='')}/calendarView/delta"
+            params = {"startDateTime": lower.isoformat(), "endDateTime": upper.isoformat()}
+
+        headers = {"Prefer": f'outlook.timezone="UTC", outlook.body-content-type="text", odata.maxpagesize={MicrosoftGraphConfig.PAGE_SIZE}'}
+        seen_masters: set[str] = set()
+        while url:
+            response = await self._request("GET", url, params=params, headers=headers)
+            params = None
+            items = []
+            masters = []
+            removed = []
+            for item in response.get("value", []):
+                master_id = item.get("seriesMasterId")
+                if master_id and master_id not in seen_masters:
+                    seen_masters.add(master_id)
+                    masters.append(master_id)
+                if "@removed" in item:
+                    removed.append(item["id"])
+                elif item.get("type") == "occurrence" and not item.get("isCancelled"):
+                    continue
+                items.append(graph_event_to_event(item))
+            if removed:
+                for master_id in await asyncio.to_thread(self._masters_to_refresh, calendar_id, removed):
+                    if master_id not in seen_masters:
+                        seen_masters.add(master_id)
+                        masters.append(master_id)
+            for master_id in masters:
+                try:
+                    master = await self._request("GET", f"/me/events/{quote(master_id, safe='')}", params={"$select": MASTER_FIELDS}, headers=headers)
+                except ProviderError as e:
+                    if e.status_code != 404:
+                        raise
+                    continue
+                items.insert(0, graph_event_to_event(master))
+
+            url = response.get("@odata.nextLink")
+            yield {
+                "items": items,
+                "next_page_token": url,
+                "next_sync_token": response.get("@odata.deltaLink") if not url else None,
+            }
+
+    async def _resolve_event_id(self, event_id: str) -> str:
+        master_id, instance_key = split_instance_id(event_id)
+        if instance_key is None:
+            return event_id
+        original_start = parse_ical_datetime(instance_key)
+        if original_start is None:
+            return event_id
+        response = await self._request(
+            "GET", f"/me/events/{quote(master_id, safe='')}/instances",
+            params={"startDateTime": original_start.isoformat(), "endDateTime": (original_start + timedelta(days=1)).isoformat()},
+            headers={"Prefer": 'outlook.timezone="UTC"'},
+        )
+        for instance in response.get("value", []):
+            instance_start = instance.get("originalStart")
+            if instance_start and _graph_instant(instance_start) == original_start:
+                return instance["id"]
+            if len(instance_key) == 8 and instance_start and instance_start[:10] == original_start.date().isoformat():
+                return instance["id"]
+        raise ProviderError(404, "Microsoft Graph resource not found")
+
+    def _event_path(self, event_id: str) -> str:
+        return f"/me/events/{quote(event_id, safe='')}"
+
+    async def get_event(self, calendar_id: str, event_id: str):
+        event_id = await self._resolve_event_id(event_id)
+        return graph_event_to_event(await self._request("GET", self._event_path(event_id), headers={"Prefer": 'outlook.timezone="UTC", outlook.body-content-type="text"'}))
+
+    async def insert_event(self, calendar_id: str, body: dict):
+        created = await self._request("POST", f"/me/calendars/{quote(calendar_id, safe='')}/events", json=event_to_graph(body))
+        return graph_event_to_event(created)
+
+    async def create_event(self, calendar_id: str, event: Event):
+        return await self.insert_event(calendar_id, event.model_dump(exclude_none=True, exclude={"id", "color", "colorId", "calendarId", "completed"}))
+
+    async def edit_event(self, calendar_id: str, event_id: str, event: EventPatch, etag: str | None = None):
+        event_id = await self._resolve_event_id(event_id)
+        changes = event.model_dump(exclude_none=True, exclude={"completed", "colorId"})
+        if "recurrence" in changes and "start" not in changes:
+            changes["start"] = (await self.get_event(calendar_id, event_id))["start"]
+        updated = await self._request(
+            "PATCH", self._event_path(event_id),
+            json=event_to_graph(changes), headers={"If-Match": etag} if etag else None,
+        )
+        return graph_event_to_event(updated)
+
+    async def delete_event(self, calendar_id: str, event_id: str, etag: str | None = None):
+        event_id = await self._resolve_event_id(event_id)
+        await self._request("DELETE", self._event_path(event_id), headers={"If-Match": etag} if etag else None)
+
+    async def create_watch_channel(self, calendar_external_id: str, webhook_url: str, channel_id: str, channel_token: str):
+        expires_at = datetime.now(timezone.utc) + MicrosoftGraphConfig.SUBSCRIPTION_TTL
+        response = await self._request("POST", "/subscriptions", json={
+            "changeType": "created,updated,deleted",
+            "notificationUrl": webhook_url,
+            "resource": f"me/calendars/{calendar_external_id}/events",
+            "expirationDateTime": expires_at.isoformat(),
+            "clientState": channel_token,
+        })
+        return {
+            "resource_id": response["id"],
+            "expires_at": _graph_instant(response["expirationDateTime"]) if response.get("expirationDateTime") else expires_at,
+        }
+
+    async def stop_watch_channel(self, channel_id: str, resource_id: str):
+        await self._request("DELETE", f"/subscriptions/{quote(resource_id, safe='')}")
@@ -0,0 +1,109 @@
+import asyncio
+import heapq
+import itertools
+import logging
+import time
+from enum import IntEnum
+from functools import lru_cache
+
+from app.config import get_settings
+
+logger = logging.getLogger(__name__)
+
+SLOW_ACQUIRE_SECONDS = 5.0
+
+
+class RequestPriority(IntEnum):
+    INTERACTIVE = 0
+    SYNC = 1
+    BACKGROUND = 2
+
+
+class QuotaManager:
+    def __init__(self, rate_per_second: float, burst: int):
+        self.rate = rate_per_second
+        self.burst = max(burst, 1)
+        self._tokens = float(self.burst)
+        self._updated_at = time.monotonic()
+        self._waiters: list[tuple[int, int, asyncio.Future, int]] = []
+        self._sequence = itertools.count()
+        self._dispatcher: asyncio.Task | None = None
+        self._stats = {
+            priority.name.lower(): {"requests": 0, "queued": 0, "total_wait_seconds": 0.0, "max_wait_seconds": 0.0}
+            for priority in RequestPriority
+        }
+
+    def _refill(self) -> None:
+        now = time.monotonic()
+        self._tokens = min(self.burst, self._tokens + (now - self._updated_at) * self.rate)
+        self._updated_at = now
+
+    def _available(self, tokens: int) -> bool:
+        """Requests larger than the burst wait for a full bucket and leave it in debt."""
+        return self._tokens >= min(tokens, self.burst)
+
+    async def acquire(self, priority: RequestPriority = RequestPriority.INTERACTIVE, tokens: int = 1) -> float:
+        if self.rate <= 0:
+            return 0.0
+        started = time.monotonic()
+        self._refill()
+        if not self._waiters and self._available(tokens):
+            self._tokens -= tokens
+            self._record(priority, 0.0, queued=False)
+            return 0.0
+
+        future = asyncio.get_running_loop().create_future()
+        heapq.heappush(self._waiters, (priority, next(self._sequence), future, tokens))
+        if self._dispatcher is None or self._dispatcher.done():
+            self._dispatcher = asyncio.create_task(self._dispatch())
+        await future
+
+        waited = time.monotonic() - started
+        self._record(priority, waited, queued=True)
+        if waited > SLOW_ACQUIRE_SECONDS:
+            logger.warning("Google quota wait of %.1fs for %s request", waited, priority.name.lower())
+        return waited
+
+    async def _dispatch(self) -> None:
+        while self._waiters:
+            self._refill()
+            while self._waiters and self._available(self._waiters[0][3]):
+                _, _, future, tokens = heapq.heappop(self._waiters)
+                if future.done():
+                    continue
+                self._tokens -= tokens
+                future.set_result(None)
+            if self._waiters:
+                await asyncio.sleep((min(self._waiters[0][3], self.burst) - self._tokens) / self.rate)
+
+    def _record(self, priority: RequestPriority, waited: float, queued: bool) -> None:
+        stats = self._stats[priority.name.lower()]
+        stats["requests"] += 1
+        stats["total_wait_seconds"] += waited
+        stats["max_wait_seconds"] = max(stats["max_wait_seconds"], waited)
+        if queued:
+            stats["queued"] += 1
+
+    def snapshot(self) -> dict:
+        self._refill()
+        return {
+            "rate_per_second": self.rate,
+            "burst": self.burst,
+            "available_tokens": round(self._tokens, 2),
+            "waiting": sum(1 for _, _, future, _ in self._waiters if not future.done()),
+            "priorities": {
+                name: {
+                    **stats,
+                    "avg_wait_seconds": stats["total_wait_seconds"] / stats["requests"] if stats["requests"] else 0.0,
+                }
+                for name, stats in self._stats.items()
+            },
+        }
+
+
+@lru_cache
+def get_quota_manager() -> QuotaManager:
+    """This worker's share of the Google quota; the bucket lives in process memory."""
+    settings = get_settings()
+    workers = max(settings.WEB_CONCURRENCY, 1)
+    return QuotaManager(settings.GOOGLE_QUOTA_REQUESTS_PER_SECOND / workers, settings.GOOGLE_QUOTA_BURST // workers)
@@ -0,0 +1,287 @@
+import logging
+import re
+from datetime import date, datetime, timedelta, timezone
+from zoneinfo import ZoneInfo, ZoneInfoNotFoundError
+
+from dateutil.rrule import rruleset, rrulestr
+
+from app.calendar.helpers import parse_event_time, parse_ical_datetime
+
+logger = logging.getLogger(__name__)
+
+MAX_INSTANCES_PER_MASTER = 5000
+_UNTIL_PATTERN = re.compile(r"UNTIL=([0-9T]+)Z?")
+_INSTANCE_ID = re.compile(r"^(?P<master>.+)_(?P<start>\d{8}(?:T\d{6}Z)?)$")
+
+
+def is_all_day(event: dict) -> bool:
+    start = event.get("start") or {}
+    return bool(start.get("date")) and not start.get("dateTime")
+
+
+def to_js_iso(value: datetime) -> str:
+    value = value.astimezone(timezone.utc)
+    return value.strftime("%Y-%m-%dT%H:%M:%S.") + f"{value.microsecond // 1000:03d}Z"
+
+
+def google_instance_id(master_event_id: str, instance_start: datetime, all_day: bool) -> str:
+    instance_start = instance_start.astimezone(timezone.utc)
+    if all_day:
+        return f"{master_event_id}_{instance_start.strftime('%Y%m%d')}"
+    return f"{master_event_id}_{instance_start.strftime('%Y%m%dT%H%M%SZ')}"
+
+
+def split_instance_id(event_id: str) -> tuple[str, str | None]:
+    match = _INSTANCE_ID.match(event_id)
+    return (match.group("master"), match.group("start")) if match else (event_id, None)
+
+
+def instance_original_start(master: dict, instance_key: str) -> dict:
+    if len(instance_key) == 8:
+        return {"date": f"{instance_key[:4]}-{instance_key[4:6]}-{instance_key[6:]}"}
+    start = datetime.strptime(instance_key, "%Y%m%dT%H%M%SZ").replace(tzinfo=timezone.utc)
+    original = {"dateTime": start.isoformat()}
+    if (master.get("start") or {}).get("timeZone"):
+        original["timeZone"] = master["start"]["timeZone"]
+    return original
+
+
+def shift_time(value: dict, delta: timedelta) -> dict:
+    if value.get("date"):
+        shifted = date.fromisoformat(str(value["date"])) + timedelta(days=delta.days)
+        return {"date": shifted.isoformat()}
+    start = parse_event_time(value)
+    shifted_value = {"dateTime": (start + delta).isoformat()} if start else {}
+    if value.get("timeZone"):
+        shifted_value["timeZone"] = value["timeZone"]
+    return shifted_value
+
+
+def materialize_instance(master: dict, event_id: str) -> dict:
+    """Build the exception that replaces one occurrence of a master, keyed by its Google-style instance id."""
+    master_id, instance_key = split_instance_id(event_id)
+    if instance_key is None:
+        raise ValueError(f"{event_id} is not an instance id")
+    original = instance_original_start(master, instance_key)
+    duration = (parse_event_time(master.get("end")) or parse_event_time(master["start"])) - parse_event_time(master["start"])
+    return {
+        **{key: value for key, value in master.items() if key not in ("recurrence", "id")},
+        "id": event_id,
+        "recurringEventId": master_id,
+        "originalStartTime": original,
+        "start": original,
+        "end": shift_time(original, duration or timedelta(0)),
+    }
+
+
+def instance_start_key(instance_start: datetime, all_day: bool) -> str:
+    if all_day:
+        return instance_start.astimezone(timezone.utc).strftime("%Y-%m-%d")
+    return to_js_iso(instance_start)
+
+
+def _format_event_time(value: datetime, all_day: bool, time_zone: str | None) -> dict:
+    if all_day:
+        return {"date": value.astimezone(timezone.utc).strftime("%Y-%m-%d")}
+    formatted = {"dateTime": to_js_iso(value)}
+    if time_zone:
+        formatted["timeZone"] = time_zone
+    return formatted
+
+
+def normalize_until(rule: str) -> str:
+    def replace(match: re.Match) -> str:
+        value = match.group(1)
+        if "T" not in value:
+            value = f"{value}T235959"
+        return f"UNTIL={value}Z"
+    return _UNTIL_PATTERN.sub(replace, rule)
+
+
+def event_zone(event: dict) -> ZoneInfo | timezone:
+    name = (event.get("start") or {}).get("timeZone")
+    if name:
+        try:
+            return ZoneInfo(name)
+        except (ZoneInfoNotFoundError, ValueError):
+            logger.warning("Unknown time zone %s on event %s", name, event.get("googleEventId") or event.get("id"))
+    return timezone.utc
+
+
+def param_zone(params: str) -> ZoneInfo | None:
+    for param in params.split(";")[1:]:
+        if param.startswith("TZID="):
+            try:
+                return ZoneInfo(param[5:])
+            except (ZoneInfoNotFoundError, ValueError):
+                return None
+    return None
+
+
+def build_rule_set(master: dict, dtstart: datetime) -> rruleset | None:
+    rule_set = rruleset()
+    has_rule = False
+    try:
+        for line in master.get("recurrence") or []:
+            if line.startswith("RRULE:"):
+                rule_set.rrule(rrulestr(normalize_until(line[6:]), dtstart=dtstart))
+                has_rule = True
+            elif line.startswith(("EXDATE:", "EXDATE;", "RDATE:", "RDATE;")):
+                params, raw_values = line.split(":", 1)
+                zone = param_zone(params)
+                for raw in raw_values.split(","):
+                    value = parse_ical_datetime(raw)
+                    if value is None:
+                        continue
+                    if zone is not None and "T" in raw and not raw.strip().endswith("Z"):
+                        value = value.replace(tzinfo=zone)
+                    if line.startswith("EXDATE"):
+                        rule_set.exdate(value)
+                    else:
+                        rule_set.rdate(value)
+                        has_rule = True
+    except (ValueError, TypeError):
+        logger.warning("Failed to parse recurrence for event %s: %s", master.get("googleEventId"), master.get("recurrence"))
+        return None
+    return rule_set if has_rule else None
+
+
+def _exception_matches(instance_start: datetime, exception: dict, all_day: bool) -> bool:
+    original = exception.get("originalStartTime") or {}
+    if all_day:
+        return original.get("date") == instance_start.astimezone(timezone.utc).strftime("%Y-%m-%d")
+    original_start = parse_event_time(original)
+    if original_start is None:
+        return False
+    return abs((instance_start - original_start).total_seconds()) < 1
+
+
+def expand_master(
+    master: dict,
+    exceptions: list[dict],
+    range_start: datetime,
+    range_end: datetime,
+    completion_keys: set[str],
+) -> tuple[list[dict], set[str]]:
+    all_day = is_all_day(master)
+    start = parse_event_time(master.get("start"))
+    end = parse_event_time(master.get("end")) or start
+    if start is None or end is None:
+        return [], set()
+
+    duration = end - start
+    if all_day:
+        dtstart = start
+    else:
+        dtstart = start.astimezone(event_zone(master))
+
+    rule_set = build_rule_set(master, dtstart)
+    if rule_set is None:
+        return [], set()
+
+    master_id = str(master["googleEventId"])
+    instances: list[dict] = []
+    used_exceptions: set[str] = set()
+    search_start = range_start - duration
+    for index, occurrence in enumerate(rule_set.between(search_start, range_end, inc=True)):
+        if index >= MAX_INSTANCES_PER_MASTER:
+            logger.warning("Truncated expansion of event %s at %s instances", master_id, MAX_INSTANCES_PER_MASTER)
+            break
+        occurrence = occurrence.astimezone(timezone.utc)
+        if occurrence + duration <= range_start and duration > timedelta(0):
+            continue
+
+        exception = next((exc for exc in exceptions if _exception_matches(occurrence, exc, all_day)), None)
+        if exception is not None:
+            used_exceptions.add(str(exception["googleEventId"]))
+            if exception.get("status") == "cancelled":
+                continue
+            instances.append({
+                **exception,
+                "isVirtual": False,
+                "originalMasterId": master_id,
+                "completed": f"{master_id}|{instance_start_key(occurrence, all_day)}" in completion_keys,
+            })
+            continue
+
+        instances.append({
+            **master,
+            "googleEventId": google_instance_id(master_id, occurrence, all_day),
+            "start": _format_event_time(occurrence, all_day, (master.get("start") or {}).get("timeZone")),
+            "end": _format_event_time(occurrence + duration, all_day, (master.get("end") or {}).get("timeZone")),
+            "recurrence": None,
+            "recurringEventId": master_id,
+            "originalStartTime": _format_event_time(occurrence, all_day, (master.get("start") or {}).get("timeZone")),
+            "isVirtual": True,
+            "originalMasterId": master_id,
+            "completed": f"{master_id}|{instance_start_key(occurrence, all_day)}" in completion_keys,
+        })
+    return instances, used_exceptions
+
+
+def _overlaps(event: dict, range_start: datetime, range_end: datetime) -> bool:
+    start = parse_event_time(event.get("start"))
+    end = parse_event_time(event.get("end")) or start
+    if start is None or end is None:
+        return False
+    return start < range_end and (end > range_start or (end == start and start >= range_start))
+
+
+def expand_events(
+    events: list[dict],
+    masters: list[dict],
+    exceptions: list[dict],
+    range_start: datetime,
+    range_end: datetime,
+    completions: list[dict] | None = None,
+) -> list[dict]:
+    completion_keys = {
+        f"{c['master_event_id']}|{c['instance_start']}" for c in completions or []
+    }
+
+    exceptions_by_master: dict[str, list[dict]] = {}
+    for exception in exceptions:
+        master_id = exception.get("recurringEventId")
+        if master_id:
+            exceptions_by_master.setdefault(str(master_id), []).append(exception)
+
+    expanded: list[dict] = []
+    seen: set[str] = set()
+
+    for event in events:
+        if event.get("status") == "cancelled" or not _overlaps(event, range_start, range_end):
+            continue
+        event_id = str(event["googleEventId"])
+        if event_id in seen:
+            continue
+        seen.add(event_id)
+        start = event.get("start") or {}
+        instance_start = start.get("dateTime") or start.get("date")
+        expanded.append({
+            **event,
+            "isVirtual": False,
+            "completed": f"{event_id}|{instance_start}" in completion_keys,
+        })
+
+    for master in masters:
+        if master.get("status") == "cancelled" or not master.get("recurrence"):
+            continue
+        master_id = str(master["googleEventId"])
+        instances, used = expand_master(
+            master, exceptions_by_master.get(master_id, []), range_start, range_end, completion_keys,
+        )
+        for instance in instances:
+            if instance["googleEventId"] not in seen:
+                seen.add(instance["googleEventId"])
+                expanded.append(instance)
+
+        for exception in exceptions_by_master.get(master_id, []):
+            exception_id = str(exception["googleEventId"])
+            if exception_id in used or exception_id in seen or exception.get("status") == "cancelled":
+                continue
+            if _overlaps(exception, range_start, range_end):
+                seen.add(exception_id)
+                expanded.append({**exception, "isVirtual": False, "originalMasterId": master_id, "completed": False})
+
+    expanded.sort(key=lambda e: parse_event_time(e.get("start")) or range_start)
+    return expanded
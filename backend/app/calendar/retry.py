@@ -0,0 +1,80 @@
+import logging
+import random
+from dataclasses import dataclass
+from datetime import datetime, timezone
+from email.utils import parsedate_to_datetime
+
+from app.calendar.constants import GoogleCalendarConfig
+from app.core.coordination import get_coordination
+
+logger = logging.getLogger(__name__)
+
+
+@dataclass(frozen=True)
+class RetryPolicy:
+    max_attempts: int = GoogleCalendarConfig.MAX_RETRIES
+    base_delay: float = GoogleCalendarConfig.BASE_DELAY_SECONDS
+    max_delay: float = GoogleCalendarConfig.MAX_DELAY_SECONDS
+    deadline_seconds: float = GoogleCalendarConfig.REQUEST_DEADLINE_SECONDS
+
+    def backoff(self, attempt: int, retry_after: float | None = None) -> float:
+        if retry_after is not None:
+            return retry_after
+        return random.uniform(0, min(self.max_delay, self.base_delay * 2 ** attempt))
+
+
+DEFAULT_RETRY_POLICY = RetryPolicy()
+
+
+def parse_retry_after(value: str | None) -> float | None:
+    if not value:
+        return None
+    try:
+        return max(float(value), 0.0)
+    except ValueError:
+        pass
+    try:
+        retry_at = parsedate_to_datetime(value)
+    except (TypeError, ValueError):
+        return None
+    if retry_at.tzinfo is None:
+        retry_at = retry_at.replace(tzinfo=timezone.utc)
+    return max((retry_at - datetime.now(timezone.utc)).total_seconds(), 0.0)
+
+
+class CircuitBreaker:
+    """Opens an account's circuit once failure_threshold failures land within failure_window_seconds.
+    Failures are counted in the coordination backend, so every worker's failures count toward the threshold."""
+
+    def __init__(self, failure_threshold: int, cooldown_seconds: float, failure_window_seconds: float):
+        self.failure_threshold = failure_threshold
+        self.cooldown_seconds = cooldown_seconds
+        self.failure_window_seconds = failure_window_seconds
+
+    @staticmethod
+    def _key(account_id: str) -> str:
+        return f"google-circuit:{account_id}"
+
+    def _failure_key(self, account_id: str, slot: int) -> str:
+        return f"{self._key(account_id)}:failure:{slot}"
+
+    async def is_open(self, account_id: str) -> bool:
+        return await get_coordination().exists(self._key(account_id))
+
+    async def record_failure(self, account_id: str) -> bool:
+        coordination = get_coordination()
+        for slot in range(self.failure_threshold - 1):
+            if await coordination.claim(self._failure_key(account_id, slot), self.failure_window_seconds):
+                return False
+        logger.warning("Opening circuit for Google account %s for %ss", account_id, self.cooldown_seconds)
+        await coordination.touch(self._key(account_id), self.cooldown_seconds)
+        for slot in range(self.failure_threshold - 1):
+            await coordination.release(self._failure_key(account_id, slot))
+        return True
+
+
+google_circuit_breaker = CircuitBreaker(
+    GoogleCalendarConfig.CIRCUIT_FAILURE_THRESHOLD,
+    GoogleCalendarConfig.CIRCUIT_COOLDOWN_SECONDS,
+    GoogleCalendarConfig.CIRCUIT_FAILURE_WINDOW_SECONDS,
+)
@@ -0,0 +1,347 @@
+import asyncio
+import logging
+import os
+import uuid
+from datetime import datetime, timedelta, timezone
+
+from supabase import Client
+
+from app.calendar.channels import sweep_watch_channels
+from app.calendar.constants import SyncSchedulerConfig
+from app.calendar.providers.caldav import CREDENTIALS_TABLE as CALDAV_CREDENTIALS_TABLE
+from app.calendar.quota import RequestPriority
+from app.calendar.sync import CalendarListSync, Sync
+from app.config import get_settings
+from app.core.coordination import get_coordination
+from app.core.db_utils import Row, all_rows, first_row
+from app.core.dependencies import get_http_client
+from app.core.live_updates import publish_calendar_changed
+from app.core.supabase import create_supabase_client
+
+logger = logging.getLogger(__name__)
+
+CALENDAR_LIST_SYNC_LEASE = "scheduler:calendar-list-sync"
+
+
+class JobStatus:
+    QUEUED = "queued"
+    RUNNING = "running"
+    IDLE = "idle"
+    FAILED = "failed"
+
+
+def _now() -> datetime:
+    return datetime.now(timezone.utc)
+
+
+def enqueue_sync_job(supabase: Client, calendar_id: str, user_id: str, reason: str) -> None:
+    jobs = supabase.table("calendar_sync_jobs")
+    # A running job is never re-queued, or a second worker could claim it; it is flagged to run again when it finishes.
+    for _ in range(3):
+        requeued = (
+            jobs.update({"status": JobStatus.QUEUED, "reason": reason, "next_run_at": _now().isoformat()})
+            .eq("google_calendar_id", calendar_id)
+            .neq("status", JobStatus.RUNNING)
+            .execute()
+        )
+        if first_row(requeued.data) is not None:
+            return
+        flagged = (
+            jobs.update({"rerun_requested": True, "reason": reason})
+            .eq("google_calendar_id", calendar_id)
+            .eq("status", JobStatus.RUNNING)
+            .execute()
+        )
+        if first_row(flagged.data) is not None:
+            return
+        inserted = jobs.upsert({
+            "google_calendar_id": calendar_id,
+            "user_id": user_id,
+            "status": JobStatus.QUEUED,
+            "reason": reason,
+            "next_run_at": _now().isoformat(),
+        }, on_conflict="google_calendar_id", ignore_duplicates=True).execute()
+        if first_row(inserted.data) is not None:
+            return
+    logger.warning("Could not enqueue sync job for calendar %s", calendar_id)
+
+
+def seed_sync_jobs(supabase: Client) -> int:
+    calendars = [
+        *all_rows(
+            supabase.table("google_calendars")
+            .select("id, google_accounts!inner(user_id, google_account_tokens!inner(refresh_token))")
+            .not_.is_("google_accounts.google_account_tokens.refresh_token", "null")
+            .execute()
+            .data
+        ),
+        *all_rows(
+            supabase.table("google_calendars")
+            .select(f"id, google_accounts!inner(user_id, {CALDAV_CREDENTIALS_TABLE}!inner(google_account_id))")
+            .execute()
+            .data
+        ),
+    ]
+    if not calendars:
+        return 0
+    rows = [
+        {
+            "google_calendar_id": cal["id"],
+            "user_id": cal["google_accounts"]["user_id"],
+            "status": JobStatus.QUEUED,
+            "reason": "scheduled",
+            "next_run_at": _now().isoformat(),
+        }
+        for cal in calendars
+    ]
+    supabase.table("calendar_sync_jobs").upsert(
+        rows, on_conflict="google_calendar_id", ignore_duplicates=True,
+    ).execute()
+    return len(rows)
+
+
+def get_syncable_accounts(supabase: Client) -> list[Row]:
+    return [
+        *all_rows(
+            supabase.table("google_accounts")
+            .select("id, user_id, google_account_tokens!inner(refresh_token)")
+            .not_.is_("google_account_tokens.refresh_token", "null")
+            .execute()
+            .data
+        ),
+        *all_rows(
+            supabase.table("google_accounts")
+            .select(f"id, user_id, {CALDAV_CREDENTIALS_TABLE}!inner(google_account_id)")
+            .execute()
+            .data
+        ),
+    ]
+
+
+async def run_calendar_list_sync(user_id: str, google_account_id: str) -> None:
+    try:
+        http = await get_http_client()
+        await CalendarListSync(create_supabase_client(), http, user_id, google_account_id, priority=RequestPriority.BACKGROUND).run()
+    except Exception:
+        logger.exception("Calendar list sync failed for account %s", google_account_id)
+
+
+async def run_calendar_sync(user_id: str, calendar_id: str) -> None:
+    """Syncs a calendar in the calling worker, for deployments where no scheduler drains the job queue."""
+    try:
+        http = await get_http_client()
+        sync = Sync(create_supabase_client(), http, user_id, calendar_id, priority=RequestPriority.BACKGROUND)
+        await sync.run()
+        if sync.changed:
+            publish_calendar_changed(user_id, calendar_id, sync.changed_events)
+    except Exception:
+        logger.exception("Sync failed for calendar %s", calendar_id)
+
+
+def recover_stale_jobs(supabase: Client) -> None:
+    cutoff = _now() - timedelta(seconds=SyncSchedulerConfig.JOB_LEASE_SECONDS)
+    supabase.table("calendar_sync_jobs").update({
+        "status": JobStatus.QUEUED,
+        "locked_by": None,
+        "next_run_at": _now().isoformat(),
+    }).eq("status", JobStatus.RUNNING).lt("started_at", cutoff.isoformat()).execute()
+
+
+def get_due_jobs(supabase: Client, limit: int) -> list[Row]:
+    return all_rows(
+        supabase.table("calendar_sync_jobs")
+        .select("*")
+        .neq("status", JobStatus.RUNNING)
+        .lte("next_run_at", _now().isoformat())
+        .order("next_run_at")
+        .limit(limit)
+        .execute()
+        .data
+    )
+
+
+def claim_job(supabase: Client, job: Row, worker_id: str) -> Row | None:
+    result = (
+        supabase.table("calendar_sync_jobs")
+        .update({
+            "status": JobStatus.RUNNING,
+            "locked_by": worker_id,
+            "started_at": _now().isoformat(),
+            "rerun_requested": False,
+        })
+        .eq("google_calendar_id", job["google_calendar_id"])
+        .eq("status", job["status"])
+        .execute()
+    )
+    return first_row(result.data)
+
+
+def finish_job(supabase: Client, job: Row, error: dict | None) -> None:
+    settings = get_settings()
+    finished_at = _now()
+    if error is None:
+        update = {
+            "status": JobStatus.IDLE,
+            "attempts": 0,
+            "last_error": None,
+            "last_success_at": finished_at.isoformat(),
+            "next_run_at": (finished_at + timedelta(seconds=settings.SYNC_SCHEDULER_INTERVAL_SECONDS)).isoformat(),
+        }
+    else:
+        attempts = int(job.get("attempts") or 0) + 1
+        delay = min(
+            SyncSchedulerConfig.RETRY_BASE_SECONDS * 2 ** (attempts - 1),
+            settings.SYNC_SCHEDULER_INTERVAL_SECONDS,
+        )
+        update = {
+            "status": JobStatus.FAILED,
+            "attempts": attempts,
+            "last_error": f"{error.get('code')}: {error.get('message')}",
+            "next_run_at": (finished_at + timedelta(seconds=delay)).isoformat(),
+        }
+    update = {**update, "locked_by": None, "finished_at": finished_at.isoformat()}
+    finished = (
+        supabase.table("calendar_sync_jobs")
+        .update(update)
+        .eq("google_calendar_id", job["google_calendar_id"])
+        .eq("locked_by", job["locked_by"])
+        .eq("rerun_requested", False)
+        .execute()
+    )
+    if first_row(finished.data) is None:
+        # Enqueued while it ran, so the changes it was asked to pick up may have landed after this run read them.
+        supabase.table("calendar_sync_jobs").update({
+            **update,
+            "status": JobStatus.QUEUED,
+            "rerun_requested": False,
+            "next_run_at": finished_at.isoformat(),
+        }).eq("google_calendar_id", job["google_calendar_id"]).eq("locked_by", job["locked_by"]).execute()
+
+
+class SyncScheduler:
+    def __init__(self):
+        self.worker_id = f"{os.getpid()}-{uuid.uuid4().hex[:8]}"
+        self._task: asyncio.Task | None = None
+        self._wake = asyncio.Event()
+        self._running: set[asyncio.Task] = set()
+        self._calendar_list_task: asyncio.Task | None = None
+        self._semaphore = asyncio.Semaphore(SyncSchedulerConfig.MAX_CONCURRENT_JOBS)
+
+    def start(self) -> None:
+        settings = get_settings()
+        if not settings.SYNC_SCHEDULER_ENABLED or self._task is not None:
+            return
+        self._task = asyncio.create_task(self._loop())
+        logger.info("Sync scheduler %s started", self.worker_id)
+
+    async def stop(self) -> None:
+        if self._task is None:
+            return
+        tasks = [self._task, *self._running, *([self._calendar_list_task] if self._calendar_list_task else [])]
+        for task in tasks:
+            task.cancel()
+        await asyncio.gather(*tasks, return_exceptions=True)
+        self._task = None
+        self._calendar_list_task = None
+        self._running.clear()
+        logger.info("Sync scheduler %s stopped", self.worker_id)
+
+    def wake(self) -> None:
+        self._wake.set()
+
+    async def _loop(self) -> None:
+        await asyncio.sleep(SyncSchedulerConfig.STARTUP_DELAY_SECONDS)
+        supabase = create_supabase_client()
+        last_seed: datetime | None = None
+        last_sweep: datetime | None = None
+        while True:
+            try:
+                await asyncio.to_thread(recover_stale_jobs, supabase)
+                if last_seed is None or _now() - last_seed >= timedelta(seconds=SyncSchedulerConfig.SEED_INTERVAL_SECONDS):
+                    await asyncio.to_thread(seed_sync_jobs, supabase)
+                    last_seed = _now()
+                if last_sweep is None or _now() - last_sweep >= timedelta(seconds=SyncSchedulerConfig.CHANNEL_SWEEP_INTERVAL_SECONDS):
+                    swept = await asyncio.to_thread(sweep_watch_channels, supabase)
+                    if swept:
+                        logger.info("Swept %s expired or orphaned watch channels", swept)
+                    last_sweep = _now()
+                await self._start_calendar_list_sync(supabase)
+                await self._dispatch(supabase)
+            except asyncio.CancelledError:
+                raise
+            except Exception:
+                logger.exception("Sync scheduler tick failed")
+
+            self._wake.clear()
+            try:
+                await asyncio.wait_for(self._wake.wait(), timeout=SyncSchedulerConfig.TICK_SECONDS)
+            except asyncio.TimeoutError:
+                pass
+
+    async def _start_calendar_list_sync(self, supabase: Client) -> None:
+        if self._calendar_list_task is not None and not self._calendar_list_task.done():
+            return
+        # One worker per interval runs the pass; the lease expiring is what schedules the next one.
+        if not await get_coordination().claim(CALENDAR_LIST_SYNC_LEASE, SyncSchedulerConfig.CALENDAR_LIST_SYNC_INTERVAL_SECONDS):
+            return
+        self._calendar_list_task = asyncio.create_task(self._sync_calendar_lists(supabase))
+
+    async def _sync_calendar_lists(self, supabase: Client) -> None:
+        accounts = await asyncio.to_thread(get_syncable_accounts, supabase)
+        limit = asyncio.Semaphore(SyncSchedulerConfig.MAX_CONCURRENT_CALENDAR_LIST_SYNCS)
+
+        async def sync_account(account: Row) -> None:
+            async with limit:
+                await run_calendar_list_sync(str(account["user_id"]), str(account["id"]))
+
+        await asyncio.gather(*(sync_account(account) for account in accounts))
+
+    async def _dispatch(self, supabase: Client) -> None:
+        capacity = SyncSchedulerConfig.MAX_CONCURRENT_JOBS - len(self._running)
+        if capacity <= 0:
+            return
+        jobs = await asyncio.to_thread(get_due_jobs, supabase, capacity)
+        for job in jobs:
+            claimed = await asyncio.to_thread(claim_job, supabase, job, self.worker_id)
+            if claimed is None:
+                continue
+            task = asyncio.create_task(self._run_job(claimed))
+            self._running.add(task)
+            task.add_done_callback(self._running.discard)
+
+    async def _run_job(self, job: Row) -> None:
+        calendar_id = str(job["google_calendar_id"])
+        supabase = create_supabase_client()
+        error: dict | None = None
+        backfill_pending = False
+        async with self._semaphore:
+            try:
+                http = await get_http_client()
+                sync = Sync(supabase, http, str(job["user_id"]), calendar_id, backfill=True, priority=RequestPriority.BACKGROUND)
+                async with asyncio.timeout(SyncSchedulerConfig.JOB_TIMEOUT_SECONDS):
+                    await sync.run()
+                error = sync.error
+                backfill_pending = sync.backfill_pending
+                if sync.changed:
+                    publish_calendar_changed(str(job["user_id"]), calendar_id, sync.changed_events)
+            except TimeoutError:
+                error = {"code": "408", "message": "Sync timed out"}
+            except asyncio.CancelledError:
+                await asyncio.to_thread(finish_job, supabase, job, {"code": "499", "message": "Sync cancelled"})
+                raise
+            except Exception as e:
+                logger.exception("Scheduled sync crashed for calendar %s", calendar_id)
+                error = {"code": "500", "message": str(e)}
+
+        if error is not None:
+            logger.warning(
+                "Scheduled sync failed for calendar %s (attempt %s): %s %s",
+                calendar_id, int(job.get("attempts") or 0) + 1, error.get("code"), error.get("message"),
+            )
+        await asyncio.to_thread(finish_job, supabase, job, error)
+        if error is None and backfill_pending:
+            await asyncio.to_thread(enqueue_sync_job, supabase, calendar_id, str(job["user_id"]), "backfill")
+            self.wake()
+
+
+sync_scheduler = SyncScheduler()
@@ -0,0 +1,258 @@
+import logging
+from datetime import datetime, timedelta, timezone
+
+from dateutil.rrule import rrulestr
+from supabase import Client
+
+from app.calendar.helpers import ProviderError, parse_event_time, parse_ical_datetime
+from app.calendar.providers import CalendarProvider
+from app.calendar.recurrence import (
+    event_zone,
+    google_instance_id,
+    instance_start_key,
+    is_all_day,
+    normalize_until,
+    param_zone,
+)
+from app.core.db_utils import all_rows
+from app.models.event import Event, EventDateTime, EventPatch
+
+logger = logging.getLogger(__name__)
+
+SERIES_FIELDS = (
+    "summary", "description", "location", "attendees", "reminders",
+    "colorId", "visibility", "transparency",
+)
+EXCEPTION_FIELDS = SERIES_FIELDS + ("start", "end", "status")
+
+
+class SeriesSplitError(ValueError):
+    pass
+
+
+def _rule_parts(rule: str) -> list[tuple[str, str]]:
+    return [tuple(part.split("=", 1)) for part in rule.split(";") if "=" in part]
+
+
+def _format_until(split_at: datetime, all_day: bool) -> str:
+    if all_day:
+        return (split_at - timedelta(days=1)).strftime("%Y%m%d")
+    return (split_at - timedelta(seconds=1)).astimezone(timezone.utc).strftime("%Y%m%dT%H%M%SZ")
+
+
+def _format_ical(value: datetime, all_day: bool) -> str:
+    if all_day:
+        return value.astimezone(timezone.utc).strftime("%Y%m%d")
+    return value.astimezone(timezone.utc).strftime("%Y%m%dT%H%M%SZ")
+
+
+def _date_lines(recurrence: list[str], keep, offset: timedelta, all_day: bool) -> list[str]:
+    lines = []
+    for prefix in ("EXDATE", "RDATE"):
+        values: list[datetime] = []
+        for line in recurrence:
+            if not line.startswith((prefix + ":", prefix + ";")):
+                continue
+            params, raw_values = line.split(":", 1)
+            zone = param_zone(params)
+            for raw in raw_values.split(","):
+                value = parse_ical_datetime(raw)
+                if value is None:
+                    continue
+                if zone is not None and "T" in raw and not raw.strip().endswith("Z"):
+                    value = value.replace(tzinfo=zone)
+                if keep(value):
+                    values.append(value + offset)
+        if values:
+            formatted = ",".join(_format_ical(value, all_day) for value in sorted(values))
+            lines.append(f"{prefix};VALUE=DATE:{formatted}" if all_day else f"{prefix}:{formatted}")
+    return lines
+
+
+def truncate_recurrence(recurrence: list[str], split_at: datetime, all_day: bool) -> list[str]:
+    truncated = []
+    for line in recurrence:
+        if line.startswith("RRULE:"):
+            parts = [(k, v) for k, v in _rule_parts(line[6:]) if k not in ("COUNT", "UNTIL")]
+            parts.append(("UNTIL", _format_until(split_at, all_day)))
+            truncated.append("RRULE:" + ";".join(f"{k}={v}" for k, v in parts))
+        elif not line.startswith(("EXDATE", "RDATE")):
+            truncated.append(line)
+    return truncated + _date_lines(recurrence, lambda value: value < split_at, timedelta(0), all_day)
+
+
+def following_recurrence(
+    recurrence: list[str],
+    dtstart: datetime,
+    split_at: datetime,
+    offset: timedelta,
+    all_day: bool,
+    cancelled: list[datetime],
+) -> list[str]:
+    following = []
+    for line in recurrence:
+        if line.startswith("RRULE:"):
+            parts = _rule_parts(line[6:])
+            count = next((int(v) for k, v in parts if k == "COUNT"), None)
+            if count is not None:
+                rule = rrulestr(normalize_until(line[6:]), dtstart=dtstart)
+                remaining = count - sum(1 for occurrence in rule if occurrence < split_at)
+                if remaining <= 0:
+                    raise SeriesSplitError("Series has no instances after the split point")
+                parts = [(k, str(remaining) if k == "COUNT" else v) for k, v in parts]
+            elif offset:
+                parts = [
+                    (k, _format_ical(parse_ical_datetime(v) + offset, "T" not in v) if k == "UNTIL" else v)
+                    for k, v in parts
+                ]
+            following.append("RRULE:" + ";".join(f"{k}={v}" for k, v in parts))
+        elif not line.startswith(("EXDATE", "RDATE")):
+            following.append(line)
+
+    following += _date_lines(recurrence, lambda value: value >= split_at, offset, all_day)
+    if cancelled:
+        formatted = ",".join(_format_ical(value + offset, all_day) for value in sorted(cancelled))
+        following.append(f"EXDATE;VALUE=DATE:{formatted}" if all_day else f"EXDATE:{formatted}")
+    return following
+
+
+def _format_event_time(value: datetime, all_day: bool, master_time: dict, zone) -> dict:
+    if all_day:
+        return {"date": value.astimezone(timezone.utc).strftime("%Y-%m-%d")}
+    formatted = {"dateTime": value.astimezone(zone).isoformat()}
+    if master_time.get("timeZone"):
+        formatted["timeZone"] = master_time["timeZone"]
+    return formatted
+
+
+def _later_exceptions(supabase: Client, calendar_id: str, source: str, master_id: str, split_at: datetime) -> list[dict]:
+    rows = all_rows(
+        supabase.table("events")
+        .select("*")
+        .eq("googleCalendarId", calendar_id)
+        .eq("recurringEventId", master_id)
+        .eq("source", source)
+        .execute()
+        .data
+    )
+    later = []
+    for row in rows:
+        original = parse_event_time(row.get("originalStartTime"))
+        if original is not None and original >= split_at:
+            later.append({**row, "_original": original})
+    return later
+
+
+def migrate_completions(
+    supabase: Client,
+    calendar_id: str,
+    master_id: str,
+    new_master_id: str,
+    split_at: datetime,
+    offset: timedelta,
+    all_day: bool,
+) -> None:
+    rows = all_rows(
+        supabase.table("completed_events")
+        .select("*")
+        .eq("google_calendar_id", calendar_id)
+        .eq("master_event_id", master_id)
+        .execute()
+        .data
+    )
+    for row in rows:
+        raw = str(row["instance_start"])
+        instance = parse_event_time({"date": raw} if len(raw) == 10 else {"dateTime": raw})
+        if instance is None or instance < split_at:
+            continue
+        supabase.table("completed_events").upsert(
+            {
+                "user_id": row["user_id"],
+                "google_calendar_id": calendar_id,
+                "master_event_id": new_master_id,
+                "instance_start": instance_start_key(instance + offset, all_day),
+            },
+            on_conflict="google_calendar_id, master_event_id, instance_start",
+        ).execute()
+        supabase.table("completed_events").delete().eq(
+            "google_calendar_id", calendar_id
+        ).eq("master_event_id", master_id).eq("instance_start", raw).execute()
+
+
+async def split_series(
+    client: CalendarProvider,
+    supabase: Client,
+    calendar: dict,
+    master_id: str,
+    split_start: EventDateTime,
+    patch: EventPatch,
+) -> dict:
+    google_calendar_id = calendar["google_calendar_id"]
+    master = await client.get_event(google_calendar_id, master_id)
+    recurrence = master.get("recurrence") or []
+    if not recurrence or master.get("recurringEventId"):
+        raise SeriesSplitError("Event is not a recurring series master")
+
+    all_day = is_all_day(master)
+    zone = event_zone(master)
+    master_start = parse_event_time(master.get("start"))
+    master_end = parse_event_time(master.get("end")) or master_start
+    split_at = parse_event_time(split_start.model_dump(exclude_none=True))
+    if master_start is None or master_end is None or split_at is None:
+        raise SeriesSplitError("Series or split point has no start time")
+
+    if split_at <= master_start:
+        updated = await client.edit_event(google_calendar_id, master_id, patch)
+        return {"master": updated, "series": None, "exceptions": [], "cancelled": []}
+
+    dtstart = master_start if all_day else master_start.astimezone(zone)
+    duration = master_end - master_start
+    new_start = patch.start.model_dump(exclude_none=True) if patch.start else _format_event_time(split_at, all_day, master["start"], zone)
+    new_end = patch.end.model_dump(exclude_none=True) if patch.end else None
+    new_start_at = parse_event_time(new_start)
+    if new_start_at is None:
+        raise SeriesSplitError("Invalid start time")
+    offset = new_start_at - split_at
+    if new_end is None:
+        new_end = _format_event_time(new_start_at + duration, all_day, master.get("end") or {}, zone)
+
+    exceptions = _later_exceptions(supabase, calendar["id"], client.source, master_id, split_at)
+    # The instance being split on becomes the new series' first occurrence and takes this patch instead.
+    following = [exc for exc in exceptions if exc["_original"] > split_at]
+    cancelled = [exc["_original"] for exc in following if exc.get("status") == "cancelled"]
+    modified = [exc for exc in following if exc.get("status") != "cancelled"]
+
+    body = {field: master[field] for field in SERIES_FIELDS if master.get(field) is not None}
+    body.update(patch.model_dump(exclude_none=True, exclude={"completed", "status", "start", "end"}))
+    body["start"] = new_start
+    body["end"] = new_end
+    if patch.recurrence is None:
+        body["recurrence"] = following_recurrence(recurrence, dtstart, split_at, offset, all_day, cancelled)
+    body.setdefault("summary", "(No title)")
+
+    created = await client.create_event(google_calendar_id, Event(**body))
+    migrated: list[dict] = []
+    try:
+        for exc in modified:
+            instance_id = google_instance_id(created["id"], exc["_original"] + offset, all_day)
+            fields = {field: exc[field] for field in EXCEPTION_FIELDS if exc.get(field) is not None}
+            migrated.append(await client.edit_event(google_calendar_id, instance_id, EventPatch(**fields)))
+        truncated = await client.edit_event(
+            google_calendar_id, master_id,
+            EventPatch(recurrence=truncate_recurrence(recurrence, split_at, all_day)),
+        )
+    except ProviderError:
+        logger.warning("Rolling back series split of %s, deleting new series %s", master_id, created["id"])
+        try:
+            await client.delete_event(google_calendar_id, created["id"])
+        except ProviderError as e:
+            logger.error("Failed to roll back new series %s: %s", created["id"], e.message)
+        raise
+
+    migrate_completions(supabase, calendar["id"], master_id, created["id"], split_at, offset, all_day)
+    return {
+        "master": truncated,
+        "series": created,
+        "exceptions": migrated,
+        "cancelled": [str(exc["googleEventId"]) for exc in exceptions],
+    }
@@ -7,25 +7,68 @@ from datetime import datetime, timedelta, timezone
 import httpx
 from supabase import Client
 
-from app.calendar.constants import WEBHOOK_CHANNEL_BUFFER_HOURS
-from app.calendar.google_client import GoogleAPIClient
+from app.calendar.channels import (
+    ACCOUNT_SYNC_STATE_TABLE,
+    channel_needs_renewal,
+    stop_calendar_channels,
+    stop_watch_channel,
+)
+from app.calendar.constants import BackfillConfig
+from app.calendar.providers import CalendarProvider, provider_for_account, provider_for_calendar
+from app.calendar.quota import RequestPriority
 from app.calendar.helpers import (
-    GoogleAPIError,
+    ProviderError,
+    calendar_list_row,
     get_google_calendar,
-    transform_events,
 )
 from app.config import get_settings
+from app.core.db_utils import all_rows, first_row
+from app.core.live_updates import MAX_PUSHED_EVENTS, publish_calendars_changed
 
 logger = logging.getLogger(__name__)
 
 
+class BackfillStatus:
+    PAST = "past"
+    FUTURE = "future"
+    COMPLETE = "complete"
+    DISABLED = "disabled"
+
+
+def _parse_ts(value: object) -> datetime:
+    parsed = datetime.fromisoformat(str(value))
+    return parsed if parsed.tzinfo else parsed.replace(tzinfo=timezone.utc)
+
+
+async def remove_calendars(client: CalendarProvider, supabase: Client, calendar_ids: list[str]) -> None:
+    if not calendar_ids:
+        return
+    await stop_calendar_channels(client, supabase, calendar_ids)
+
+    def delete_rows():
+        supabase.table("events").delete().in_("googleCalendarId", calendar_ids).execute()
+        supabase.table("calendar_sync_jobs").delete().in_("google_calendar_id", calendar_ids).execute()
+        supabase.table("calendar_sync_state").delete().in_("google_calendar_id", calendar_ids).execute()
+        supabase.table("completed_events").delete().in_("google_calendar_id", calendar_ids).execute()
+        supabase.table("google_calendars").delete().in_("id", calendar_ids).execute()
+
+    await asyncio.to_thread(delete_rows)
+
+
 class Sync:
-    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, cal_id: str, events_queue: asyncio.Queue | None = None):
+    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, cal_id: str, events_queue: asyncio.Queue | None = None, backfill: bool = False, priority: RequestPriority = RequestPriority.SYNC):
         self.supabase = supabase
         self.http = http
         self.user_id = user_id
         self.cal_id = cal_id
         self.events_queue = events_queue
+        self.backfill = backfill
+        self.priority = priority
+        self.backfill_pending = False
+        self.calendar: dict | None = None
+        self.error: dict | None = None
+        self.changed = False
+        self.changed_events: list[dict] | None = []
 
     async def run(self):
         try:
@@ -35,7 +78,7 @@ class Sync:
                 return
 
             self.calendar = calendar
-            self.google_client = GoogleAPIClient(self.supabase, self.http, self.user_id, calendar["google_account_id"])
+            self.provider = provider_for_calendar(self.supabase, self.http, self.user_id, calendar, priority=self.priority)
 
             sync_state, self.contacts = await asyncio.gather(
                 asyncio.to_thread(self._get_sync_state),
@@ -46,16 +89,21 @@ class Sync:
 
             await self._sync_calendar(sync_token, page_token)
             await self._refresh_webhook()
+            if self.backfill and self.error is None:
+                await self._backfill_history()
+            await asyncio.shield(self._record_status())
         except Exception as e:
             logger.exception("Sync failed for calendar %s", self.cal_id)
             await self._emit({"type": "error", "calendar_id": self.cal_id, "code": "500", "message": str(e), "retryable": False})
+            if self.calendar:
+                await asyncio.shield(self._record_status())
         finally:
             await self._emit({"type": "calendar_done", "calendar_id": self.cal_id})
 
     async def _sync_contacts(self):
         contacts = {}
         try:
-            people = await self.google_client.fetch_contacts()
+            people = await self.provider.fetch_contacts()
         except Exception as e:
             logger.exception("Failed to fetch contacts for calendar %s", self.cal_id)
             return contacts
@@ -96,55 +144,138 @@ class Sync:
         is_retry = False
 
         while True:
-            current_page_token = None
-            upsert_tasks = []
+            window = None if sync_token else await asyncio.to_thread(self._ensure_sync_window, calendar_id)
             try:
-                async for page in self.google_client.fetch_events(
+                async for page in self.provider.fetch_events(
                     self.calendar["google_calendar_id"],
                     page_token=page_token,
                     sync_token=sync_token if not page_token else None,
+                    time_min=window[0] if window else None,
+                    time_max=window[1] if window else None,
                 ):
-                    current_page_token = page.get("next_page_token")
-                    transformed = transform_events(
-                        page["items"], calendar_id,
-                        self.calendar["google_account_id"], self.calendar.get("color"),
-                    )
+                    next_page_token = page.get("next_page_token")
+                    transformed = self.provider.transform_events(page["items"], self.calendar)
                     self._apply_display_names(transformed)
-                    if transformed:
-                        upsert_tasks.append(asyncio.create_task(self._save_events(transformed)))
+                    await asyncio.shield(self._persist_page(calendar_id, transformed, next_page_token))
+                    self._track_changes(transformed)
                     await self._emit({"type": "events", "calendar_id": calendar_id, "events": transformed})
 
-                    if not current_page_token and page.get("next_sync_token"):
-                        if upsert_tasks:
-                            await asyncio.gather(*upsert_tasks)
+                    if not next_page_token and page.get("next_sync_token"):
                         self._save_sync_state(calendar_id, page["next_sync_token"])
                         await self._emit({"type": "sync_token", "calendar_id": calendar_id})
 
-            except GoogleAPIError as e:
-                for task in upsert_tasks:
-                    if not task.done():
-                        task.cancel()
-                if upsert_tasks:
-                    await asyncio.gather(*upsert_tasks, return_exceptions=True)
-
+            except ProviderError as e:
                 if e.status_code == 410 and not is_retry:
                     self._clear_sync_state(calendar_id)
                     sync_token = None
                     page_token = None
                     is_retry = True
                     continue
-                if page_token and not is_retry:
+                if page_token and e.status_code == 400 and not is_retry:
                     page_token = None
                     is_retry = True
                     continue
-                if current_page_token and sync_token:
-                    self._save_sync_state(calendar_id, sync_token, current_page_token)
                 await self._emit({
                     "type": "error", "calendar_id": calendar_id,
                     "code": str(e.status_code), "message": e.message, "retryable": e.retryable,
                 })
             break
 
+    def _track_changes(self, events: list[dict]):
+        if not events:
+            return
+        self.changed = True
+        if self.changed_events is None:
+            return
+        if len(self.changed_events) + len(events) > MAX_PUSHED_EVENTS:
+            self.changed_events = None
+        else:
+            self.changed_events.extend(events)
+
+    def _ensure_sync_window(self, calendar_id: str) -> tuple[datetime, datetime]:
+        state = first_row(
+            self.supabase.table("calendar_sync_state")
+            .select("sync_window_start, sync_window_end, backfill_enabled")
+            .eq("google_calendar_id", calendar_id)
+            .limit(1)
+            .execute()
+            .data
+        )
+        if state and state.get("sync_window_start") and state.get("sync_window_end"):
+            return _parse_ts(state["sync_window_start"]), _parse_ts(state["sync_window_end"])
+
+        settings = get_settings()
+        now = datetime.now(timezone.utc)
+        window_start = now - timedelta(days=settings.INITIAL_SYNC_DAYS_BACK)
+        window_end = now + timedelta(days=settings.INITIAL_SYNC_DAYS_FORWARD)
+        backfill_enabled = not state or state.get("backfill_enabled") is not False
+        self.supabase.table("calendar_sync_state").upsert({
+            "google_calendar_id": calendar_id,
+            "sync_window_start": window_start.isoformat(),
+            "sync_window_end": window_end.isoformat(),
+            "backfill_status": BackfillStatus.PAST if backfill_enabled else BackfillStatus.DISABLED,
+            "backfill_page_token": None,
+            "backfill_events_synced": 0,
+        }, on_conflict="google_calendar_id").execute()
+        return window_start, window_end
+
+    async def _backfill_history(self):
+        calendar_id = self.calendar["id"]
+        state = first_row(await asyncio.to_thread(
+            lambda: self.supabase.table("calendar_sync_state")
+            .select("sync_window_start, sync_window_end, backfill_enabled, backfill_status, backfill_page_token, backfill_events_synced")
+            .eq("google_calendar_id", calendar_id)
+            .limit(1)
+            .execute()
+            .data
+        ))
+        if not state or state.get("backfill_enabled") is False:
+            return
+        status = state.get("backfill_status")
+        if status not in (BackfillStatus.PAST, BackfillStatus.FUTURE):
+            return
+
+        bounds = (
+            {"time_max": _parse_ts(state["sync_window_start"])}
+            if status == BackfillStatus.PAST
+            else {"time_min": _parse_ts(state["sync_window_end"])}
+        )
+        page_token = state.get("backfill_page_token")
+        synced = int(state.get("backfill_events_synced") or 0)
+        pages = 0
+        try:
+            async for page in self.provider.fetch_events(self.calendar["google_calendar_id"], page_token=page_token, **bounds):
+                transformed = self.provider.transform_events(page["items"], self.calendar)
+                self._apply_display_names(transformed)
+                if transformed:
+                    await asyncio.shield(self._save_events(transformed))
+                self._track_changes(transformed)
+                synced += len(transformed)
+                page_token = page.get("next_page_token")
+                pages += 1
+                if page_token and pages >= BackfillConfig.PAGES_PER_RUN:
+                    break
+        except ProviderError as e:
+            logger.warning("History backfill failed for calendar %s: %s", calendar_id, e.message)
+            return
+
+        if not page_token:
+            status = BackfillStatus.FUTURE if status == BackfillStatus.PAST else BackfillStatus.COMPLETE
+        self.backfill_pending = status != BackfillStatus.COMPLETE
+        await asyncio.to_thread(
+            lambda: self.supabase.table("calendar_sync_state").update({
+                "backfill_status": status,
+                "backfill_page_token": page_token,
+                "backfill_events_synced": synced,
+            }).eq("google_calendar_id", calendar_id).execute()
+        )
+
+    async def _persist_page(self, calendar_id: str, events: list[dict], next_page_token: str | None):
+        if events:
+            await self._save_events(events)
+        if next_page_token:
+            await asyncio.to_thread(self._save_page_token, calendar_id, next_page_token)
+
     def _apply_display_names(self, events: list[dict]):
         for event in events:
             for attendee in event.get("attendees") or []:
@@ -160,26 +291,20 @@ class Sync:
 
     async def _refresh_webhook(self):
         settings = get_settings()
-        if not settings.WEBHOOK_BASE_URL:
+        if not settings.WEBHOOK_BASE_URL or not self.provider.supports_push:
             return
 
         calendar_id = self.calendar["id"]
         sync_state = await asyncio.to_thread(self._get_sync_state)
-
-        expires_at = sync_state.get("webhook_expires_at") if sync_state else None
-        if expires_at:
-            parsed = datetime.fromisoformat(str(expires_at))
-            if parsed.tzinfo is None:
-                parsed = parsed.replace(tzinfo=timezone.utc)
-            if parsed > datetime.now(timezone.utc) + timedelta(hours=WEBHOOK_CHANNEL_BUFFER_HOURS):
-                return
+        if not channel_needs_renewal(sync_state.get("webhook_expires_at") if sync_state else None):
+            return
 
         try:
             channel_id = str(uuid.uuid4())
             channel_token = secrets.token_urlsafe(32)
-            webhook_url = f"{settings.WEBHOOK_BASE_URL}/calendar/webhook"
+            webhook_url = f"{settings.WEBHOOK_BASE_URL}{self.provider.webhook_path}"
 
-            result = await self.google_client.create_watch_channel(
+            result = await self.provider.create_watch_channel(
                 self.calendar["google_calendar_id"],
                 webhook_url,
                 channel_id,
@@ -195,16 +320,79 @@ class Sync:
                 }, on_conflict="google_calendar_id",
             ).execute()
 
-        except GoogleAPIError as e:
+            # Stop the previous channel only once its replacement is stored so no notifications are missed.
+            if sync_state:
+                await stop_watch_channel(
+                    self.provider,
+                    sync_state.get("webhook_channel_id"),
+                    sync_state.get("webhook_resource_id"),
+                )
+
+        except ProviderError as e:
             logger.warning("Webhook registration failed for calendar %s: %s", calendar_id, e.message)
 
+    async def _record_status(self):
+        try:
+            await asyncio.to_thread(self._save_status, self.calendar["id"])
+        except Exception:
+            logger.exception("Failed to record sync status for calendar %s", self.cal_id)
+
+    def _save_status(self, calendar_id: str):
+        now = datetime.now(timezone.utc).isoformat()
+        if self.error is None:
+            event_count = (
+                self.supabase.table("events")
+                .select("id", count="exact", head=True)
+                .eq("googleCalendarId", calendar_id)
+                .neq("status", "cancelled")
+                .execute()
+                .count
+            )
+            status = {
+                "last_success_at": now,
+                "last_error_code": None,
+                "last_error_message": None,
+                "consecutive_failures": 0,
+                "event_count": event_count or 0,
+            }
+        else:
+            state = first_row(
+                self.supabase.table("calendar_sync_state")
+                .select("consecutive_failures")
+                .eq("google_calendar_id", calendar_id)
+                .limit(1)
+                .execute()
+                .data
+            )
+            status = {
+                "last_error_at": now,
+                "last_error_code": str(self.error.get("code")),
+                "last_error_message": self.error.get("message"),
+                "consecutive_failures": int((state or {}).get("consecutive_failures") or 0) + 1,
+            }
+        self.supabase.table("calendar_sync_state").upsert(
+            {"google_calendar_id": calendar_id, **status}, on_conflict="google_calendar_id",
+        ).execute()
+
     def _clear_sync_state(self, calendar_id: str):
-        self.supabase.table("calendar_sync_state").update({"sync_token": None, "next_page_token": None}).eq("google_calendar_id", calendar_id).execute()
+        self.supabase.table("calendar_sync_state").update({
+            "sync_token": None,
+            "next_page_token": None,
+            "sync_window_start": None,
+            "sync_window_end": None,
+            "backfill_page_token": None,
+        }).eq("google_calendar_id", calendar_id).execute()
 
     def _get_sync_state(self) -> dict | None:
-        result = self.supabase.table("calendar_sync_state").select("sync_token, next_page_token, webhook_expires_at").eq("google_calendar_id", self.calendar["id"]).limit(1).execute()
+        result = self.supabase.table("calendar_sync_state").select("sync_token, next_page_token, webhook_expires_at, webhook_channel_id, webhook_resource_id").eq("google_calendar_id", self.calendar["id"]).limit(1).execute()
         return result.data[0] if result.data else None
 
+    def _save_page_token(self, calendar_id: str, page_token: str):
+        self.supabase.table("calendar_sync_state").upsert({
+            "google_calendar_id": calendar_id,
+            "next_page_token": page_token,
+        }, on_conflict="google_calendar_id").execute()
+
     def _save_sync_state(self, calendar_id: str, sync_token: str | None, page_token: str | None = None):
         self.supabase.table("calendar_sync_state").upsert({
             "google_calendar_id": calendar_id,
@@ -224,5 +412,119 @@ class Sync:
             )
 
     async def _emit(self, data: dict) -> None:
+        if data["type"] == "error":
+            self.error = data
         if self.events_queue:
             await self.events_queue.put(data)
+
+
+class CalendarListSync:
+    def __init__(self, supabase: Client, http: httpx.AsyncClient, user_id: str, google_account_id: str, priority: RequestPriority = RequestPriority.SYNC):
+        self.supabase = supabase
+        self.user_id = user_id
+        self.http = http
+        self.google_account_id = google_account_id
+        self.priority = priority
+        self.provider: CalendarProvider | None = None
+        self.changed = False
+        self.removed: list[str] = []
+
+    async def run(self, full: bool = False):
+        if self.provider is None:
+            self.provider = await asyncio.to_thread(
+                provider_for_account, self.supabase, self.http, self.user_id, self.google_account_id, self.priority,
+            )
+        state = await asyncio.to_thread(self._get_state)
+        sync_token = state.get("calendar_list_sync_token") if state and not full else None
+        try:
+            await self._sync(sync_token)
+        except ProviderError as e:
+            if e.status_code != 410 or not sync_token:
+                raise
+            await self._sync(None)
+        await self._refresh_webhook(state)
+        if self.changed:
+            publish_calendars_changed(self.user_id, self.removed)
+
+    async def _sync(self, sync_token: str | None):
+        upserts: list[dict] = []
+        deleted: set[str] = set()
+        next_sync_token = None
+        async for page in self.provider.fetch_calendar_list(sync_token):
+            for calendar in page["items"]:
+                if calendar.get("deleted"):
+                    deleted.add(calendar["id"])
+                else:
+                    upserts.append(calendar_list_row(self.google_account_id, calendar))
+            next_sync_token = page["next_sync_token"] or next_sync_token
+
+        if upserts:
+            await asyncio.to_thread(
+                lambda: self.supabase.table("google_calendars")
+                .upsert(upserts, on_conflict="google_account_id,google_calendar_id")
+                .execute()
+            )
+
+        stored = await asyncio.to_thread(self._get_stored_calendars)
+        if sync_token is None:
+            # A full listing is authoritative: anything we store that Google no longer returns is gone.
+            listed = {row["google_calendar_id"] for row in upserts}
+            removed = [str(row["id"]) for row in stored if listed and row["google_calendar_id"] not in listed]
+        else:
+            removed = [str(row["id"]) for row in stored if row["google_calendar_id"] in deleted]
+        await remove_calendars(self.provider, self.supabase, removed)
+
+        self.removed.extend(removed)
+        self.changed = self.changed or bool(upserts) or bool(removed)
+        await asyncio.to_thread(self._save_state, {"calendar_list_sync_token": next_sync_token})
+
+    async def _refresh_webhook(self, state: dict | None):
+        settings = get_settings()
+        if not settings.WEBHOOK_BASE_URL or not self.provider.supports_calendar_list_push:
+            return
+        if not channel_needs_renewal(state.get("webhook_expires_at") if state else None):
+            return
+
+        try:
+            channel_id = str(uuid.uuid4())
+            channel_token = secrets.token_urlsafe(32)
+            result = await self.provider.create_calendar_list_watch_channel(
+                f"{settings.WEBHOOK_BASE_URL}{self.provider.webhook_path}",
+                channel_id,
+                channel_token,
+            )
+            await asyncio.to_thread(self._save_state, {
+                "webhook_channel_id": channel_id,
+                "webhook_resource_id": result["resource_id"],
+                "webhook_expires_at": result["expires_at"].isoformat(),
+                "webhook_channel_token": channel_token,
+            })
+            if state:
+                await stop_watch_channel(self.provider, state.get("webhook_channel_id"), state.get("webhook_resource_id"))
+        except ProviderError as e:
+            logger.warning("Calendar list webhook registration failed for account %s: %s", self.google_account_id, e.message)
+
+    def _get_state(self) -> dict | None:
+        result = (
+            self.supabase.table(ACCOUNT_SYNC_STATE_TABLE)
+            .select("calendar_list_sync_token, webhook_expires_at, webhook_channel_id, webhook_resource_id")
+            .eq("google_account_id", self.google_account_id)
+            .limit(1)
+            .execute()
+        )
+        return result.data[0] if result.data else None
+
+    def _get_stored_calendars(self) -> list[dict]:
+        return all_rows(
+            self.supabase.table("google_calendars")
+            .select("id, google_calendar_id")
+            .eq("google_account_id", self.google_account_id)
+            .execute()
+            .data
+        )
+
+    def _save_state(self, values: dict):
+        self.supabase.table(ACCOUNT_SYNC_STATE_TABLE).upsert(
+            {"google_account_id": self.google_account_id, **values},
+            on_conflict="google_account_id",
+        ).execute()
@@ -0,0 +1,84 @@
+import asyncio
+import time
+import uuid
+
+from app.core.coordination import get_coordination
+
+CANCEL_POLL_SECONDS = 1.0
+CANCEL_WAIT_SECONDS = 10.0
+
+
+def _owner_key(session_id: str, user_id: str) -> str:
+    return f"sync-session:{session_id}:{user_id}"
+
+
+def _cancel_key(session_id: str) -> str:
+    return f"sync-session-cancel:{session_id}"
+
+
+class SyncSession:
+    def __init__(self, user_id: str, calendar_ids: list[str]):
+        self.id = str(uuid.uuid4())
+        self.user_id = user_id
+        self.calendar_ids = calendar_ids
+        self.tasks: list[asyncio.Task] = []
+        self.cancelled = False
+
+    def cancel(self) -> None:
+        self.cancelled = True
+        self.stop_tasks()
+
+    def stop_tasks(self) -> None:
+        for task in self.tasks:
+            if not task.done():
+                task.cancel()
+
+    async def wait_cancelled(self) -> None:
+        if self.tasks:
+            await asyncio.gather(*self.tasks, return_exceptions=True)
+
+    async def watch_cancel(self) -> None:
+        """Picks up cancellations requested on other workers."""
+        coordination = get_coordination()
+        while not self.cancelled:
+            if await coordination.exists(_cancel_key(self.id)):
+                self.cancel()
+                return
+            await asyncio.sleep(CANCEL_POLL_SECONDS)
+
+
+_sessions: dict[str, SyncSession] = {}
+
+
+async def start_session(user_id: str, calendar_ids: list[str], ttl_seconds: float) -> SyncSession:
+    session = SyncSession(user_id, calendar_ids)
+    _sessions[session.id] = session
+    await get_coordination().touch(_owner_key(session.id, user_id), ttl_seconds)
+    return session
+
+
+async def cancel_session(session_id: str, user_id: str) -> bool:
+    """Cancel a session owned by the user on any worker and wait for its syncs to stop. False if there is none."""
+    coordination = get_coordination()
+    owner_key = _owner_key(session_id, user_id)
+    if not await coordination.exists(owner_key):
+        return False
+
+    session = _sessions.get(session_id)
+    if session is not None:
+        session.cancel()
+        await session.wait_cancelled()
+        return True
+
+    await coordination.touch(_cancel_key(session_id), CANCEL_WAIT_SECONDS + CANCEL_POLL_SECONDS)
+    deadline = time.monotonic() + CANCEL_WAIT_SECONDS
+    while time.monotonic() < deadline and await coordination.exists(owner_key):
+        await asyncio.sleep(CANCEL_POLL_SECONDS)
+    return True
+
+
+async def end_session(session: SyncSession) -> None:
+    _sessions.pop(session.id, None)
+    coordination = get_coordination()
+    await coordination.release(_owner_key(session.id, session.user_id))
+    await coordination.release(_cancel_key(session.id))
@@ -5,6 +5,7 @@ from pydantic import field_validator, model_validator
 from pydantic_settings import BaseSettings, SettingsConfigDict
 
 SameSitePolicy = Literal["lax", "strict", "none"]
+CoordinationBackendName = Literal["memory", "postgres"]
 
 
 class Settings(BaseSettings):
@@ -44,6 +45,37 @@ class Settings(BaseSettings):
     RATE_LIMIT_AUTH: str
     RATE_LIMIT_API: str
 
+    SYNC_SCHEDULER_ENABLED: bool = True
+    SYNC_SCHEDULER_INTERVAL_SECONDS: int = 900
+    INITIAL_SYNC_DAYS_BACK: int = 365
+    INITIAL_SYNC_DAYS_FORWARD: int = 730
+
+    COORDINATION_BACKEND: CoordinationBackendName = "memory"
+
+    GOOGLE_CALENDAR_API_URL: str = "https://www.googleapis.com/calendar/v3"
+    GOOGLE_CALENDAR_BATCH_URL: str = "https://www.googleapis.com/batch/calendar/v3"
+    GOOGLE_PEOPLE_API_URL: str = "https://people.googleapis.com/v1"
+    GOOGLE_CLOUD_IDENTITY_API_URL: str = "https://cloudidentity.googleapis.com/v1"
+    GOOGLE_OAUTH_URL: str = "https://oauth2.googleapis.com"
+
+    MICROSOFT_CLIENT_ID: str = ""
+    MICROSOFT_CLIENT_SECRET: str = ""
+    MICROSOFT_REDIRECT_URL: str = ""
+    MICROSOFT_GRAPH_API_URL: str = "https://graph.microsoft.com/v1.0"
+    MICROSOFT_OAUTH_URL: str = "https://login.microsoftonline.com/common/oauth2/v2.0"
+
+    # Key for secrets stored in the database (CalDAV passwords). Falls back to CSRF_SECRET_KEY when unset;
+    # changing either one makes stored CalDAV credentials unreadable and those accounts must reconnect.
+    CREDENTIALS_ENCRYPTION_KEY: str = ""
+    # Lets CalDAV accounts point at plain-http, private or loopback hosts. Only for local development.
+    CALDAV_ALLOW_PRIVATE_HOSTS: bool = False
+
+    # Project-wide Google budget. Each worker enforces its share, so WEB_CONCURRENCY must match the
+    # number of uvicorn/gunicorn workers across all instances.
+    GOOGLE_QUOTA_REQUESTS_PER_SECOND: float = 10.0
+    GOOGLE_QUOTA_BURST: int = 20
+    WEB_CONCURRENCY: int = 1
+
     @field_validator("COOKIE_DOMAIN", mode="before")
     @classmethod
     def empty_str_to_none(cls, v: str | None) -> str | None:
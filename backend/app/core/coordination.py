@@ -0,0 +1,219 @@
+import asyncio
+import logging
+import time
+import uuid
+from abc import ABC, abstractmethod
+from collections import OrderedDict
+from contextlib import AbstractAsyncContextManager, asynccontextmanager
+from datetime import datetime, timedelta, timezone
+from functools import lru_cache
+
+from supabase import Client
+
+from app.config import get_settings
+from app.core.supabase import create_supabase_client
+
+logger = logging.getLogger(__name__)
+
+LEASES_TABLE = "coordination_leases"
+MAX_CACHED_PRIMITIVES = 100
+CACHE_CLEANUP_THRESHOLD = 150
+MAX_MEMORY_KEYS = 4096
+POLL_INTERVAL_SECONDS = 0.1
+MAX_POLL_INTERVAL_SECONDS = 1.0
+
+
+class CoordinationBackend(ABC):
+    @abstractmethod
+    async def claim(self, key: str, ttl_seconds: float) -> bool:
+        """Set the key unless it is already live. Returns True when this caller set it."""
+
+    @abstractmethod
+    async def touch(self, key: str, ttl_seconds: float) -> None:
+        ...
+
+    @abstractmethod
+    async def exists(self, key: str) -> bool:
+        ...
+
+    @abstractmethod
+    async def release(self, key: str) -> None:
+        ...
+
+    @abstractmethod
+    def lock(self, key: str, lease_seconds: float) -> AbstractAsyncContextManager[None]:
+        ...
+
+    @abstractmethod
+    def semaphore(self, key: str, limit: int, lease_seconds: float) -> AbstractAsyncContextManager[None]:
+        ...
+
+
+class MemoryCoordination(CoordinationBackend):
+    def __init__(self):
+        self._keys: dict[str, float] = {}
+        self._locks: OrderedDict[str, asyncio.Lock] = OrderedDict()
+        self._semaphores: OrderedDict[str, tuple[asyncio.Semaphore, int]] = OrderedDict()
+        self._dict_lock = asyncio.Lock()
+
+    def _live(self, key: str) -> bool:
+        expires_at = self._keys.get(key)
+        if expires_at is None:
+            return False
+        if expires_at <= time.monotonic():
+            del self._keys[key]
+            return False
+        return True
+
+    def _set(self, key: str, ttl_seconds: float) -> None:
+        if len(self._keys) >= MAX_MEMORY_KEYS:
+            now = time.monotonic()
+            for expired in [k for k, expires_at in self._keys.items() if expires_at <= now]:
+                del self._keys[expired]
+        self._keys[key] = time.monotonic() + ttl_seconds
+
+    async def claim(self, key: str, ttl_seconds: float) -> bool:
+        if self._live(key):
+            return False
+        self._set(key, ttl_seconds)
+        return True
+
+    async def touch(self, key: str, ttl_seconds: float) -> None:
+        self._set(key, ttl_seconds)
+
+    async def exists(self, key: str) -> bool:
+        return self._live(key)
+
+    async def release(self, key: str) -> None:
+        self._keys.pop(key, None)
+
+    async def _get_cached(self, cache: OrderedDict, key: str, factory, check_active):
+        async with self._dict_lock:
+            if key in cache:
+                cache.move_to_end(key)
+                return cache[key]
+            if len(cache) >= CACHE_CLEANUP_THRESHOLD:
+                to_remove = []
+                for cached_key, obj in cache.items():
+                    if len(cache) - len(to_remove) <= MAX_CACHED_PRIMITIVES:
+                        break
+                    if not check_active(obj):
+                        to_remove.append(cached_key)
+                for cached_key in to_remove:
+                    del cache[cached_key]
+            obj = factory()
+            cache[key] = obj
+            return obj
+
+    @asynccontextmanager
+    async def lock(self, key: str, lease_seconds: float):
+        lock = await self._get_cached(self._locks, key, asyncio.Lock, lambda lock: lock.locked())
+        async with lock:
+            yield
+
+    @asynccontextmanager
+    async def semaphore(self, key: str, limit: int, lease_seconds: float):
+        semaphore, _ = await self._get_cached(
+            self._semaphores, key,
+            lambda: (asyncio.Semaphore(limit), limit),
+            lambda entry: entry[0]._value < entry[1],
+        )
+        async with semaphore:
+            yield
+
+
+class PostgresCoordination(CoordinationBackend):
+    """Leases stored in the coordination_leases table (key primary key, owner, expires_at)."""
+
+    def __init__(self, supabase: Client):
+        self.supabase = supabase
+
+    def _try_acquire(self, key: str, owner: str, ttl_seconds: float) -> bool:
+        now = datetime.now(timezone.utc)
+        row = {"key": key, "owner": owner, "expires_at": (now + timedelta(seconds=ttl_seconds)).isoformat()}
+        inserted = (
+            self.supabase.table(LEASES_TABLE)
+            .upsert(row, on_conflict="key", ignore_duplicates=True)
+            .execute()
+        )
+        if inserted.data:
+            return True
+        taken_over = (
+            self.supabase.table(LEASES_TABLE)
+            .update(row)
+            .eq("key", key)
+            .lt("expires_at", now.isoformat())
+            .execute()
+        )
+        return bool(taken_over.data)
+
+    def _touch(self, key: str, ttl_seconds: float) -> None:
+        expires_at = datetime.now(timezone.utc) + timedelta(seconds=ttl_seconds)
+        self.supabase.table(LEASES_TABLE).upsert(
+            {"key": key, "owner": None, "expires_at": expires_at.isoformat()},
+            on_conflict="key",
+        ).execute()
+
+    def _exists(self, key: str) -> bool:
+        result = (
+            self.supabase.table(LEASES_TABLE)
+            .select("key")
+            .eq("key", key)
+            .gt("expires_at", datetime.now(timezone.utc).isoformat())
+            .limit(1)
+            .execute()
+        )
+        return bool(result.data)
+
+    def _release(self, key: str, owner: str | None = None) -> None:
+        query = self.supabase.table(LEASES_TABLE).delete().eq("key", key)
+        if owner is not None:
+            query = query.eq("owner", owner)
+        query.execute()
+
+    async def claim(self, key: str, ttl_seconds: float) -> bool:
+        return await asyncio.to_thread(self._try_acquire, key, uuid.uuid4().hex, ttl_seconds)
+
+    async def touch(self, key: str, ttl_seconds: float) -> None:
+        await asyncio.to_thread(self._touch, key, ttl_seconds)
+
+    async def exists(self, key: str) -> bool:
+        return await asyncio.to_thread(self._exists, key)
+
+    async def release(self, key: str) -> None:
+        await asyncio.to_thread(self._release, key)
+
+    async def _acquire_any(self, keys: list[str], owner: str, lease_seconds: float) -> str:
+        delay = POLL_INTERVAL_SECONDS
+        while True:
+            for key in keys:
+                if await asyncio.to_thread(self._try_acquire, key, owner, lease_seconds):
+                    return key
+            await asyncio.sleep(delay)
+            delay = min(delay * 2, MAX_POLL_INTERVAL_SECONDS)
+
+    @asynccontextmanager
+    async def _hold(self, keys: list[str], lease_seconds: float):
+        owner = uuid.uuid4().hex
+        key = await self._acquire_any(keys, owner, lease_seconds)
+        try:
+            yield
+        finally:
+            try:
+                await asyncio.shield(asyncio.to_thread(self._release, key, owner))
+            except Exception:
+                logger.exception("Failed to release coordination lease %s", key)
+
+    def lock(self, key: str, lease_seconds: float):
+        return self._hold([f"lock:{key}"], lease_seconds)
+
+    def semaphore(self, key: str, limit: int, lease_seconds: float):
+        return self._hold([f"sem:{key}:{slot}" for slot in range(limit)], lease_seconds)
+
+
+@lru_cache()
+def get_coordination() -> CoordinationBackend:
+    settings = get_settings()
+    if settings.COORDINATION_BACKEND == "postgres":
+        return PostgresCoordination(create_supabase_client())
+    return MemoryCoordination()
@@ -0,0 +1,30 @@
+import base64
+import hashlib
+from functools import lru_cache
+
+from cryptography.fernet import Fernet, InvalidToken
+
+from app.config import get_settings
+
+ENCRYPTED_PREFIX = "enc:v1:"
+
+
+@lru_cache
+def _fernet() -> Fernet:
+    settings = get_settings()
+    secret = settings.CREDENTIALS_ENCRYPTION_KEY or settings.CSRF_SECRET_KEY
+    return Fernet(base64.urlsafe_b64encode(hashlib.sha256(secret.encode()).digest()))
+
+
+def encrypt_secret(value: str) -> str:
+    return ENCRYPTED_PREFIX + _fernet().encrypt(value.encode()).decode()
+
+
+def decrypt_secret(value: str) -> str:
+    """Decrypt a stored secret. Values written before encryption was introduced are returned as-is."""
+    if not value.startswith(ENCRYPTED_PREFIX):
+        return value
+    try:
+        return _fernet().decrypt(value[len(ENCRYPTED_PREFIX):].encode()).decode()
+    except InvalidToken:
+        raise ValueError("Stored secret could not be decrypted; was CREDENTIALS_ENCRYPTION_KEY changed?")
@@ -2,7 +2,7 @@ from fastapi import HTTPException
 
 import logging
 
-from app.calendar.helpers import GoogleAPIError
+from app.calendar.helpers import ProviderError
 
 logger = logging.getLogger(__name__)
 
@@ -11,6 +11,7 @@ SAFE_ERROR_MESSAGES = {
     401: "Authentication required",
     403: "Access denied",
     404: "Resource not found",
+    409: "Event was changed on another device",
     429: "Too many requests, please try again later",
     500: "An internal error occurred",
     502: "Service temporarily unavailable",
@@ -23,13 +24,25 @@ def get_safe_message(status_code: int, fallback: str = "An error occurred") -> s
     return SAFE_ERROR_MESSAGES.get(status_code, fallback)
 
 
+class EventConflictError(HTTPException):
+    def __init__(self, current: dict | None):
+        super().__init__(
+            status_code=409,
+            detail={"message": SAFE_ERROR_MESSAGES[409], "current": current},
+        )
+
+
 _GOOGLE_API_ERROR_MAP: dict[int, tuple[int, str]] = {
     401: (401, "Google account needs reconnection"),
     429: (429, SAFE_ERROR_MESSAGES[429]),
 }
 
 
-def handle_google_api_error(e: GoogleAPIError):
+def handle_google_api_error(e: ProviderError):
+    if e.status_code == 412:
+        raise EventConflictError(None)
+    if e.status_code == 501:
+        raise HTTPException(status_code=501, detail=e.message)
     if e.status_code >= 500:
         raise HTTPException(status_code=502, detail=get_safe_message(502))
 
@@ -0,0 +1,170 @@
+import asyncio
+import logging
+import uuid
+from collections import OrderedDict, defaultdict
+from datetime import datetime, timedelta, timezone
+
+from supabase import Client
+
+from app.config import get_settings
+from app.core.supabase import create_supabase_client
+
+logger = logging.getLogger(__name__)
+
+LIVE_QUEUE_SIZE = 256
+MAX_PUSHED_EVENTS = 500
+MESSAGES_TABLE = "live_update_messages"
+RELAY_POLL_SECONDS = 1.0
+RELAY_OVERLAP = timedelta(seconds=10)
+RELAY_RETENTION = timedelta(minutes=5)
+RELAY_BATCH_SIZE = 1000
+RELAY_PRUNE_EVERY_POLLS = 60
+MAX_SEEN_MESSAGES = 10000
+
+_subscribers: dict[str, set[asyncio.Queue]] = defaultdict(set)
+
+
+def subscribe(user_id: str) -> asyncio.Queue:
+    queue: asyncio.Queue = asyncio.Queue(maxsize=LIVE_QUEUE_SIZE)
+    _subscribers[user_id].add(queue)
+    return queue
+
+
+def unsubscribe(user_id: str, queue: asyncio.Queue) -> None:
+    queues = _subscribers.get(user_id)
+    if queues is None:
+        return
+    queues.discard(queue)
+    if not queues:
+        _subscribers.pop(user_id, None)
+
+
+def _deliver(user_id: str, event: str, data: dict) -> None:
+    for queue in list(_subscribers.get(user_id, ())):
+        try:
+            queue.put_nowait((event, data))
+        except asyncio.QueueFull:
+            logger.warning("Live update queue full for user %s, asking client to resync", user_id)
+            while not queue.empty():
+                queue.get_nowait()
+            queue.put_nowait(("resync", {}))
+
+
+class PostgresRelay:
+    """Carries updates to subscribers on other workers through the live_update_messages table
+    (id identity primary key, origin, user_id, event, data jsonb, created_at default now())."""
+
+    def __init__(self, supabase: Client):
+        self.supabase = supabase
+        self.origin = uuid.uuid4().hex
+        self._seen: OrderedDict[int, None] = OrderedDict()
+        self._since = datetime.now(timezone.utc)
+        self._sending: set[asyncio.Task] = set()
+
+    def _insert(self, user_id: str, event: str, data: dict) -> None:
+        self.supabase.table(MESSAGES_TABLE).insert({
+            "origin": self.origin, "user_id": user_id, "event": event, "data": data,
+        }).execute()
+
+    def send(self, user_id: str, event: str, data: dict) -> None:
+        try:
+            loop = asyncio.get_running_loop()
+        except RuntimeError:
+            self._insert(user_id, event, data)
+            return
+        task = loop.create_task(asyncio.to_thread(self._insert, user_id, event, data))
+        self._sending.add(task)
+        task.add_done_callback(self._sent)
+
+    def _sent(self, task: asyncio.Task) -> None:
+        self._sending.discard(task)
+        if not task.cancelled() and task.exception() is not None:
+            logger.warning("Failed to relay live update: %s", task.exception())
+
+    def _fetch(self) -> list[dict]:
+        return (
+            self.supabase.table(MESSAGES_TABLE)
+            .select("id, origin, user_id, event, data, created_at")
+            .gte("created_at", (self._since - RELAY_OVERLAP).isoformat())
+            .order("id")
+            .limit(RELAY_BATCH_SIZE)
+            .execute()
+            .data
+        ) or []
+
+    def _prune(self) -> None:
+        cutoff = datetime.now(timezone.utc) - RELAY_RETENTION
+        self.supabase.table(MESSAGES_TABLE).delete().lt("created_at", cutoff.isoformat()).execute()
+
+    def poll(self) -> list[dict]:
+        """New messages from other workers. Rows inside the overlap window are refetched so late commits are not lost."""
+        fresh = []
+        for message in self._fetch():
+            if message["id"] in self._seen:
+                continue
+            self._seen[message["id"]] = None
+            if len(self._seen) > MAX_SEEN_MESSAGES:
+                self._seen.popitem(last=False)
+            created_at = datetime.fromisoformat(message["created_at"].replace("Z", "+00:00"))
+            self._since = max(self._since, created_at)
+            if message["origin"] != self.origin:
+                fresh.append(message)
+        return fresh
+
+    async def run(self) -> None:
+        polls = 0
+        while True:
+            try:
+                for message in await asyncio.to_thread(self.poll):
+                    _deliver(message["user_id"], message["event"], message["data"])
+                polls += 1
+                if polls % RELAY_PRUNE_EVERY_POLLS == 0:
+                    await asyncio.to_thread(self._prune)
+            except Exception:
+                logger.exception("Live update relay poll failed")
+            await asyncio.sleep(RELAY_POLL_SECONDS)
+
+
+_relay: PostgresRelay | None = None
+_relay_task: asyncio.Task | None = None
+
+
+def start_relay() -> None:
+    """Fan updates out across workers when they share the Postgres coordination backend."""
+    global _relay, _relay_task
+    if get_settings().COORDINATION_BACKEND != "postgres" or _relay_task is not None:
+        return
+    _relay = PostgresRelay(create_supabase_client())
+    _relay_task = asyncio.create_task(_relay.run())
+
+
+async def stop_relay() -> None:
+    global _relay, _relay_task
+    if _relay_task is None:
+        return
+    _relay_task.cancel()
+    try:
+        await _relay_task
+    except asyncio.CancelledError:
+        pass
+    _relay, _relay_task = None, None
+
+
+def publish(user_id: str, event: str, data: dict) -> None:
+    _deliver(user_id, event, data)
+    if _relay is not None:
+        _relay.send(user_id, event, data)
+
+
+def publish_calendar_changed(user_id: str, calendar_id: str, events: list[dict] | None) -> None:
+    if events is not None and len(events) > MAX_PUSHED_EVENTS:
+        events = None
+    publish(user_id, "calendar_changed", {"calendar_id": calendar_id, "events": events})
+
+
+def publish_todos_changed(user_id: str, resource: str) -> None:
+    publish(user_id, "todos_changed", {"resource": resource})
+
+
+def publish_calendars_changed(user_id: str, removed: list[str]) -> None:
+    publish(user_id, "calendars_changed", {"removed": removed})
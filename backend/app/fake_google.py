@@ -0,0 +1,409 @@
+"""Fake Google APIs for tests and local development.
+
+Run with `uvicorn app.fake_google:app --port 8090` and set GOOGLE_CALENDAR_API_URL=http://localhost:8090/calendar/v3,
+GOOGLE_PEOPLE_API_URL=http://localhost:8090/people/v1 and GOOGLE_OAUTH_URL=http://localhost:8090/oauth.
+"""
+import base64
+import json
+import uuid
+from dataclasses import dataclass
+from datetime import datetime, timedelta, timezone
+from urllib.parse import parse_qs
+
+import httpx
+from fastapi import FastAPI, Request
+from fastapi.responses import JSONResponse, Response
+
+DEFAULT_PAGE_SIZE = 250
+CHANNEL_TTL = timedelta(days=7)
+ACCESS_TOKEN_TTL_SECONDS = 3600
+
+FAULT_REASONS = {
+    401: "authError",
+    403: "rateLimitExceeded",
+    410: "fullSyncRequired",
+    429: "rateLimitExceeded",
+}
+
+
+@dataclass
+class Fault:
+    status: int
+    remaining: int = 1
+    method: str | None = None
+    path: str | None = None
+    reason: str | None = None
+    retry_after: int | None = None
+
+    def matches(self, request: Request) -> bool:
+        if self.method and request.method != self.method.upper():
+            return False
+        return not self.path or self.path in request.url.path
+
+    def response(self) -> JSONResponse:
+        reason = self.reason or FAULT_REASONS.get(self.status, "backendError")
+        headers = {"Retry-After": str(self.retry_after)} if self.retry_after is not None else None
+        return _error(self.status, f"Injected {self.status}", reason, headers)
+
+
+def _error(status: int, message: str, reason: str | None = None, headers: dict | None = None) -> JSONResponse:
+    body = {"error": {"code": status, "message": message, "errors": [{"reason": reason, "message": message}] if reason else []}}
+    return JSONResponse(body, status_code=status, headers=headers)
+
+
+def _now() -> datetime:
+    return datetime.now(timezone.utc)
+
+
+def _encode(data: dict) -> str:
+    return base64.urlsafe_b64encode(json.dumps(data).encode()).decode()
+
+
+def _decode(token: str) -> dict | None:
+    try:
+        return json.loads(base64.urlsafe_b64decode(token.encode()))
+    except ValueError:
+        return None
+
+
+def _event_bounds(event: dict) -> tuple[datetime | None, datetime | None]:
+    def parse(value: dict | None) -> datetime | None:
+        if not value:
+            return None
+        raw = value.get("dateTime") or value.get("date")
+        if not raw:
+            return None
+        parsed = datetime.fromisoformat(raw.replace("Z", "+00:00"))
+        return parsed if parsed.tzinfo else parsed.replace(tzinfo=timezone.utc)
+
+    return parse(event.get("start")), parse(event.get("end"))
+
+
+class FakeGoogle:
+    """In-process stand-in for the Calendar, People and OAuth APIs used by GoogleAPIClient."""
+
+    def __init__(self, page_size: int = DEFAULT_PAGE_SIZE, permissive: bool = False):
+        self.page_size = page_size
+        self.permissive = permissive
+        self.calendars: dict[str, dict] = {}
+        self.events: dict[str, dict[str, dict]] = {}
+        self.contacts: list[dict] = []
+        self.other_contacts: list[dict] = []
+        self.directory: list[dict] = []
+        self.channels: dict[str, dict] = {}
+        self.notifications: list[dict] = []
+        self.access_tokens: set[str] = set()
+        self.refresh_tokens: set[str] = set()
+        self.faults: list[Fault] = []
+        self.requests: list[tuple[str, str]] = []
+        self._seq = 0
+        self._min_sync_seq = 0
+        self._calendar_seq: dict[str, int] = {}
+        self._event_seq: dict[tuple[str, str], int] = {}
+        self.app = self._build_app()
+
+    def http_client(self) -> httpx.AsyncClient:
+        return httpx.AsyncClient(transport=httpx.ASGITransport(app=self.app))
+
+    def _next_seq(self) -> int:
+        self._seq += 1
+        return self._seq
+
+    def issue_tokens(self) -> tuple[str, str]:
+        access_token, refresh_token = f"access-{uuid.uuid4().hex}", f"refresh-{uuid.uuid4().hex}"
+        self.access_tokens.add(access_token)
+        self.refresh_tokens.add(refresh_token)
+        return access_token, refresh_token
+
+    def expire_access_tokens(self) -> None:
+        self.access_tokens.clear()
+
+    def expire_sync_tokens(self) -> None:
+        self._min_sync_seq = self._seq + 1
+
+    def inject(self, status: int, times: int = 1, method: str | None = None, path: str | None = None, reason: str | None = None, retry_after: int | None = None) -> None:
+        self.faults.append(Fault(status, times, method, path, reason, retry_after))
+
+    def add_calendar(self, calendar_id: str, summary: str | None = None, access_role: str = "owner", primary: bool = False) -> dict:
+        entry = {
+            "kind": "calendar#calendarListEntry",
+            "id": calendar_id,
+            "summary": summary or calendar_id,
+            "accessRole": access_role,
+            "backgroundColor": "#9fc6e7",
+            "primary": primary,
+        }
+        self.calendars[calendar_id] = entry
+        self.events.setdefault(calendar_id, {})
+        self._calendar_seq[calendar_id] = self._next_seq()
+        self._notify(None)
+        return entry
+
+    def remove_calendar(self, calendar_id: str) -> None:
+        self.calendars[calendar_id] = {"kind": "calendar#calendarListEntry", "id": calendar_id, "deleted": True}
+        self._calendar_seq[calendar_id] = self._next_seq()
+        self._notify(None)
+
+    def add_event(self, calendar_id: str, event: dict) -> dict:
+        event_id = event.get("id") or uuid.uuid4().hex
+        timestamp = _now().isoformat()
+        stored = {
+            "kind": "calendar#event",
+            "status": "confirmed",
+            "created": timestamp,
+            **event,
+            "id": event_id,
+            "updated": timestamp,
+            "etag": f'"{uuid.uuid4().hex}"',
+            "iCalUID": event.get("iCalUID") or f"{event_id}@fake.google.com",
+        }
+        return self._store_event(calendar_id, stored)
+
+    def update_event(self, calendar_id: str, event_id: str, **changes) -> dict:
+        stored = {
+            **self.events[calendar_id][event_id],
+            **changes,
+            "updated": _now().isoformat(),
+            "etag": f'"{uuid.uuid4().hex}"',
+        }
+        return self._store_event(calendar_id, stored)
+
+    def delete_event(self, calendar_id: str, event_id: str) -> dict:
+        return self.update_event(calendar_id, event_id, status="cancelled")
+
+    def _store_event(self, calendar_id: str, event: dict) -> dict:
+        self.events.setdefault(calendar_id, {})[event["id"]] = event
+        self._event_seq[(calendar_id, event["id"])] = self._next_seq()
+        self._notify(calendar_id)
+        return event
+
+    def _notify(self, calendar_id: str) -> None:
+        for channel in self.channels.values():
+            if channel["calendar_id"] == calendar_id:
+                self.notifications.append({
+                    "X-Goog-Channel-Id": channel["id"],
+                    "X-Goog-Channel-Token": channel.get("token") or "",
+                    "X-Goog-Resource-Id": channel["resourceId"],
+                    "X-Goog-Resource-State": "exists",
+                })
+
+    def _page(self, entries: list[tuple[int, dict]], params: dict, show_deleted: bool, is_deleted) -> dict | JSONResponse:
+        page_token = params.get("pageToken")
+        sync_token = params.get("syncToken")
+        if page_token:
+            state = _decode(page_token)
+            if state is None:
+                return _error(400, "Invalid page token", "invalid")
+        else:
+            since = None
+            if sync_token:
+                decoded = _decode(sync_token)
+                if decoded is None or decoded.get("seq", -1) < self._min_sync_seq:
+                    return _error(410, "Sync token is no longer valid, a full sync is required.", "fullSyncRequired")
+                since = decoded["seq"]
+            state = {"offset": 0, "since": since, "snapshot": self._seq}
+
+        since, snapshot = state["since"], state["snapshot"]
+        visible = [
+            item for seq, item in sorted(entries, key=lambda entry: entry[1]["id"])
+            if seq <= snapshot
+            and (since is None or seq > since)
+            and (since is not None or show_deleted or not is_deleted(item))
+        ]
+        limit = min(int(params.get("maxResults") or self.page_size), self.page_size)
+        offset = state["offset"]
+        body: dict = {"items": visible[offset:offset + limit]}
+        if offset + limit < len(visible):
+            body["nextPageToken"] = _encode({**state, "offset": offset + limit})
+        else:
+            body["nextSyncToken"] = _encode({"seq": snapshot})
+        return body
+
+    def list_events(self, calendar_id: str, params: dict) -> dict | JSONResponse:
+        if calendar_id not in self.events:
+            return _error(404, "Not Found", "notFound")
+        time_min = params.get("timeMin")
+        time_max = params.get("timeMax")
+        windowed = not params.get("syncToken") and (time_min or time_max)
+        lower = datetime.fromisoformat(time_min.replace("Z", "+00:00")) if windowed and time_min else None
+        upper = datetime.fromisoformat(time_max.replace("Z", "+00:00")) if windowed and time_max else None
+
+        entries = []
+        for event_id, event in self.events[calendar_id].items():
+            start, end = _event_bounds(event)
+            if upper and start and start >= upper:
+                continue
+            if lower and not event.get("recurrence") and (end or start) and (end or start) <= lower:
+                continue
+            entries.append((self._event_seq[(calendar_id, event_id)], event))
+        return self._page(
+            entries, params,
+            show_deleted=params.get("showDeleted") == "true",
+            is_deleted=lambda item: item.get("status") == "cancelled",
+        )
+
+    def list_calendars(self, params: dict) -> dict | JSONResponse:
+        entries = [(self._calendar_seq[calendar_id], entry) for calendar_id, entry in self.calendars.items()]
+        return self._page(
+            entries, params,
+            show_deleted=params.get("showDeleted") == "true",
+            is_deleted=lambda item: bool(item.get("deleted")),
+        )
+
+    def watch(self, calendar_id: str | None, body: dict) -> dict:
+        channel = {
+            "kind": "api#channel",
+            "id": body["id"],
+            "resourceId": uuid.uuid4().hex,
+            "resourceUri": f"/calendars/{calendar_id}/events" if calendar_id else "/users/me/calendarList",
+            "token": body.get("token"),
+            "expiration": str(int((_now() + CHANNEL_TTL).timestamp() * 1000)),
+            "address": body.get("address"),
+            "calendar_id": calendar_id,
+        }
+        self.channels[channel["id"]] = channel
+        return {key: value for key, value in channel.items() if key not in ("address", "calendar_id")}
+
+    def _build_app(self) -> FastAPI:
+        app = FastAPI(title="Fake Google APIs")
+        google = self
+
+        @app.middleware("http")
+        async def faults_and_auth(request: Request, call_next):
+            google.requests.append((request.method, request.url.path))
+            for fault in list(google.faults):
+                if fault.matches(request):
+                    fault.remaining -= 1
+                    if fault.remaining <= 0:
+                        google.faults.remove(fault)
+                    return fault.response()
+            if not request.url.path.startswith("/oauth"):
+                token = request.headers.get("Authorization", "").removeprefix("Bearer ")
+                if not (token and google.permissive) and token not in google.access_tokens:
+                    return _error(401, "Invalid Credentials", "authError")
+            return await call_next(request)
+
+        @app.get("/calendar/v3/users/me/calendarList")
+        async def calendar_list(request: Request):
+            return google.list_calendars(dict(request.query_params))
+
+        @app.post("/calendar/v3/users/me/calendarList/watch")
+        async def calendar_list_watch(request: Request):
+            return google.watch(None, await request.json())
+
+        @app.post("/calendar/v3/channels/stop")
+        async def channels_stop(request: Request):
+            body = await request.json()
+            channel = google.channels.get(body.get("id"))
+            if not channel or channel["resourceId"] != body.get("resourceId"):
+                return _error(404, "Channel not found", "notFound")
+            del google.channels[channel["id"]]
+            return Response(status_code=204)
+
+        @app.get("/calendar/v3/calendars/{calendar_id}/events")
+        async def list_events(calendar_id: str, request: Request):
+            return google.list_events(calendar_id, dict(request.query_params))
+
+        @app.post("/calendar/v3/calendars/{calendar_id}/events")
+        async def insert_event(calendar_id: str, request: Request):
+            if calendar_id not in google.events:
+                return _error(404, "Not Found", "notFound")
+            body = await request.json()
+            body.pop("id", None)
+            return google.add_event(calendar_id, body)
+
+        @app.post("/calendar/v3/calendars/{calendar_id}/events/watch")
+        async def events_watch(calendar_id: str, request: Request):
+            if calendar_id not in google.events:
+                return _error(404, "Not Found", "notFound")
+            return google.watch(calendar_id, await request.json())
+
+        def find_event(calendar_id: str, event_id: str) -> dict | JSONResponse:
+            event = google.events.get(calendar_id, {}).get(event_id)
+            if event is None:
+                return _error(404, "Not Found", "notFound")
+            if event.get("status") == "cancelled":
+                return _error(410, "Resource has been deleted", "deleted")
+            return event
+
+        def precondition_failed(request: Request, event: dict) -> JSONResponse | None:
+            etag = request.headers.get("If-Match")
+            if etag and etag != event["etag"]:
+                return _error(412, "Precondition Failed", "conditionNotMet")
+            return None
+
+        @app.get("/calendar/v3/calendars/{calendar_id}/events/{event_id}")
+        async def get_event(calendar_id: str, event_id: str):
+            return find_event(calendar_id, event_id)
+
+        @app.patch("/calendar/v3/calendars/{calendar_id}/events/{event_id}")
+        async def patch_event(calendar_id: str, event_id: str, request: Request):
+            event = find_event(calendar_id, event_id)
+            if isinstance(event, JSONResponse):
+                return event
+            if failed := precondition_failed(request, event):
+                return failed
+            changes = {key: value for key, value in (await request.json()).items() if key not in ("id", "etag")}
+            return google.update_event(calendar_id, event_id, **changes)
+
+        @app.delete("/calendar/v3/calendars/{calendar_id}/events/{event_id}")
+        async def delete_event(calendar_id: str, event_id: str, request: Request):
+            event = find_event(calendar_id, event_id)
+            if isinstance(event, JSONResponse):
+                return event
+            if failed := precondition_failed(request, event):
+                return failed
+            google.delete_event(calendar_id, event_id)
+            return Response(status_code=204)
+
+        @app.post("/calendar/v3/calendars/{calendar_id}/events/{event_id}/move")
+        async def move_event(calendar_id: str, event_id: str, destination: str):
+            event = find_event(calendar_id, event_id)
+            if isinstance(event, JSONResponse):
+                return event
+            if destination not in google.events:
+                return _error(404, "Not Found", "notFound")
+            google.delete_event(calendar_id, event_id)
+            return google.add_event(destination, {**event, "status": "confirmed"})
+
+        @app.get("/people/v1/people/me/connections")
+        async def connections():
+            return {"connections": google.contacts, "totalItems": len(google.contacts)}
+
+        @app.get("/people/v1/otherContacts")
+        async def other_contacts():
+            return {"otherContacts": google.other_contacts}
+
+        @app.get("/people/v1/people:searchDirectoryPeople")
+        async def search_directory(query: str = ""):
+            needle = query.lower()
+            return {"people": [
+                person for person in google.directory
+                if any(needle in email.get("value", "").lower() for email in person.get("emailAddresses", []))
+            ]}
+
+        @app.post("/oauth/token")
+        async def token(request: Request):
+            form = {key: values[0] for key, values in parse_qs((await request.body()).decode()).items()}
+            refresh_token = form.get("refresh_token")
+            if form.get("grant_type") != "refresh_token" or not (
+                refresh_token in google.refresh_tokens or (refresh_token and google.permissive)
+            ):
+                return JSONResponse({"error": "invalid_grant", "error_description": "Token has been expired or revoked."}, status_code=400)
+            access_token = f"access-{uuid.uuid4().hex}"
+            google.access_tokens.add(access_token)
+            return {"access_token": access_token, "expires_in": ACCESS_TOKEN_TTL_SECONDS, "token_type": "Bearer"}
+
+        @app.post("/oauth/revoke")
+        async def revoke(request: Request):
+            form = {key: values[0] for key, values in parse_qs((await request.body()).decode()).items()}
+            google.access_tokens.discard(form.get("token", ""))
+            google.refresh_tokens.discard(form.get("token", ""))
+            return {}
+
+        return app
+
+
+fake_google = FakeGoogle(permissive=True)
+fake_google.add_calendar("primary@example.com", "Fake calendar", primary=True)
+app = fake_google.app
@@ -0,0 +1,300 @@
+"""Fake Microsoft Graph calendar API for tests and local development.
+
+Run with `uvicorn app.fake_graph:app --port 8091` and set MICROSOFT_GRAPH_API_URL=http://localhost:8091/v1.0
+and MICROSOFT_OAUTH_URL=http://localhost:8091/oauth.
+"""
+import uuid
+from datetime import datetime, timedelta, timezone
+from urllib.parse import parse_qs
+
+import httpx
+from fastapi import FastAPI, Request
+from fastapi.responses import JSONResponse, Response
+
+from app.fake_google import Fault, _decode, _encode
+
+DEFAULT_PAGE_SIZE = 100
+SUBSCRIPTION_TTL = timedelta(days=3)
+ACCESS_TOKEN_TTL_SECONDS = 3600
+
+
+def _error(status: int, code: str, message: str) -> JSONResponse:
+    return JSONResponse({"error": {"code": code, "message": message}}, status_code=status)
+
+
+def _now() -> datetime:
+    return datetime.now(timezone.utc)
+
+
+class FakeGraph:
+    """In-process stand-in for the Graph calendar, subscription and OAuth endpoints used by MicrosoftProvider."""
+
+    def __init__(self, page_size: int = DEFAULT_PAGE_SIZE, permissive: bool = False):
+        self.page_size = page_size
+        self.permissive = permissive
+        self.me = {"id": uuid.uuid4().hex, "displayName": "Fake User", "mail": "user@example.com", "userPrincipalName": "user@example.com"}
+        self.calendars: dict[str, dict] = {}
+        self.events: dict[str, dict] = {}
+        self.removed: dict[str, str] = {}
+        self.subscriptions: dict[str, dict] = {}
+        self.notifications: list[dict] = []
+        self.access_tokens: set[str] = set()
+        self.refresh_tokens: set[str] = set()
+        self.codes: set[str] = set()
+        self.faults: list[Fault] = []
+        self.requests: list[tuple[str, str]] = []
+        self._seq = 0
+        self._min_delta_seq = 0
+        self._event_seq: dict[str, int] = {}
+        self.app = self._build_app()
+
+    def http_client(self) -> httpx.AsyncClient:
+        return httpx.AsyncClient(transport=httpx.ASGITransport(app=self.app))
+
+    def issue_tokens(self) -> tuple[str, str]:
+        access_token, refresh_token = f"access-{uuid.uuid4().hex}", f"refresh-{uuid.uuid4().hex}"
+        self.access_tokens.add(access_token)
+        self.refresh_tokens.add(refresh_token)
+        return access_token, refresh_token
+
+    def issue_code(self) -> str:
+        code = f"code-{uuid.uuid4().hex}"
+        self.codes.add(code)
+        return code
+
+    def expire_access_tokens(self) -> None:
+        self.access_tokens.clear()
+
+    def expire_delta_tokens(self) -> None:
+        self._min_delta_seq = self._seq + 1
+
+    def inject(self, status: int, times: int = 1, method: str | None = None, path: str | None = None, retry_after: int | None = None) -> None:
+        self.faults.append(Fault(status, times, method, path, retry_after=retry_after))
+
+    def add_calendar(self, calendar_id: str, name: str | None = None, default: bool = False, can_edit: bool = True) -> dict:
+        calendar = {"id": calendar_id, "name": name or calendar_id, "hexColor": "#3a87ad", "isDefaultCalendar": default, "canEdit": can_edit}
+        self.calendars[calendar_id] = calendar
+        return calendar
+
+    def add_event(self, calendar_id: str, event: dict) -> dict:
+        event_id = event.get("id") or f"AAMk{uuid.uuid4().hex}="
+        timestamp = _now().isoformat()
+        stored = {
+            "type": "singleInstance",
+            "isAllDay": False,
+            "createdDateTime": timestamp,
+            **event,
+            "id": event_id,
+            "calendarId": calendar_id,
+            "iCalUId": event.get("iCalUId") or f"{event_id}@fake.graph",
+        }
+        return self._store(stored)
+
+    def update_event(self, event_id: str, **changes) -> dict:
+        return self._store({**self.events[event_id], **changes})
+
+    def delete_event(self, event_id: str) -> None:
+        event = self.events.pop(event_id)
+        self.removed[event_id] = event["calendarId"]
+        self._touch(event_id, event["calendarId"])
+
+    def cancel_occurrence(self, event_id: str, day: str) -> None:
+        """Delete one occurrence: the master records it in cancelledOccurrences and delta reports only the occurrence id."""
+        event = self.events[event_id]
+        event["cancelledOccurrences"] = [*event.get("cancelledOccurrences", []), f"OID.{event_id}.{day}"]
+        event["@odata.etag"] = f'W/"{uuid.uuid4().hex}"'
+        occurrence_id = f"{event_id}-{day}-occurrence"
+        self.removed[occurrence_id] = event["calendarId"]
+        self._touch(occurrence_id, event["calendarId"])
+
+    def _store(self, event: dict) -> dict:
+        event["lastModifiedDateTime"] = _now().isoformat()
+        event["@odata.etag"] = f'W/"{uuid.uuid4().hex}"'
+        self.events[event["id"]] = event
+        self.removed.pop(event["id"], None)
+        self._touch(event["id"], event["calendarId"])
+        return event
+
+    def _touch(self, event_id: str, calendar_id: str) -> None:
+        self._seq += 1
+        self._event_seq[event_id] = self._seq
+        for subscription in self.subscriptions.values():
+            if subscription["resource"] == f"me/calendars/{calendar_id}/events":
+                self.notifications.append({
+                    "subscriptionId": subscription["id"],
+                    "clientState": subscription.get("clientState"),
+                    "changeType": "updated",
+                    "resource": f"me/events/{event_id}",
+                })
+
+    def _public(self, event: dict) -> dict:
+        return {key: value for key, value in event.items() if key != "calendarId"}
+
+    def _delta_items(self, event_id: str) -> list[dict]:
+        if event_id in self.removed:
+            return [{"id": event_id, "@removed": {"reason": "deleted"}}]
+        event = self.events[event_id]
+        if event["type"] == "seriesMaster":
+            # calendarView only returns instances, so a series surfaces as an occurrence pointing at its master.
+            return [{"id": f"{event_id}-occurrence", "type": "occurrence", "seriesMasterId": event_id, "start": event["start"], "end": event["end"]}]
+        return [self._public(event)]
+
+    def delta(self, request: Request, calendar_id: str) -> dict | JSONResponse:
+        if calendar_id not in self.calendars:
+            return _error(404, "ErrorItemNotFound", "The specified object was not found in the store.")
+        params = request.query_params
+        if params.get("$skiptoken"):
+            state = _decode(params["$skiptoken"])
+        elif params.get("$deltatoken"):
+            state = _decode(params["$deltatoken"])
+            if state is None or state.get("seq", -1) < self._min_delta_seq:
+                return _error(410, "SyncStateNotFound", "The sync state is no longer valid.")
+            state = {"offset": 0, "since": state["seq"], "snapshot": self._seq}
+        else:
+            state = {"offset": 0, "since": None, "snapshot": self._seq}
+        if state is None:
+            return _error(400, "BadRequest", "Invalid token")
+
+        since, snapshot = state["since"], state["snapshot"]
+        changed = [
+            event_id for event_id, seq in sorted(self._event_seq.items())
+            if seq <= snapshot and (since is None or seq > since)
+            and (event_id in self.events and self.events[event_id]["calendarId"] == calendar_id or self.removed.get(event_id) == calendar_id)
+            and (since is not None or event_id not in self.removed)
+        ]
+        offset = state["offset"]
+        items = [item for event_id in changed[offset:offset + self.page_size] for item in self._delta_items(event_id)]
+        base = f"{str(request.base_url).rstrip('/')}/v1.0/me/calendars/{calendar_id}/calendarView/delta"
+        body: dict = {"value": items}
+        if offset + self.page_size < len(changed):
+            body["@odata.nextLink"] = f"{base}?$skiptoken={_encode({**state, 'offset': offset + self.page_size})}"
+        else:
+            body["@odata.deltaLink"] = f"{base}?$deltatoken={_encode({'seq': snapshot})}"
+        return body
+
+    def _build_app(self) -> FastAPI:
+        app = FastAPI(title="Fake Microsoft Graph")
+        graph = self
+
+        @app.middleware("http")
+        async def faults_and_auth(request: Request, call_next):
+            graph.requests.append((request.method, request.url.path))
+            for fault in list(graph.faults):
+                if fault.matches(request):
+                    fault.remaining -= 1
+                    if fault.remaining <= 0:
+                        graph.faults.remove(fault)
+                    return fault.response()
+            if not request.url.path.startswith("/oauth"):
+                token = request.headers.get("Authorization", "").removeprefix("Bearer ")
+                if not (token and graph.permissive) and token not in graph.access_tokens:
+                    return _error(401, "InvalidAuthenticationToken", "Access token has expired or is not yet valid.")
+            return await call_next(request)
+
+        def find_event(event_id: str) -> dict | JSONResponse:
+            event = graph.events.get(event_id)
+            if event is None:
+                return _error(404, "ErrorItemNotFound", "The specified object was not found in the store.")
+            return event
+
+        def precondition_failed(request: Request, event: dict) -> JSONResponse | None:
+            etag = request.headers.get("If-Match")
+            if etag and etag != event["@odata.etag"]:
+                return _error(412, "ErrorIrresolvableConflict", "The change key passed in the request does not match the current change key for the item.")
+            return None
+
+        @app.get("/v1.0/me")
+        async def me():
+            return graph.me
+
+        @app.get("/v1.0/me/calendars")
+        async def calendars():
+            return {"value": list(graph.calendars.values())}
+
+        @app.get("/v1.0/me/calendars/{calendar_id}/calendarView/delta")
+        async def calendar_view_delta(calendar_id: str, request: Request):
+            return graph.delta(request, calendar_id)
+
+        @app.post("/v1.0/me/calendars/{calendar_id}/events", status_code=201)
+        async def create_event(calendar_id: str, request: Request):
+            if calendar_id not in graph.calendars:
+                return _error(404, "ErrorItemNotFound", "The specified object was not found in the store.")
+            body = await request.json()
+            body.pop("id", None)
+            if body.get("recurrence"):
+                body["type"] = "seriesMaster"
+            return graph._public(graph.add_event(calendar_id, body))
+
+        @app.get("/v1.0/me/events/{event_id}")
+        async def get_event(event_id: str, request: Request):
+            event = find_event(event_id)
+            if isinstance(event, JSONResponse):
+                return event
+            public = graph._public(event)
+            if "cancelledOccurrences" not in request.query_params.get("$select", "").split(","):
+                public.pop("cancelledOccurrences", None)
+            return public
+
+        @app.patch("/v1.0/me/events/{event_id}")
+        async def patch_event(event_id: str, request: Request):
+            event = find_event(event_id)
+            if isinstance(event, JSONResponse):
+                return event
+            if failed := precondition_failed(request, event):
+                return failed
+            changes = {key: value for key, value in (await request.json()).items() if key not in ("id", "@odata.etag")}
+            return graph._public(graph.update_event(event_id, **changes))
+
+        @app.delete("/v1.0/me/events/{event_id}")
+        async def delete_event(event_id: str, request: Request):
+            event = find_event(event_id)
+            if isinstance(event, JSONResponse):
+                return event
+            if failed := precondition_failed(request, event):
+                return failed
+            graph.delete_event(event_id)
+            return Response(status_code=204)
+
+        @app.post("/v1.0/subscriptions", status_code=201)
+        async def create_subscription(request: Request):
+            body = await request.json()
+            subscription = {
+                "id": uuid.uuid4().hex,
+                "resource": body["resource"],
+                "changeType": body["changeType"],
+                "notificationUrl": body["notificationUrl"],
+                "clientState": body.get("clientState"),
+                "expirationDateTime": min(datetime.fromisoformat(body["expirationDateTime"]), _now() + SUBSCRIPTION_TTL).isoformat(),
+            }
+            graph.subscriptions[subscription["id"]] = subscription
+            return subscription
+
+        @app.delete("/v1.0/subscriptions/{subscription_id}")
+        async def delete_subscription(subscription_id: str):
+            if graph.subscriptions.pop(subscription_id, None) is None:
+                return _error(404, "ResourceNotFound", "The object was not found.")
+            return Response(status_code=204)
+
+        @app.post("/oauth/token")
+        async def token(request: Request):
+            form = {key: values[0] for key, values in parse_qs((await request.body()).decode()).items()}
+            grant = form.get("grant_type")
+            if grant == "authorization_code":
+                valid = form.get("code") in graph.codes
+                graph.codes.discard(form.get("code", ""))
+            elif grant == "refresh_token":
+                refresh_token = form.get("refresh_token")
+                valid = refresh_token in graph.refresh_tokens or bool(refresh_token and graph.permissive)
+            else:
+                valid = False
+            if not valid:
+                return JSONResponse({"error": "invalid_grant", "error_description": "The provided grant is invalid or expired."}, status_code=400)
+            access_token, refresh_token = graph.issue_tokens()
+            return {"access_token": access_token, "refresh_token": refresh_token, "expires_in": ACCESS_TOKEN_TTL_SECONDS, "token_type": "Bearer"}
+
+        return app
+
+
+fake_graph = FakeGraph(permissive=True)
+fake_graph.add_calendar("calendar", "Calendar", default=True)
+app = fake_graph.app
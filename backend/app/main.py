@@ -1,17 +1,20 @@
 import logging
 from contextlib import asynccontextmanager
 
-from fastapi import FastAPI, Request
+from fastapi import Depends, FastAPI, Request
 from fastapi.middleware.cors import CORSMiddleware
 from fastapi.middleware.gzip import GZipMiddleware
 from fastapi.responses import JSONResponse
 from slowapi import _rate_limit_exceeded_handler
 from slowapi.errors import RateLimitExceeded
 
+from app.calendar.quota import get_quota_manager
+from app.calendar.scheduler import sync_scheduler
 from app.config import get_settings
-from app.core.dependencies import close_http_client
+from app.core.dependencies import close_http_client, get_current_user
+from app.core.live_updates import start_relay, stop_relay
 from app.core.security import SecurityHeadersMiddleware
-from app.routers import auth, calendar, todos
+from app.routers import auth, calendar, live, todos
 
 logging.basicConfig(
     level=logging.INFO,
@@ -28,7 +31,11 @@ settings = get_settings()
 
 @asynccontextmanager
 async def lifespan(app: FastAPI):
+    sync_scheduler.start()
+    start_relay()
     yield
+    await stop_relay()
+    await sync_scheduler.stop()
     await close_http_client()
 
 
@@ -51,12 +58,13 @@ app.add_middleware(
     allow_origins=settings.cors_origins,
     allow_credentials=True,
     allow_methods=["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"],
-    allow_headers=["Content-Type", "Accept", "X-Request-ID", "X-CSRF-Token"],
+    allow_headers=["Content-Type", "Accept", "X-Request-ID", "X-CSRF-Token", "Last-Event-ID", "If-Match"],
 )
 
 app.include_router(auth.router, prefix="/auth", tags=["auth"])
 app.include_router(calendar.router, prefix="/calendar", tags=["calendar"])
 app.include_router(todos.router, prefix="/todos", tags=["todos"])
+app.include_router(live.router, prefix="/live", tags=["live"])
 
 @app.get("/")
 async def root():
@@ -65,3 +73,7 @@ async def root():
 @app.get("/health")
 async def health():
     return {"status": "healthy"}
+
+@app.get("/metrics", dependencies=[Depends(get_current_user)])
+async def metrics():
+    return {"google_quota": get_quota_manager().snapshot()}
@@ -1,4 +1,8 @@
-from pydantic import BaseModel, model_validator
+from typing import Literal
+
+from pydantic import BaseModel, Field, model_validator
+
+MAX_BATCH_OPERATIONS = 50
 
 
 class EventDateTime(BaseModel):
@@ -56,6 +60,37 @@ class EventPatch(BaseModel):
     conferenceData: dict | None = None
 
 
+class BatchOperation(BaseModel):
+    method: Literal["create", "update", "delete"]
+    eventId: str | None = None
+    etag: str | None = None
+    event: Event | None = None
+    patch: EventPatch | None = None
+
+    @model_validator(mode="after")
+    def require_operation_fields(self):
+        if self.method == "create" and self.event is None:
+            raise ValueError("create operations require an event")
+        if self.method == "update" and (not self.eventId or self.patch is None):
+            raise ValueError("update operations require eventId and patch")
+        if self.method == "delete" and not self.eventId:
+            raise ValueError("delete operations require eventId")
+        return self
+
+
+class EventBatch(BaseModel):
+    operations: list[BatchOperation] = Field(..., min_length=1, max_length=MAX_BATCH_OPERATIONS)
+
+
+class EventMove(BaseModel):
+    destinationCalendarId: str
+
+
+class SeriesSplit(BaseModel):
+    instanceStart: EventDateTime
+    event: EventPatch
+
+
 class EventCompletion(BaseModel):
     google_calendar_id: str
     master_event_id: str
@@ -1,21 +1,32 @@
+import asyncio
+import hmac
 import html
 import json
 import logging
+import secrets
 from datetime import datetime, timedelta, timezone
 from urllib.parse import parse_qsl, urlencode, urlparse, urlunparse
 from uuid import UUID
 
 import httpx
 from fastapi import APIRouter, Depends, HTTPException, Query, Request, Response
-from fastapi.responses import HTMLResponse
+from fastapi.responses import HTMLResponse, RedirectResponse
 from pydantic import BaseModel
 from supabase_auth.errors import AuthApiError
 from slowapi import Limiter
 from slowapi.util import get_remote_address
 
+from app.calendar.channels import stop_account_channels
+from app.calendar.constants import MicrosoftGraphConfig
+from app.calendar.google_client import APIBaseURL
+from app.calendar.providers import provider_source
+from app.calendar.providers.microsoft import MicrosoftProvider, graph_url, microsoft_account_key, oauth_url
+from app.calendar.scheduler import enqueue_sync_job, sync_scheduler
+from app.calendar.sync import CalendarListSync
 from app.config import get_settings
+from app.core.coordination import get_coordination
 from app.core.csrf import create_csrf_token
-from app.core.dependencies import CurrentUser, RefreshTokenCookie, SessionTokenCookie, get_user
+from app.core.dependencies import CurrentUser, RefreshTokenCookie, SessionTokenCookie, get_http_client, get_user
 from app.core.sessions import (
     delete_cookie,
     get_expires_at,
@@ -94,6 +105,128 @@ def store_google_account(
     raise ValueError("Failed to upsert google account")
 
 
+def store_microsoft_account(supabase, user_id: str, profile: dict, tokens: dict) -> str:
+    account = (
+        supabase.table("google_accounts")
+        .upsert({
+            "user_id": user_id,
+            "google_id": microsoft_account_key(profile["id"]),
+            "email": profile.get("mail") or profile.get("userPrincipalName"),
+            "name": profile.get("displayName"),
+            "provider": MicrosoftProvider.source,
+        }, on_conflict="user_id,google_id")
+        .execute()
+    )
+    if not account.data:
+        raise ValueError("Failed to upsert Microsoft account")
+    account_id = account.data[0]["id"]
+    expires_at = datetime.now(timezone.utc) + timedelta(seconds=tokens.get("expires_in", 3600))
+    supabase.table("google_account_tokens").upsert({
+        "google_account_id": account_id,
+        "access_token": tokens["access_token"],
+        "refresh_token": tokens.get("refresh_token"),
+        "expires_at": expires_at.isoformat(),
+    }, on_conflict="google_account_id").execute()
+    logger.info("Stored Microsoft account %s for user %s", profile["id"], user_id)
+    return account_id
+
+
+MICROSOFT_STATE_COOKIE = "microsoft_oauth_state"
+
+
+def _microsoft_state_key(state: str) -> str:
+    return f"microsoft-oauth:{state}"
+
+
+@router.get("/microsoft/connect")
+@limiter.limit(settings.RATE_LIMIT_AUTH)
+async def initiate_microsoft_connect(request: Request, response: Response, current_user: CurrentUser):
+    if not settings.MICROSOFT_CLIENT_ID or not settings.MICROSOFT_REDIRECT_URL:
+        raise HTTPException(status_code=503, detail="Microsoft accounts are not configured")
+
+    state = f"{secrets.token_urlsafe(24)}.{current_user['id']}"
+    await get_coordination().touch(_microsoft_state_key(state), MicrosoftGraphConfig.OAUTH_STATE_TTL_SECONDS)
+    # Binds the callback to this browser; lax so it survives the cross-site redirect back from Microsoft.
+    response.set_cookie(
+        key=MICROSOFT_STATE_COOKIE,
+        value=state,
+        max_age=MicrosoftGraphConfig.OAUTH_STATE_TTL_SECONDS,
+        httponly=True,
+        secure=settings.COOKIE_SECURE,
+        samesite="lax",
+        domain=settings.COOKIE_DOMAIN,
+        path="/",
+    )
+    query = urlencode({
+        "client_id": settings.MICROSOFT_CLIENT_ID,
+        "response_type": "code",
+        "redirect_uri": settings.MICROSOFT_REDIRECT_URL,
+        "response_mode": "query",
+        "scope": MicrosoftGraphConfig.SCOPES,
+        "state": state,
+        "prompt": "select_account",
+    })
+    return {"redirectUrl": f"{oauth_url()}/authorize?{query}"}
+
+
+@router.get("/microsoft/callback", include_in_schema=False)
+@limiter.limit(settings.RATE_LIMIT_AUTH)
+async def microsoft_callback(
+    request: Request,
+    code: str | None = Query(default=None),
+    state: str | None = Query(default=None),
+    error: str | None = Query(default=None),
+):
+    frontend_url = settings.FRONTEND_URL.rstrip("/")
+    coordination = get_coordination()
+    bound_state = request.cookies.get(MICROSOFT_STATE_COOKIE)
+    if not state or not bound_state or not hmac.compare_digest(state, bound_state):
+        raise HTTPException(status_code=400, detail="Invalid OAuth state")
+    if not await coordination.exists(_microsoft_state_key(state)):
+        raise HTTPException(status_code=400, detail="Invalid OAuth state")
+    await coordination.release(_microsoft_state_key(state))
+    if error or not code:
+        return _microsoft_redirect(f"{frontend_url}/?{urlencode({'error': error or 'microsoft_connect_failed'})}")
+
+    user_id = state.rsplit(".", 1)[1]
+    http = await get_http_client()
+    try:
+        token_response = await http.post(oauth_url() + "/token", data={
+            "client_id": settings.MICROSOFT_CLIENT_ID,
+            "client_secret": settings.MICROSOFT_CLIENT_SECRET,
+            "code": code,
+            "redirect_uri": settings.MICROSOFT_REDIRECT_URL,
+            "grant_type": "authorization_code",
+            "scope": MicrosoftGraphConfig.SCOPES,
+        })
+        if token_response.status_code != 200:
+            raise HTTPException(status_code=400, detail="Failed to exchange Microsoft authorization code")
+        tokens = token_response.json()
+        profile_response = await http.get(graph_url() + "/me", headers={"Authorization": f"Bearer {tokens['access_token']}"})
+        if profile_response.status_code != 200:
+            raise HTTPException(status_code=502, detail="Failed to load Microsoft profile")
+    except httpx.HTTPError:
+        raise HTTPException(status_code=502, detail="External service error")
+
+    supabase = get_supabase_client()
+    account_id = str(store_microsoft_account(supabase, user_id, profile_response.json(), tokens))
+    try:
+        await CalendarListSync(supabase, http, user_id, account_id).run(full=True)
+        calendars = supabase.table("google_calendars").select("id").eq("google_account_id", account_id).execute().data or []
+        for calendar in calendars:
+            await asyncio.to_thread(enqueue_sync_job, supabase, str(calendar["id"]), user_id, "connect")
+        sync_scheduler.wake()
+    except Exception:
+        logger.exception("Initial calendar list sync failed for Microsoft account %s", account_id)
+    return _microsoft_redirect(f"{frontend_url}/?{urlencode({'connected': MicrosoftProvider.source})}")
+
+
+def _microsoft_redirect(url: str) -> RedirectResponse:
+    response = RedirectResponse(url)
+    response.delete_cookie(MICROSOFT_STATE_COOKIE, domain=settings.COOKIE_DOMAIN, path="/")
+    return response
+
+
 @router.get("/google/login")
 @limiter.limit(settings.RATE_LIMIT_AUTH)
 async def initiate_google_login(
@@ -362,7 +495,7 @@ async def delete_google_account(
 
     account_result = (
         supabase.table("google_accounts")
-        .select("id, user_id, google_account_tokens(access_token)")
+        .select("id, user_id, provider, google_account_tokens(access_token)")
         .eq("id", str(google_account_id))
         .maybe_single()
         .execute()
@@ -372,11 +505,15 @@ async def delete_google_account(
     if account and account["user_id"] == user_id:
         tokens = account.get("google_account_tokens")
         if tokens and tokens.get("access_token"):
-            access_token = tokens["access_token"]
-            async with httpx.AsyncClient() as client:
-                await client.post(
-                    "https://oauth2.googleapis.com/revoke", data={"token": access_token}
-                )
+            try:
+                await stop_account_channels(supabase, await get_http_client(), user_id, str(google_account_id))
+            except Exception:
+                logger.exception("Failed to stop watch channels for Google account %s", google_account_id)
+            if provider_source(account) == "google":
+                async with httpx.AsyncClient() as client:
+                    await client.post(
+                        APIBaseURL.OAUTH.url + "/revoke", data={"token": tokens["access_token"]}
+                    )
 
         delete_result = (
             supabase.table("google_accounts")
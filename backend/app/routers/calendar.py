@@ -1,25 +1,37 @@
 import asyncio
 import hmac
 import logging
+import re
 import uuid
-from urllib.parse import quote
+from datetime import datetime, timedelta, timezone
+from urllib.parse import quote, urlparse
 
-from cachetools import TTLCache
-from fastapi import APIRouter, Depends, HTTPException, Query, Request
-from fastapi.responses import Response, StreamingResponse
+from fastapi import APIRouter, BackgroundTasks, Depends, Header, HTTPException, Query, Request
+from fastapi.responses import PlainTextResponse, Response, StreamingResponse
 from pydantic import BaseModel, Field
 from supabase import Client
 
-from app.calendar.google_client import GoogleAPIClient, proxy_photo
+from app.calendar.channels import ACCOUNT_SYNC_STATE_TABLE
+from app.calendar.constants import SyncHealthConfig
+from app.calendar.export import export_ical
+from app.calendar.google_client import proxy_photo
 from app.calendar.helpers import (
-    GoogleAPIError,
+    ProviderError,
     format_sse,
     get_google_account,
     get_google_accounts_for_user,
-    transform_events,
+    get_google_calendar,
 )
-from app.calendar.sync import Sync
-from app.models.event import Event, EventPatch
+from app.calendar.move import EventMoveError, move_event
+from app.calendar.providers import CalendarProvider, get_provider, provider_for_calendar, provider_source, provider_sources
+from app.calendar.providers.caldav import CREDENTIALS_TABLE, CalDAVProvider, caldav_account_key, discover_calendar_home
+from app.calendar.providers.chronos import CHRONOS_DEFAULT_COLOR, ChronosProvider, ensure_chronos_account
+from app.calendar.recurrence import expand_events
+from app.calendar.series import SeriesSplitError, split_series
+from app.calendar.scheduler import enqueue_sync_job, run_calendar_list_sync, run_calendar_sync, sync_scheduler
+from app.calendar.sync import BackfillStatus, CalendarListSync, Sync, remove_calendars
+from app.calendar.sync_sessions import cancel_session, end_session, start_session
+from app.models.event import Event, EventBatch, EventMove, EventPatch, SeriesSplit
 from app.config import get_settings
 from app.core.dependencies import (
     CurrentUser,
@@ -27,30 +39,32 @@ from app.core.dependencies import (
     SupabaseClientDep,
     VerifiedAccount,
     VerifiedCalendar,
-    get_http_client,
 )
-from app.core.db_utils import all_rows, first_row
+from app.core.coordination import get_coordination
+from app.core.crypto import encrypt_secret
+from app.core.db_utils import Row, all_rows, first_row
 
 from app.core.security import request_guard
-from app.core.exceptions import handle_google_api_error
+from app.core.exceptions import EventConflictError, handle_google_api_error
+from app.core.live_updates import publish_calendar_changed, publish_calendars_changed
 from app.core.supabase import get_supabase_client, create_supabase_client
 from app.models.event import EventCompletion
 
-settings = get_settings()
-
 logger = logging.getLogger(__name__)
 router = APIRouter()
 
 MAX_CALENDARS_PER_SYNC = 20
 MAX_CONCURRENT_CALENDAR_FETCHES = 5
 MAX_SYNC_DURATION_SECONDS = 300
+EVENTS_PAGE_SIZE = 1000
+MAX_EVENTS_PAGE_SIZE = 1000
+MAX_INSTANCES_RANGE_DAYS = 366
 SYNC_RATE_LIMIT_SECONDS = 5
 WEBHOOK_DEBOUNCE_SECONDS = 10
 LOCAL_MUTATION_WEBHOOK_TTL_SECONDS = 15
-_sync_rate_limits: TTLCache = TTLCache(maxsize=1024, ttl=SYNC_RATE_LIMIT_SECONDS)
-_webhook_debounce: TTLCache = TTLCache(maxsize=1024, ttl=WEBHOOK_DEBOUNCE_SECONDS)
-_local_mutation_webhook_suppression: TTLCache = TTLCache(maxsize=1024, ttl=LOCAL_MUTATION_WEBHOOK_TTL_SECONDS)
-_webhook_sync_semaphore = asyncio.Semaphore(1)
+INTERRUPTED_SYNC_RESUME_SECONDS = 300
+# syncedAt is stamped before the upsert commits, so a delta window must reach back past slow writes.
+DELTA_SYNC_OVERLAP_SECONDS = 120
 
 
 def get_completed_events(supabase: Client, calendar_ids: list[str]) -> list[dict]:
@@ -81,6 +95,52 @@ def get_latest_sync_at(supabase: Client, calendar_ids: list[str]) -> str | None:
     return str(row["last_sync_at"]) if row else None
 
 
+def sync_health(state: Row, now: datetime) -> str:
+    failures = int(state.get("consecutive_failures") or 0)
+    if failures >= SyncHealthConfig.FAILING_AFTER_FAILURES:
+        return "failing"
+    if failures:
+        return "degraded"
+    if not state.get("last_success_at"):
+        return "never_synced"
+    last_success = datetime.fromisoformat(str(state["last_success_at"]))
+    if last_success.tzinfo is None:
+        last_success = last_success.replace(tzinfo=timezone.utc)
+    return "stale" if now - last_success > SyncHealthConfig.STALE_AFTER else "healthy"
+
+
+def get_calendar_sync_statuses(supabase: Client, calendar_ids: list[str]) -> list[dict]:
+    if not calendar_ids:
+        return []
+    states = {
+        row["google_calendar_id"]: row
+        for row in all_rows(
+            supabase.table("calendar_sync_state")
+            .select("google_calendar_id, last_sync_at, last_success_at, last_error_at, last_error_code, last_error_message, consecutive_failures, event_count, webhook_expires_at")
+            .in_("google_calendar_id", calendar_ids)
+            .execute()
+            .data
+        )
+    }
+    now = datetime.now(timezone.utc)
+    statuses = []
+    for calendar_id in calendar_ids:
+        state = states.get(calendar_id, {})
+        statuses.append({
+            "calendarId": calendar_id,
+            "lastSyncAt": state.get("last_sync_at"),
+            "lastSuccessAt": state.get("last_success_at"),
+            "lastErrorAt": state.get("last_error_at"),
+            "lastErrorCode": state.get("last_error_code"),
+            "lastErrorMessage": state.get("last_error_message"),
+            "consecutiveFailures": int(state.get("consecutive_failures") or 0),
+            "eventCount": state.get("event_count"),
+            "webhookExpiresAt": state.get("webhook_expires_at"),
+            "health": sync_health(state, now),
+        })
+    return statuses
+
+
 def resolve_calendar_ids(supabase: Client, user_id: str, calendar_ids: str | None = None) -> list[str]:
     all_ids = [
         cal["id"] for cal in
@@ -96,44 +156,104 @@ def resolve_calendar_ids(supabase: Client, user_id: str, calendar_ids: str | Non
     return [cid for cid in all_ids if cid in requested]
 
 
-def query_events(supabase: Client, calendar_ids: list[str]) -> tuple[list[dict], list[dict], list[dict]]:
-    events_result = (
-        supabase.table("events")
-        .select("*")
-        .in_("googleCalendarId", calendar_ids)
-        .eq("source", "google")
-        .is_("recurrence", "null")
-        .is_("recurringEventId", "null")
-        .neq("status", "cancelled")
-        .execute()
-    )
-    masters_result = (
-        supabase.table("events")
-        .select("*")
-        .in_("googleCalendarId", calendar_ids)
-        .eq("source", "google")
-        .not_.is_("recurrence", "null")
-        .is_("recurringEventId", "null")
-        .neq("status", "cancelled")
-        .execute()
-    )
-    exceptions_result = (
-        supabase.table("events")
-        .select("*")
-        .in_("googleCalendarId", calendar_ids)
-        .eq("source", "google")
-        .not_.is_("recurringEventId", "null")
-        .execute()
-    )
-    return (
-        all_rows(events_result.data),
-        all_rows(masters_result.data),
-        all_rows(exceptions_result.data),
+def _filter_ts(value: datetime) -> str:
+    return f'"{value.astimezone(timezone.utc).isoformat()}"'
+
+
+def query_events(
+    supabase: Client,
+    calendar_ids: list[str],
+    time_min: datetime | None = None,
+    time_max: datetime | None = None,
+    updated_since: datetime | None = None,
+    cursor: str | None = None,
+    limit: int = EVENTS_PAGE_SIZE,
+) -> tuple[list[dict], list[dict], list[dict], str | None]:
+    def base_query():
+        query = (
+            supabase.table("events")
+            .select("*")
+            .in_("googleCalendarId", calendar_ids)
+            .in_("source", provider_sources())
+        )
+        if updated_since is not None:
+            query = query.gte("syncedAt", updated_since.isoformat())
+        if cursor:
+            query = query.gt("id", cursor)
+        return query
+
+    events_query = base_query().is_("recurrence", "null").is_("recurringEventId", "null")
+    masters_query = base_query().not_.is_("recurrence", "null").is_("recurringEventId", "null")
+    exceptions_query = base_query().not_.is_("recurringEventId", "null")
+
+    if updated_since is None:
+        events_query = events_query.neq("status", "cancelled")
+        masters_query = masters_query.neq("status", "cancelled")
+
+    if time_min is not None:
+        events_query = events_query.gt("endAt", time_min.isoformat())
+        masters_query = masters_query.or_(f"recurrenceEndAt.is.null,recurrenceEndAt.gt.{_filter_ts(time_min)}")
+    if time_max is not None:
+        events_query = events_query.lt("startAt", time_max.isoformat())
+        masters_query = masters_query.lt("startAt", time_max.isoformat())
+    if time_min is not None or time_max is not None:
+        moved = ["startAt.lt." + _filter_ts(time_max)] if time_max else []
+        moved += ["endAt.gt." + _filter_ts(time_min)] if time_min else []
+        original = ["originalStartAt.gte." + _filter_ts(time_min)] if time_min else []
+        original += ["originalStartAt.lt." + _filter_ts(time_max)] if time_max else []
+        exceptions_query = exceptions_query.or_(f"and({','.join(moved)}),and({','.join(original)})")
+
+    results = [
+        all_rows(query.order("id").limit(limit).execute().data)
+        for query in (events_query, masters_query, exceptions_query)
+    ]
+
+    page = sorted((row for rows in results for row in rows), key=lambda row: str(row["id"]))[:limit]
+    has_more = any(len(rows) == limit for rows in results) or sum(len(rows) for rows in results) > limit
+    next_cursor = str(page[-1]["id"]) if has_more and page else None
+    page_ids = {row["id"] for row in page}
+
+    events, masters, exceptions = (
+        [row for row in rows if row["id"] in page_ids] for rows in results
     )
+    return events, masters, exceptions, next_cursor
+
+
+def query_all_events(
+    supabase: Client,
+    calendar_ids: list[str],
+    time_min: datetime | None = None,
+    time_max: datetime | None = None,
+) -> tuple[list[dict], list[dict], list[dict]]:
+    events: list[dict] = []
+    masters: list[dict] = []
+    exceptions: list[dict] = []
+    cursor: str | None = None
+    while True:
+        page_events, page_masters, page_exceptions, cursor = query_events(
+            supabase, calendar_ids, time_min=time_min, time_max=time_max, cursor=cursor,
+        )
+        events += page_events
+        masters += page_masters
+        exceptions += page_exceptions
+        if cursor is None:
+            return events, masters, exceptions
 
 
-def suppress_webhooks_for_calendar(calendar_id: str) -> None:
-    _local_mutation_webhook_suppression[calendar_id] = True
+async def suppress_webhooks_for_calendar(calendar_id: str) -> None:
+    await get_coordination().touch(f"webhook-suppress:{calendar_id}", LOCAL_MUTATION_WEBHOOK_TTL_SECONDS)
+
+
+async def raise_event_conflict(client: CalendarProvider, supabase: Client, calendar: dict, event_id: str):
+    try:
+        current = await client.get_event(calendar["google_calendar_id"], event_id)
+    except ProviderError as e:
+        if e.status_code not in (404, 410):
+            handle_google_api_error(e)
+        raise EventConflictError(None)
+    transformed = client.transform_events([current], calendar)
+    supabase.table("events").upsert(transformed, on_conflict="googleCalendarId,googleEventId,source").execute()
+    raise EventConflictError(transformed[0])
 
 
 @router.get("/events")
@@ -141,21 +261,109 @@ async def list_events(
     current_user: CurrentUser,
     supabase: SupabaseClientDep,
     calendar_ids: str | None = Query(None),
+    time_min: datetime | None = Query(None, alias="timeMin"),
+    time_max: datetime | None = Query(None, alias="timeMax"),
+    updated_since: datetime | None = Query(None, alias="updatedSince"),
+    cursor: str | None = Query(None),
+    limit: int = Query(EVENTS_PAGE_SIZE, ge=1, le=MAX_EVENTS_PAGE_SIZE),
 ):
+    for value in (time_min, time_max, updated_since):
+        if value is not None and value.tzinfo is None:
+            raise HTTPException(status_code=400, detail="Timestamps must include a timezone offset")
+    if time_min and time_max and time_min >= time_max:
+        raise HTTPException(status_code=400, detail="timeMin must be before timeMax")
+
     user_id = current_user["id"]
     calendar_id_list = resolve_calendar_ids(supabase, user_id, calendar_ids)
+    server_time = (datetime.now(timezone.utc) - timedelta(seconds=DELTA_SYNC_OVERLAP_SECONDS)).isoformat()
 
     if calendar_id_list:
-        events_raw, masters_raw, exceptions_raw = query_events(supabase, calendar_id_list)
-        completions = get_completed_events(supabase, calendar_id_list)
+        events_raw, masters_raw, exceptions_raw, next_cursor = query_events(
+            supabase, calendar_id_list,
+            time_min=time_min, time_max=time_max, updated_since=updated_since,
+            cursor=cursor, limit=limit,
+        )
+        completions = get_completed_events(supabase, calendar_id_list) if cursor is None else []
 
         return {
             "events": events_raw,
             "masters": masters_raw,
             "exceptions": exceptions_raw,
             "completions": completions,
+            "nextCursor": next_cursor,
+            "serverTime": server_time,
         }
-    return {"events": [], "masters": [], "exceptions": [], "completions": []}
+    return {
+        "events": [], "masters": [], "exceptions": [], "completions": [],
+        "nextCursor": None, "serverTime": server_time,
+    }
+
+
+@router.get("/instances")
+async def list_instances(
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    time_min: datetime = Query(..., alias="timeMin"),
+    time_max: datetime = Query(..., alias="timeMax"),
+    calendar_ids: str | None = Query(None),
+):
+    if time_min.tzinfo is None or time_max.tzinfo is None:
+        raise HTTPException(status_code=400, detail="Timestamps must include a timezone offset")
+    if time_min >= time_max:
+        raise HTTPException(status_code=400, detail="timeMin must be before timeMax")
+    if time_max - time_min > timedelta(days=MAX_INSTANCES_RANGE_DAYS):
+        raise HTTPException(status_code=400, detail=f"Range cannot exceed {MAX_INSTANCES_RANGE_DAYS} days")
+
+    calendar_id_list = resolve_calendar_ids(supabase, current_user["id"], calendar_ids)
+    if not calendar_id_list:
+        return {"instances": []}
+
+    events, masters, exceptions = query_all_events(supabase, calendar_id_list, time_min, time_max)
+    completions = get_completed_events(supabase, calendar_id_list)
+    instances = expand_events(events, masters, exceptions, time_min, time_max, completions)
+    return {"instances": instances}
+
+
+def validate_export_range(time_min: datetime | None, time_max: datetime | None) -> None:
+    for value in (time_min, time_max):
+        if value is not None and value.tzinfo is None:
+            raise HTTPException(status_code=400, detail="Timestamps must include a timezone offset")
+    if time_min and time_max and time_min >= time_max:
+        raise HTTPException(status_code=400, detail="timeMin must be before timeMax")
+
+
+def ical_response(supabase: Client, calendar_ids: list[str], name: str, time_min: datetime | None, time_max: datetime | None) -> Response:
+    events, masters, exceptions = query_all_events(supabase, calendar_ids, time_min, time_max) if calendar_ids else ([], [], [])
+    filename = re.sub(r"[^A-Za-z0-9_-]+", "-", name).strip("-") or "calendar"
+    return Response(
+        content=export_ical([*events, *masters, *exceptions], name),
+        media_type="text/calendar; charset=utf-8",
+        headers={"Content-Disposition": f'attachment; filename="{filename}.ics"'},
+    )
+
+
+@router.get("/export.ics")
+async def export_calendars(
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    calendar_ids: str | None = Query(None),
+    time_min: datetime | None = Query(None, alias="timeMin"),
+    time_max: datetime | None = Query(None, alias="timeMax"),
+):
+    validate_export_range(time_min, time_max)
+    calendar_id_list = resolve_calendar_ids(supabase, current_user["id"], calendar_ids)
+    return ical_response(supabase, calendar_id_list, "Chronos", time_min, time_max)
+
+
+@router.get("/{calendar_id}/export.ics")
+async def export_calendar(
+    supabase: SupabaseClientDep,
+    verified_calendar: VerifiedCalendar,
+    time_min: datetime | None = Query(None, alias="timeMin"),
+    time_max: datetime | None = Query(None, alias="timeMax"),
+):
+    validate_export_range(time_min, time_max)
+    return ical_response(supabase, [verified_calendar["id"]], verified_calendar["name"], time_min, time_max)
 
 
 @router.get("/accounts")
@@ -174,7 +382,7 @@ async def list_google_calendars(
 ):
     calendars = (
         supabase.table("google_calendars")
-        .select("*, google_accounts!inner(user_id)")
+        .select("*, google_accounts!inner(user_id, provider), calendar_sync_state(backfill_enabled, backfill_status, backfill_events_synced)")
         .eq("google_accounts.user_id", current_user["id"])
         .execute()
         .data or []
@@ -182,6 +390,90 @@ async def list_google_calendars(
     return {"calendars": calendars}
 
 
+class ChronosCalendarRequest(BaseModel):
+    name: str = Field(..., min_length=1, max_length=200)
+    color: str = Field(CHRONOS_DEFAULT_COLOR, pattern=r"^#[0-9a-fA-F]{6}$")
+
+
+class ChronosCalendarPatch(BaseModel):
+    name: str | None = Field(None, min_length=1, max_length=200)
+    color: str | None = Field(None, pattern=r"^#[0-9a-fA-F]{6}$")
+
+
+def require_chronos_calendar(calendar: dict) -> None:
+    if provider_source(calendar) != ChronosProvider.source:
+        raise HTTPException(status_code=400, detail="Only Chronos calendars can be managed here")
+
+
+@router.post("/calendars", dependencies=[Depends(request_guard.authorize)])
+async def create_chronos_calendar(
+    body: ChronosCalendarRequest,
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+):
+    user_id = current_user["id"]
+    try:
+        account = ensure_chronos_account(supabase, user_id, current_user.get("email") or "")
+    except ProviderError as e:
+        raise HTTPException(status_code=e.status_code, detail=e.message)
+    calendar = first_row(
+        supabase.table("google_calendars")
+        .insert({
+            "google_account_id": account["id"],
+            "google_calendar_id": uuid.uuid4().hex,
+            "name": body.name,
+            "color": body.color,
+            "is_primary": False,
+            "access_role": "owner",
+        })
+        .execute()
+        .data
+    )
+    if calendar is None:
+        raise HTTPException(status_code=500, detail="Failed to create calendar")
+    publish_calendars_changed(user_id, [])
+    return calendar
+
+
+@router.patch("/calendars/{calendar_id}", dependencies=[Depends(request_guard.authorize)])
+async def update_chronos_calendar(
+    body: ChronosCalendarPatch,
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    verified_calendar: VerifiedCalendar,
+):
+    require_chronos_calendar(verified_calendar)
+    changes = body.model_dump(exclude_none=True)
+    if not changes:
+        raise HTTPException(status_code=400, detail="Nothing to update")
+    calendar_id = verified_calendar["id"]
+    calendar = first_row(supabase.table("google_calendars").update(changes).eq("id", calendar_id).execute().data)
+    if "color" in changes and verified_calendar.get("color"):
+        # Events without their own color carry the calendar color, so recolor them with it.
+        now = datetime.now(timezone.utc).isoformat()
+        supabase.table("events").update({"colorId": changes["color"], "updatedAt": now, "syncedAt": now}).eq(
+            "googleCalendarId", calendar_id
+        ).eq("colorId", verified_calendar["color"]).execute()
+        publish_calendar_changed(current_user["id"], calendar_id, None)
+    publish_calendars_changed(current_user["id"], [])
+    return calendar
+
+
+@router.delete("/calendars/{calendar_id}", status_code=204, dependencies=[Depends(request_guard.authorize)])
+async def delete_chronos_calendar(
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    verified_calendar: VerifiedCalendar,
+    http: HttpClient,
+):
+    require_chronos_calendar(verified_calendar)
+    calendar_id = verified_calendar["id"]
+    client = provider_for_calendar(supabase, http, current_user["id"], verified_calendar)
+    await remove_calendars(client, supabase, [calendar_id])
+    publish_calendars_changed(current_user["id"], [calendar_id])
+    return Response(status_code=204)
+
+
 @router.get("/sync-status")
 async def get_sync_status(
     current_user: CurrentUser,
@@ -192,7 +484,8 @@ async def get_sync_status(
     calendar_id_list = resolve_calendar_ids(supabase, user_id, calendar_ids)
 
     last_sync_at = get_latest_sync_at(supabase, calendar_id_list)
-    return {"lastSyncAt": last_sync_at}
+    calendars = get_calendar_sync_statuses(supabase, calendar_id_list)
+    return {"lastSyncAt": last_sync_at, "calendars": calendars}
 
 
 @router.post("/complete-event", dependencies=[Depends(request_guard.authorize)])
@@ -229,13 +522,14 @@ async def event_creation(
     event_body: Event
 ):
     try:
-        suppress_webhooks_for_calendar(verified_calendar["id"])
-        client = GoogleAPIClient(supabase, http, current_user["id"], verified_calendar["google_account_id"])
+        await suppress_webhooks_for_calendar(verified_calendar["id"])
+        client = provider_for_calendar(supabase, http, current_user["id"], verified_calendar)
         response = await client.create_event(verified_calendar["google_calendar_id"], event_body)
-        transformed = transform_events([response], verified_calendar["id"], verified_calendar["google_account_id"], verified_calendar.get("color"))
+        transformed = client.transform_events([response], verified_calendar)
         supabase.table("events").upsert(transformed, on_conflict="googleCalendarId,googleEventId,source").execute()
+        publish_calendar_changed(current_user["id"], verified_calendar["id"], transformed)
         return transformed[0]
-    except GoogleAPIError as e:
+    except ProviderError as e:
         handle_google_api_error(e)
 
 @router.patch("/{calendar_id}/events/{event_id}", dependencies=[Depends(request_guard.authorize)])
@@ -246,18 +540,105 @@ async def event_update(
     supabase: SupabaseClientDep,
     verified_calendar: VerifiedCalendar,
     http: HttpClient,
-    event_body: EventPatch
+    event_body: EventPatch,
+    if_match: str | None = Header(None, alias="If-Match"),
 ):
+    client = provider_for_calendar(supabase, http, current_user["id"], verified_calendar)
     try:
-        suppress_webhooks_for_calendar(verified_calendar["id"])
-        client = GoogleAPIClient(supabase, http, current_user["id"], verified_calendar["google_account_id"])
-        response = await client.edit_event(verified_calendar["google_calendar_id"], event_id, event_body)
-        transformed = transform_events([response], verified_calendar["id"], verified_calendar["google_account_id"], verified_calendar.get("color"))
+        await suppress_webhooks_for_calendar(verified_calendar["id"])
+        response = await client.edit_event(verified_calendar["google_calendar_id"], event_id, event_body, etag=if_match)
+        transformed = client.transform_events([response], verified_calendar)
         supabase.table("events").upsert(transformed, on_conflict="googleCalendarId,googleEventId,source").execute()
+        publish_calendar_changed(current_user["id"], verified_calendar["id"], transformed)
         return transformed[0]
-    except GoogleAPIError as e:
+    except ProviderError as e:
+        if e.status_code == 412:
+            await raise_event_conflict(client, supabase, verified_calendar, event_id)
         handle_google_api_error(e)
 
+@router.post("/{calendar_id}/events/{event_id}/split", dependencies=[Depends(request_guard.authorize)])
+async def event_series_split(
+    calendar_id: str,
+    event_id: str,
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    verified_calendar: VerifiedCalendar,
+    http: HttpClient,
+    body: SeriesSplit,
+):
+    try:
+        await suppress_webhooks_for_calendar(verified_calendar["id"])
+        client = provider_for_calendar(supabase, http, current_user["id"], verified_calendar)
+        result = await split_series(client, supabase, verified_calendar, event_id, body.instanceStart, body.event)
+    except SeriesSplitError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except ProviderError as e:
+        handle_google_api_error(e)
+
+    changed = [result["master"], *([result["series"]] if result["series"] else []), *result["exceptions"]]
+    transformed = client.transform_events(changed, verified_calendar)
+    supabase.table("events").upsert(transformed, on_conflict="googleCalendarId,googleEventId,source").execute()
+    if result["cancelled"]:
+        supabase.table("events").update({
+            "status": "cancelled",
+            "syncedAt": datetime.now(timezone.utc).isoformat(),
+        }).eq("googleCalendarId", verified_calendar["id"]).in_("googleEventId", result["cancelled"]).execute()
+    publish_calendar_changed(current_user["id"], verified_calendar["id"], None if result["cancelled"] else transformed)
+
+    return {
+        "master": transformed[0],
+        "series": transformed[1] if result["series"] else None,
+        "exceptions": transformed[2:] if result["series"] else [],
+    }
+
+
+@router.post("/{calendar_id}/events/{event_id}/move", dependencies=[Depends(request_guard.authorize)])
+async def event_move(
+    calendar_id: str,
+    event_id: str,
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    verified_calendar: VerifiedCalendar,
+    http: HttpClient,
+    body: EventMove,
+    if_match: str | None = Header(None, alias="If-Match"),
+):
+    user_id = current_user["id"]
+    destination = get_google_calendar(supabase, body.destinationCalendarId, user_id)
+    if not destination:
+        raise HTTPException(status_code=404, detail="Destination calendar not found")
+
+    source_client = provider_for_calendar(supabase, http, user_id, verified_calendar)
+    destination_client = (
+        source_client
+        if destination["google_account_id"] == verified_calendar["google_account_id"]
+        else provider_for_calendar(supabase, http, user_id, destination)
+    )
+    try:
+        await suppress_webhooks_for_calendar(verified_calendar["id"])
+        await suppress_webhooks_for_calendar(destination["id"])
+        moved = await move_event(source_client, destination_client, supabase, verified_calendar, destination, event_id, etag=if_match)
+    except EventMoveError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except ProviderError as e:
+        if e.status_code == 412:
+            await raise_event_conflict(source_client, supabase, verified_calendar, event_id)
+        handle_google_api_error(e)
+
+    transformed = destination_client.transform_events([moved], destination)
+    supabase.table("events").upsert(transformed, on_conflict="googleCalendarId,googleEventId,source").execute()
+    cancelled = {"status": "cancelled", "syncedAt": datetime.now(timezone.utc).isoformat()}
+    supabase.table("events").update(cancelled).eq("googleCalendarId", verified_calendar["id"]).eq("googleEventId", event_id).execute()
+    if moved.get("recurrence"):
+        supabase.table("events").update(cancelled).eq("googleCalendarId", verified_calendar["id"]).eq("recurringEventId", event_id).execute()
+        await asyncio.to_thread(enqueue_sync_job, supabase, destination["id"], user_id, "move")
+        sync_scheduler.wake()
+
+    publish_calendar_changed(user_id, verified_calendar["id"], None)
+    publish_calendar_changed(user_id, destination["id"], transformed)
+    return transformed[0]
+
+
 @router.delete("/{calendar_id}/events/{event_id}", status_code=204, dependencies=[Depends(request_guard.authorize)])
 async def event_delete(
     calendar_id: str,
@@ -266,16 +647,107 @@ async def event_delete(
     supabase: SupabaseClientDep,
     verified_calendar: VerifiedCalendar,
     http: HttpClient,
+    if_match: str | None = Header(None, alias="If-Match"),
 ):
+    client = provider_for_calendar(supabase, http, current_user["id"], verified_calendar)
     try:
-        suppress_webhooks_for_calendar(verified_calendar["id"])
-        client = GoogleAPIClient(supabase, http, current_user["id"], verified_calendar["google_account_id"])
-        await client.delete_event(verified_calendar["google_calendar_id"], event_id)
-        supabase.table("events").delete().eq("googleCalendarId", verified_calendar["id"]).eq("googleEventId", event_id).execute()
+        await suppress_webhooks_for_calendar(verified_calendar["id"])
+        await client.delete_event(verified_calendar["google_calendar_id"], event_id, etag=if_match)
+        supabase.table("events").update({
+            "status": "cancelled",
+            "syncedAt": datetime.now(timezone.utc).isoformat(),
+        }).eq("googleCalendarId", verified_calendar["id"]).eq("googleEventId", event_id).execute()
+        publish_calendar_changed(current_user["id"], verified_calendar["id"], None)
         return Response(status_code=204)
-    except GoogleAPIError as e:
+    except ProviderError as e:
+        if e.status_code == 412:
+            await raise_event_conflict(client, supabase, verified_calendar, event_id)
         handle_google_api_error(e)
 
+
+@router.post("/{calendar_id}/events:batch", dependencies=[Depends(request_guard.authorize)])
+async def event_batch(
+    body: EventBatch,
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    verified_calendar: VerifiedCalendar,
+    http: HttpClient,
+):
+    calendar_id = verified_calendar["id"]
+    client = provider_for_calendar(supabase, http, current_user["id"], verified_calendar)
+    try:
+        await suppress_webhooks_for_calendar(calendar_id)
+        responses = await client.batch_events(verified_calendar["google_calendar_id"], body.operations)
+    except ProviderError as e:
+        handle_google_api_error(e)
+
+    results = []
+    saved: list[dict] = []
+    deleted: list[str] = []
+    for operation, response in zip(body.operations, responses):
+        status = response["status"]
+        if not 200 <= status < 300:
+            error = response["body"].get("error", {}) if isinstance(response["body"], dict) else {}
+            results.append({"status": status, "eventId": operation.eventId, "error": error.get("message") or "Request failed"})
+            continue
+        if operation.method == "delete":
+            deleted.append(str(operation.eventId))
+            results.append({"status": status, "eventId": operation.eventId})
+            continue
+        event = client.transform_events([response["body"]], verified_calendar)[0]
+        saved.append(event)
+        results.append({"status": status, "eventId": event["googleEventId"], "event": event})
+
+    if saved:
+        supabase.table("events").upsert(saved, on_conflict="googleCalendarId,googleEventId,source").execute()
+    if deleted:
+        supabase.table("events").update({
+            "status": "cancelled",
+            "syncedAt": datetime.now(timezone.utc).isoformat(),
+        }).eq("googleCalendarId", calendar_id).in_("googleEventId", deleted).execute()
+    if saved or deleted:
+        publish_calendar_changed(current_user["id"], calendar_id, None if deleted else saved)
+    return {"results": results}
+
+
+class BackfillSettings(BaseModel):
+    enabled: bool
+
+
+@router.put("/{calendar_id}/backfill", dependencies=[Depends(request_guard.authorize)])
+async def update_backfill(
+    body: BackfillSettings,
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    verified_calendar: VerifiedCalendar,
+):
+    calendar_id = verified_calendar["id"]
+    state = first_row(
+        supabase.table("calendar_sync_state")
+        .select("backfill_status")
+        .eq("google_calendar_id", calendar_id)
+        .limit(1)
+        .execute()
+        .data
+    )
+    status = state.get("backfill_status") if state else None
+    if not body.enabled:
+        status = BackfillStatus.DISABLED
+    elif status == BackfillStatus.DISABLED:
+        status = BackfillStatus.PAST
+
+    supabase.table("calendar_sync_state").upsert({
+        "google_calendar_id": calendar_id,
+        "backfill_enabled": body.enabled,
+        "backfill_status": status,
+    }, on_conflict="google_calendar_id").execute()
+
+    if body.enabled and status in (BackfillStatus.PAST, BackfillStatus.FUTURE):
+        await asyncio.to_thread(enqueue_sync_job, supabase, calendar_id, current_user["id"], "backfill")
+        sync_scheduler.wake()
+    return {"backfill_enabled": body.enabled, "backfill_status": status}
+
+
 @router.post("/accounts/{google_account_id}/refresh-calendars", dependencies=[Depends(request_guard.authorize)])
 async def refresh_calendars_from_google(
     google_account_id: str,
@@ -285,23 +757,16 @@ async def refresh_calendars_from_google(
     _account: VerifiedAccount,
 ):
     try:
-        client = GoogleAPIClient(supabase, http, current_user["id"], google_account_id)
-        response = await client.fetch_calendars()
-        items = response.get("items", [])
-        if not items:
+        await CalendarListSync(supabase, http, current_user["id"], google_account_id).run(full=True)
+        rows = all_rows(
+            supabase.table("google_calendars")
+            .select("id, google_calendar_id, name, color, is_primary")
+            .eq("google_account_id", google_account_id)
+            .execute()
+            .data
+        )
+        if not rows:
             return {"calendars": []}
-
-        rows = supabase.table("google_calendars").upsert([
-            {
-                "google_account_id": google_account_id,
-                "google_calendar_id": cal["id"],
-                "name": cal.get("summary", ""),
-                "color": cal.get("backgroundColor"),
-                "is_primary": cal.get("primary", False),
-                "access_role": cal.get("accessRole", "reader"),
-            }
-            for cal in items
-        ], on_conflict="google_account_id,google_calendar_id").execute().data
         account = get_google_account(supabase, google_account_id)
         if account is None:
             raise ValueError("Google account not found")
@@ -320,13 +785,71 @@ async def refresh_calendars_from_google(
             for row in rows
         ]
         return {"calendars": calendars}
-    except GoogleAPIError as e:
+    except ProviderError as e:
         handle_google_api_error(e)
     except Exception:
         logger.exception("Failed to refresh calendars for account %s", google_account_id)
         raise HTTPException(status_code=500, detail="An internal error occurred")
 
 
+class CalDAVAccountRequest(BaseModel):
+    server_url: str = Field(..., max_length=500)
+    username: str = Field(..., min_length=1, max_length=254)
+    password: str = Field(..., min_length=1, max_length=500)
+
+
+@router.post("/accounts/caldav", dependencies=[Depends(request_guard.authorize)])
+async def connect_caldav_account(
+    body: CalDAVAccountRequest,
+    current_user: CurrentUser,
+    supabase: SupabaseClientDep,
+    http: HttpClient,
+):
+    server = urlparse(body.server_url)
+    if server.scheme not in ("http", "https") or not server.netloc:
+        raise HTTPException(status_code=400, detail="Invalid server URL")
+
+    try:
+        discovered = await discover_calendar_home(CalDAVProvider.http_client(http), body.server_url, body.username, body.password)
+    except ProviderError as e:
+        if e.status_code in (400, 401):
+            raise HTTPException(status_code=400, detail=e.message)
+        handle_google_api_error(e)
+
+    user_id = current_user["id"]
+    account = first_row(
+        supabase.table("google_accounts")
+        .upsert({
+            "user_id": user_id,
+            "google_id": caldav_account_key(body.server_url, body.username),
+            "email": body.username,
+            "name": discovered["display_name"] or server.netloc,
+            "provider": CalDAVProvider.source,
+        }, on_conflict="user_id,google_id")
+        .execute()
+        .data
+    )
+    if account is None:
+        raise HTTPException(status_code=500, detail="Failed to store CalDAV account")
+    supabase.table(CREDENTIALS_TABLE).upsert({
+        "google_account_id": account["id"],
+        "server_url": body.server_url,
+        "username": body.username,
+        "password": encrypt_secret(body.password),
+        "calendar_home_url": discovered["calendar_home_url"],
+    }, on_conflict="google_account_id").execute()
+
+    try:
+        await CalendarListSync(supabase, http, user_id, str(account["id"])).run(full=True)
+    except ProviderError as e:
+        handle_google_api_error(e)
+    calendars = all_rows(supabase.table("google_calendars").select("id").eq("google_account_id", account["id"]).execute().data)
+    for calendar in calendars:
+        await asyncio.to_thread(enqueue_sync_job, supabase, str(calendar["id"]), user_id, "connect")
+    sync_scheduler.wake()
+    return {"account": {"id": account["id"], "email": account["email"], "name": account["name"], "provider": CalDAVProvider.source}}
+
+
 @router.get("/contacts/directory", dependencies=[Depends(request_guard.authorize)])
 async def contact_directory(
     current_user: CurrentUser,
@@ -372,12 +895,12 @@ async def workspace_search(
     accounts = get_google_accounts_for_user(supabase, user_id)
     if not accounts:
         return {"contacts": []}
-    account_id = accounts[0]["id"]
+    account = accounts[0]
 
     try:
-        client = GoogleAPIClient(supabase, http, user_id, account_id)
+        client = get_provider(supabase, http, user_id, account["id"], provider_source(account))
         people = await client.search_workspace(q)
-    except GoogleAPIError as e:
+    except ProviderError as e:
         handle_google_api_error(e)
         return {"contacts": []}
 
@@ -411,12 +934,12 @@ async def get_group_members(
     accounts = get_google_accounts_for_user(supabase, user_id)
     if not accounts:
         return {"members": []}
-    account_id = accounts[0]["id"]
+    account = accounts[0]
 
     try:
-        client = GoogleAPIClient(supabase, http, user_id, account_id)
+        client = get_provider(supabase, http, user_id, account["id"], provider_source(account))
         raw_members = await client.list_group_members(group_email)
-    except GoogleAPIError as e:
+    except ProviderError as e:
         handle_google_api_error(e)
         return {"members": []}
 
@@ -437,7 +960,7 @@ async def proxy_contact_photo(
         raise HTTPException(status_code=400, detail="Invalid photo URL")
     try:
         content, content_type = await proxy_photo(http, url)
-    except GoogleAPIError as e:
+    except ProviderError as e:
         raise HTTPException(status_code=e.status_code, detail="Photo fetch failed")
     return Response(
         content=content,
@@ -450,58 +973,98 @@ class SyncRequest(BaseModel):
     calendar_ids: list[uuid.UUID] = Field(..., max_length=MAX_CALENDARS_PER_SYNC)
 
 
+def parse_last_event_id(last_event_id: str | None) -> int | None:
+    if last_event_id is None:
+        return None
+    try:
+        value = int(last_event_id)
+    except ValueError:
+        raise HTTPException(status_code=400, detail="Invalid Last-Event-ID")
+    if value < 0:
+        raise HTTPException(status_code=400, detail="Invalid Last-Event-ID")
+    return value
+
+
 @router.post("/sync", dependencies=[Depends(request_guard.authorize)])
 async def sync_calendars(
+    request: Request,
     body: SyncRequest,
     current_user: CurrentUser,
     supabase: SupabaseClientDep,
     http: HttpClient,
+    last_event_id: str | None = Header(None, alias="Last-Event-ID"),
 ):
     user_id = current_user["id"]
-    if user_id in _sync_rate_limits:
-        raise HTTPException(status_code=429, detail="Sync rate limit exceeded. Please wait before syncing again.")
-    _sync_rate_limits[user_id] = True
+    coordination = get_coordination()
+    interrupted_key = f"sync-interrupted:{user_id}"
+    resume_from = parse_last_event_id(last_event_id)
+    is_resume = resume_from is not None and await coordination.exists(interrupted_key)
+    if not is_resume:
+        if not await coordination.claim(f"sync-rate:{user_id}", SYNC_RATE_LIMIT_SECONDS):
+            raise HTTPException(status_code=429, detail="Sync rate limit exceeded. Please wait before syncing again.")
+    await coordination.release(interrupted_key)
 
     calendar_id_list = [str(cid) for cid in body.calendar_ids]
+    session = await start_session(user_id, calendar_id_list, MAX_SYNC_DURATION_SECONDS + INTERRUPTED_SYNC_RESUME_SECONDS)
 
     async def event_generator():
         queue = asyncio.Queue()
         semaphore = asyncio.Semaphore(MAX_CONCURRENT_CALENDAR_FETCHES)
         calendars_done = 0
+        next_event_id = (resume_from or 0) + 1
+        completed = False
+
+        def frame(event: str, data: dict) -> str:
+            nonlocal next_event_id
+            chunk = format_sse(event, data, event_id=next_event_id)
+            next_event_id += 1
+            return chunk
 
         async def run_sync(cid):
             async with semaphore:
                 await Sync(create_supabase_client(), http, user_id, cid, queue).run()
 
-        tasks = [asyncio.create_task(run_sync(cid)) for cid in calendar_id_list]
+        session.tasks = [asyncio.create_task(run_sync(cid)) for cid in calendar_id_list]
+        cancel_watcher = asyncio.create_task(session.watch_cancel())
         try:
-            async with asyncio.timeout(MAX_SYNC_DURATION_SECONDS):
-                while calendars_done < len(calendar_id_list):
-                    try:
-                        item = await asyncio.wait_for(queue.get(), timeout=15)
-                    except asyncio.TimeoutError:
-                        yield ": keep-alive\n\n"
-                        continue
-                    if item["type"] == "events":
-                        yield format_sse("events", item)
-                    elif item["type"] == "calendar_done":
-                        calendars_done += 1
-                    elif item["type"] == "sync_token":
-                        yield format_sse("sync_token", item)
-                    elif item["type"] == "error":
-                        yield format_sse("sync_error", item)
-        except TimeoutError:
-            yield format_sse("sync_error", {"code": "408", "message": "Sync timed out"})
+            yield format_sse("session", {"session_id": session.id})
+            try:
+                async with asyncio.timeout(MAX_SYNC_DURATION_SECONDS):
+                    while calendars_done < len(calendar_id_list):
+                        if await request.is_disconnected():
+                            logger.info("Client disconnected from sync session %s", session.id)
+                            return
+                        try:
+                            item = await asyncio.wait_for(queue.get(), timeout=15)
+                        except asyncio.TimeoutError:
+                            yield ": keep-alive\n\n"
+                            continue
+                        if item["type"] == "events":
+                            yield frame("events", item)
+                        elif item["type"] == "calendar_done":
+                            calendars_done += 1
+                        elif item["type"] == "sync_token":
+                            yield frame("sync_token", item)
+                        elif item["type"] == "error":
+                            yield frame("sync_error", item)
+            except TimeoutError:
+                yield frame("sync_error", {"code": "408", "message": "Sync timed out"})
+            finally:
+                session.stop_tasks()
+
+            last_sync_at = await asyncio.to_thread(get_latest_sync_at, create_supabase_client(), calendar_id_list)
+            completed = True
+            yield frame("complete", {
+                "calendars_synced": calendars_done,
+                "last_sync_at": last_sync_at,
+                "resumed": is_resume,
+                "cancelled": session.cancelled,
+            })
         finally:
-            for task in tasks:
-                if not task.done():
-                    task.cancel()
-
-        last_sync_at = await asyncio.to_thread(get_latest_sync_at, create_supabase_client(), calendar_id_list)
-        yield format_sse("complete", {
-            "calendars_synced": calendars_done,
-            "last_sync_at": last_sync_at,
-        })
+            cancel_watcher.cancel()
+            await asyncio.shield(end_session(session))
+            if not completed and not session.cancelled:
+                await asyncio.shield(coordination.touch(interrupted_key, INTERRUPTED_SYNC_RESUME_SECONDS))
 
     return StreamingResponse(
         event_generator(),
@@ -510,12 +1073,56 @@ async def sync_calendars(
             "Cache-Control": "no-cache",
             "Connection": "keep-alive",
             "X-Accel-Buffering": "no",
+            "X-Sync-Session-Id": session.id,
         },
     )
 
 
+@router.delete("/sync/{session_id}", status_code=204, dependencies=[Depends(request_guard.authorize)])
+async def cancel_sync(
+    session_id: str,
+    current_user: CurrentUser,
+):
+    if not await cancel_session(session_id, current_user["id"]):
+        raise HTTPException(status_code=404, detail="Sync session not found")
+    return Response(status_code=204)
+
+
+def verify_webhook_channel(request: Request, channel: dict) -> None:
+    expected_token = channel.get("webhook_channel_token")
+    actual_token = request.headers.get("X-Goog-Channel-Token")
+    if not actual_token or not expected_token or not hmac.compare_digest(actual_token, expected_token):
+        raise HTTPException(status_code=401, detail="Invalid token")
+    if request.headers.get("X-Goog-Resource-Id") != channel.get("webhook_resource_id"):
+        raise HTTPException(status_code=401, detail="Invalid resource")
+
+
+async def receive_calendar_list_webhook(request: Request, background_tasks: BackgroundTasks, supabase: Client, channel_id: str):
+    result = (
+        supabase
+        .table(ACCOUNT_SYNC_STATE_TABLE)
+        .select("google_account_id, webhook_channel_token, webhook_resource_id, google_accounts!inner(user_id)")
+        .eq("webhook_channel_id", channel_id)
+        .limit(1)
+        .execute()
+    )
+    account_state = result.data[0] if result.data else None
+    if not account_state:
+        return {}
+
+    verify_webhook_channel(request, account_state)
+    if request.headers.get("X-Goog-Resource-State") == "sync":
+        return {}
+
+    account_id = account_state["google_account_id"]
+    if not await get_coordination().claim(f"webhook-debounce:account:{account_id}", WEBHOOK_DEBOUNCE_SECONDS):
+        return {}
+    background_tasks.add_task(run_calendar_list_sync, account_state["google_accounts"]["user_id"], account_id)
+    return {}
+
+
 @router.post("/webhook")
-async def receive_webhook(request: Request):
+async def receive_webhook(request: Request, background_tasks: BackgroundTasks):
     channel_id = request.headers.get("X-Goog-Channel-Id")
     if not channel_id:
         raise HTTPException(status_code=400, detail="Missing channel ID")
@@ -525,7 +1132,7 @@ async def receive_webhook(request: Request):
         supabase
         .table("calendar_sync_state")
         .select(
-            "google_calendar_id, webhook_channel_token,"
+            "google_calendar_id, webhook_channel_token, webhook_resource_id,"
             " google_calendars!inner(google_account_id, google_calendar_id,"
             " google_accounts!inner(user_id))"
         )
@@ -535,31 +1142,68 @@ async def receive_webhook(request: Request):
     )
     sync_state = result.data[0] if result.data else None
     if not sync_state:
-        return {}
-
-    expected_token = sync_state.get("webhook_channel_token")
-    actual_token = request.headers.get("X-Goog-Channel-Token")
-    if not actual_token or not expected_token or not hmac.compare_digest(actual_token, expected_token):
-        raise HTTPException(status_code=401, detail="Invalid token")
+        return await receive_calendar_list_webhook(request, background_tasks, supabase, channel_id)
 
+    verify_webhook_channel(request, sync_state)
     resource_state = request.headers.get("X-Goog-Resource-State")
 
     if resource_state == "sync":
         return {}
 
+    await enqueue_webhook_sync(supabase, sync_state, background_tasks)
+    return {}
+
+
+async def enqueue_webhook_sync(supabase: Client, sync_state: dict, background_tasks: BackgroundTasks) -> None:
     calendar_id = sync_state["google_calendar_id"]
-    if calendar_id in _local_mutation_webhook_suppression:
-        return {}
-    if calendar_id in _webhook_debounce:
-        return {}
-    _webhook_debounce[calendar_id] = True
+    coordination = get_coordination()
+    if await coordination.exists(f"webhook-suppress:{calendar_id}"):
+        return
+    if not await coordination.claim(f"webhook-debounce:{calendar_id}", WEBHOOK_DEBOUNCE_SECONDS):
+        return
 
     user_id = sync_state["google_calendars"]["google_accounts"]["user_id"]
-    http = await get_http_client()
+    if not get_settings().SYNC_SCHEDULER_ENABLED:
+        # Nothing would ever claim a queued job, so the change is synced after this response instead.
+        background_tasks.add_task(run_calendar_sync, user_id, calendar_id)
+        return
+    await asyncio.to_thread(enqueue_sync_job, supabase, calendar_id, user_id, "webhook")
+    sync_scheduler.wake()
 
-    async def _guarded_sync():
-        async with _webhook_sync_semaphore:
-            await Sync(create_supabase_client(), http, user_id, calendar_id).run()
 
-    asyncio.create_task(_guarded_sync())
-    return {}
+@router.post("/webhook/microsoft")
+async def receive_microsoft_webhook(request: Request, background_tasks: BackgroundTasks, validationToken: str | None = Query(default=None)):
+    # Graph validates a new subscription by expecting the token echoed back as plain text.
+    if validationToken is not None:
+        return PlainTextResponse(validationToken)
+
+    try:
+        payload = await request.json()
+    except ValueError:
+        raise HTTPException(status_code=400, detail="Invalid notification payload")
+    notifications = payload.get("value", []) if isinstance(payload, dict) else None
+    if not isinstance(notifications, list) or not all(isinstance(notification, dict) for notification in notifications):
+        raise HTTPException(status_code=400, detail="Invalid notification payload")
+
+    supabase = get_supabase_client()
+    for notification in notifications:
+        subscription_id = notification.get("subscriptionId")
+        if not subscription_id or not isinstance(subscription_id, str):
+            continue
+        sync_state = first_row(
+            supabase
+            .table("calendar_sync_state")
+            .select("google_calendar_id, webhook_channel_token, google_calendars!inner(google_accounts!inner(user_id))")
+            .eq("webhook_resource_id", subscription_id)
+            .limit(1)
+            .execute()
+            .data
+        )
+        if not sync_state:
+            continue
+        expected_token = sync_state.get("webhook_channel_token")
+        actual_token = notification.get("clientState")
+        if not isinstance(actual_token, str) or not actual_token or not expected_token or not hmac.compare_digest(actual_token, expected_token):
+            raise HTTPException(status_code=401, detail="Invalid token")
+        await enqueue_webhook_sync(supabase, sync_state, background_tasks)
+    return Response(status_code=202)
@@ -0,0 +1,45 @@
+import asyncio
+import logging
+
+from fastapi import APIRouter, Request
+from fastapi.responses import StreamingResponse
+
+from app.calendar.helpers import format_sse
+from app.core.dependencies import CurrentUser
+from app.core.live_updates import subscribe, unsubscribe
+
+logger = logging.getLogger(__name__)
+router = APIRouter()
+
+LIVE_KEEPALIVE_SECONDS = 15
+
+
+@router.get("")
+async def live_updates(request: Request, current_user: CurrentUser):
+    user_id = current_user["id"]
+
+    async def event_generator():
+        queue = subscribe(user_id)
+        try:
+            yield format_sse("ready", {})
+            while True:
+                if await request.is_disconnected():
+                    return
+                try:
+                    event, data = await asyncio.wait_for(queue.get(), timeout=LIVE_KEEPALIVE_SECONDS)
+                except asyncio.TimeoutError:
+                    yield ": keep-alive\n\n"
+                    continue
+                yield format_sse(event, data)
+        finally:
+            unsubscribe(user_id, queue)
+
+    return StreamingResponse(
+        event_generator(),
+        media_type="text/event-stream",
+        headers={
+            "Cache-Control": "no-cache",
+            "Connection": "keep-alive",
+            "X-Accel-Buffering": "no",
+        },
+    )
@@ -6,6 +6,7 @@ from slowapi.util import get_remote_address
 
 from app.config import get_settings
 from app.core.dependencies import CurrentUser
+from app.core.live_updates import publish_todos_changed
 from app.core.security import request_guard
 from app.core.supabase import get_supabase_client
 from app.models.todo import Todo, TodoPatch, TodoReorder, TodoList, TodoListPatch, TodoListReorder
@@ -104,6 +105,7 @@ async def create_todo(request: Request, todo: Todo, current_user: CurrentUser):
 
         result = supabase.table("todos").insert(todo_data).execute()
         if result.data:
+            publish_todos_changed(user_id, "todos")
             return to_camel_case(result.data[0])
         raise HTTPException(status_code=500, detail="Failed to create todo")
     raise HTTPException(status_code=400, detail="Invalid list_id")
@@ -141,6 +143,7 @@ async def update_todo(request: Request, todo_id: UUID, todo: TodoPatch, current_
     )
 
     if result.data:
+        publish_todos_changed(user_id, "todos")
         return to_camel_case(result.data[0])
     raise HTTPException(status_code=404, detail="Todo not found")
 
@@ -159,6 +162,7 @@ async def delete_todo(request: Request, todo_id: UUID, current_user: CurrentUser
     )
 
     if result.data:
+        publish_todos_changed(current_user["id"], "todos")
         return {"message": "Todo deleted"}
     raise HTTPException(status_code=404, detail="Todo not found")
 
@@ -196,6 +200,7 @@ async def create_todo_list(request: Request, todo_list: TodoList, current_user:
 
     result = supabase.table("todo_lists").insert(list_data).execute()
     if result.data:
+        publish_todos_changed(user_id, "todo_lists")
         return to_camel_case(result.data[0])
     raise HTTPException(status_code=500, detail="Failed to create list")
 
@@ -216,6 +221,7 @@ async def update_todo_list(request: Request, list_id: UUID, todo_list: TodoListP
     )
 
     if result.data:
+        publish_todos_changed(user_id, "todo_lists")
         return to_camel_case(result.data[0])
     raise HTTPException(status_code=404, detail="List not found")
 
@@ -244,6 +250,7 @@ async def delete_todo_list(request: Request, list_id: UUID, current_user: Curren
             .eq("user_id", user_id)
             .execute()
         )
+        publish_todos_changed(user_id, "todo_lists")
         return {"message": "List deleted"}
     raise HTTPException(status_code=404, detail="List not found")
 
@@ -253,6 +260,7 @@ async def delete_todo_list(request: Request, list_id: UUID, current_user: Curren
 async def reorder_todo_lists(request: Request, reorder_request: TodoListReorder, current_user: CurrentUser):
     supabase = get_supabase_client()
     reorder_items(supabase, "todo_lists", current_user["id"], reorder_request.categoryIds)
+    publish_todos_changed(current_user["id"], "todo_lists")
     return {"message": "Reordered"}
 
 
@@ -261,4 +269,5 @@ async def reorder_todo_lists(request: Request, reorder_request: TodoListReorder,
 async def reorder_todos(request: Request, reorder_request: TodoReorder, current_user: CurrentUser):
     supabase = get_supabase_client()
     reorder_items(supabase, "todos", current_user["id"], reorder_request.todoIds)
+    publish_todos_changed(current_user["id"], "todos")
     return {"message": "Reordered"}
@@ -0,0 +1,96 @@
+"""History backfill tests - 2 tests covering the initial sync window and phase advancement."""
+import asyncio
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.providers import GoogleProvider
+from app.calendar.sync import BackfillStatus, Sync
+
+CALENDAR = {"id": "uuid-work", "google_calendar_id": "work@example.com", "google_account_id": "account-1", "color": None}
+
+
+class RecordingSupabase:
+    def __init__(self, state):
+        self.state = state
+        self.writes: list[dict] = []
+
+    def table(self, name):
+        supabase = self
+
+        class Chain(FakeTableChain):
+            def update(self, data):
+                supabase.writes.append(data)
+                return self
+
+            def upsert(self, data, **kwargs):
+                if name == "calendar_sync_state":
+                    supabase.writes.append(data)
+                return self
+
+        return Chain([self.state] if name == "calendar_sync_state" and self.state else [])
+
+
+class FakeClient:
+    source = "google"
+    transform_events = GoogleProvider.transform_events
+
+    def __init__(self, pages):
+        self.pages = pages
+        self.calls = []
+
+    async def fetch_events(self, calendar_id, page_token=None, sync_token=None, time_min=None, time_max=None):
+        self.calls.append({"page_token": page_token, "time_min": time_min, "time_max": time_max})
+        for page in self.pages:
+            yield page
+
+
+def _sync(supabase, pages):
+    sync = Sync(supabase, None, "user-1", CALENDAR["id"], backfill=True)
+    sync.calendar = CALENDAR
+    sync.contacts = {}
+    sync.provider = FakeClient(pages)
+    return sync
+
+
+def test_initial_window_is_stored_and_reused():
+    """A calendar without a window gets one stored with backfill queued; a stored window is reused as-is."""
+    supabase = RecordingSupabase(None)
+    start, end = _sync(supabase, [])._ensure_sync_window(CALENDAR["id"])
+
+    assert start < end
+    assert supabase.writes[0]["backfill_status"] == BackfillStatus.PAST
+    assert supabase.writes[0]["sync_window_start"] == start.isoformat()
+
+    stored = RecordingSupabase({"sync_window_start": "2025-01-01T00:00:00+00:00", "sync_window_end": "2027-01-01T00:00:00+00:00"})
+    start, end = _sync(stored, [])._ensure_sync_window(CALENDAR["id"])
+
+    assert start.year == 2025 and end.year == 2027
+    assert stored.writes == []
+
+
+def test_backfill_advances_from_past_to_future():
+    """Once the past phase is exhausted the status moves to the future phase and another run is requested."""
+    supabase = RecordingSupabase({
+        "sync_window_start": "2025-01-01T00:00:00+00:00",
+        "sync_window_end": "2027-01-01T00:00:00+00:00",
+        "backfill_enabled": True,
+        "backfill_status": BackfillStatus.PAST,
+        "backfill_page_token": "resume-token",
+        "backfill_events_synced": 10,
+    })
+    sync = _sync(supabase, [{"items": [], "next_page_token": None}])
+    asyncio.run(sync._backfill_history())
+
+    call = sync.provider.calls[0]
+    assert call["page_token"] == "resume-token"
+    assert call["time_max"].year == 2025 and call["time_min"] is None
+    assert supabase.writes[-1] == {
+        "backfill_status": BackfillStatus.FUTURE,
+        "backfill_page_token": None,
+        "backfill_events_synced": 10,
+    }
+    assert sync.backfill_pending
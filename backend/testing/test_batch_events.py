@@ -0,0 +1,68 @@
+"""Batch event tests - 2 tests covering multipart request packing and per-item response parsing."""
+import asyncio
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar.google_client import GoogleAPIClient, parse_batch_response
+from app.models.event import BatchOperation
+
+BATCH_RESPONSE = (
+    "--batch_abc\r\n"
+    "Content-Type: application/http\r\n"
+    "Content-ID: <response-item-1>\r\n"
+    "\r\n"
+    "HTTP/1.1 204 No Content\r\n"
+    "\r\n"
+    "\r\n"
+    "--batch_abc\r\n"
+    "Content-Type: application/http\r\n"
+    "Content-ID: <response-item-0>\r\n"
+    "\r\n"
+    "HTTP/1.1 200 OK\r\n"
+    "Content-Type: application/json\r\n"
+    "\r\n"
+    '{"id": "evt-1", "summary": "Standup"}\r\n'
+    "--batch_abc--\r\n"
+)
+
+
+def test_parse_batch_response_orders_results_by_content_id():
+    """Parts are matched back to operations by Content-ID; missing parts are reported as failures."""
+    results = parse_batch_response("multipart/mixed; boundary=batch_abc", BATCH_RESPONSE, 3)
+
+    assert results[0] == {"status": 200, "body": {"id": "evt-1", "summary": "Standup"}}
+    assert results[1] == {"status": 204, "body": None}
+    assert results[2]["status"] == 500
+
+
+def test_batch_events_packs_operations_into_one_multipart_request():
+    """Each operation becomes its own application/http part with method, path and If-Match, and costs one quota token."""
+    captured = {}
+
+    class FakeResponse:
+        headers = {"Content-Type": "multipart/mixed; boundary=batch_abc"}
+        text = BATCH_RESPONSE
+
+    async def fake_request(method, base_url, endpoint, params=None, json=None, extra_headers=None, content=None, quota_cost=1):
+        captured.update(method=method, content=content, headers=extra_headers, quota_cost=quota_cost)
+        return FakeResponse()
+
+    client = GoogleAPIClient(None, None, "user-1", "account-1")
+    client._request = fake_request
+    operations = [
+        BatchOperation(method="update", eventId="evt-1", etag='"e1"', patch={"colorId": "5"}),
+        BatchOperation(method="delete", eventId="evt 2"),
+    ]
+    results = asyncio.run(client.batch_events("work@example.com", operations))
+
+    content = captured["content"]
+    assert captured["method"] == "POST"
+    assert captured["headers"]["Content-Type"].startswith("multipart/mixed; boundary=")
+    assert "PATCH /calendar/v3/calendars/work%40example.com/events/evt-1" in content
+    assert 'If-Match: "e1"' in content
+    assert "DELETE /calendar/v3/calendars/work%40example.com/events/evt%202" in content
+    assert captured["quota_cost"] == 2
+    assert [r["status"] for r in results] == [200, 204]
@@ -0,0 +1,148 @@
+"""CalDAV tests - 4 tests covering iCalendar conversion, incremental sync-collection reports, server URL checks and connection pinning."""
+import asyncio
+import socket
+import sys
+from pathlib import Path
+
+import pytest
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar.ical import events_to_ical, ical_to_events
+from app.calendar.helpers import ProviderError
+from app.calendar.providers.caldav import CalDAVProvider, PublicAddressBackend, discover_calendar_home
+from app.core.crypto import decrypt_secret, encrypt_secret
+
+SERIES = "\r\n".join([
+    "BEGIN:VCALENDAR",
+    "VERSION:2.0",
+    "BEGIN:VEVENT",
+    "UID:standup@example.com",
+    "DTSTART;TZID=Europe/Berlin:20260302T090000",
+    "DTEND;TZID=Europe/Berlin:20260302T091500",
+    "RRULE:FREQ=WEEKLY;COUNT=4",
+    "SUMMARY:Standup\\, team",
+    'ATTENDEE;CN="Doe, Jane";PARTSTAT=ACCEPTED:mailto:jane@example.com',
+    "END:VEVENT",
+    "BEGIN:VEVENT",
+    "UID:standup@example.com",
+    "RECURRENCE-ID;TZID=Europe/Berlin:20260309T090000",
+    "DTSTART;TZID=Europe/Berlin:20260309T100000",
+    "DURATION:PT30M",
+    "SUMMARY:Standup (moved)",
+    "END:VEVENT",
+    "END:VCALENDAR",
+    "",
+])
+
+SYNC_REPORT = """<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response><d:href>/cal/work/standup.ics</d:href><d:propstat><d:prop><d:getetag>"e2"</d:getetag></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>
+  <d:response><d:href>/cal/work/lunch.ics</d:href><d:status>HTTP/1.1 404 Not Found</d:status></d:response>
+  <d:sync-token>http://example.com/sync/2</d:sync-token>
+</d:multistatus>"""
+
+
+class FakeResponse:
+    def __init__(self, status_code, text, headers=None):
+        self.status_code = status_code
+        self.text = text
+        self.headers = headers or {}
+        self.is_redirect = 300 <= status_code < 400 and "Location" in self.headers
+
+
+class FakeCalDAVServer:
+    def __init__(self):
+        self.requests = []
+
+    async def request(self, method, url, content=None, **kwargs):
+        self.requests.append((method, url, content))
+        if "sync-collection" in content:
+            return FakeResponse(207, SYNC_REPORT)
+        data = SERIES.replace("\r\n", "&#13;\n")
+        return FakeResponse(207, f"""<d:multistatus xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:response><d:href>/cal/work/standup.ics</d:href><d:propstat><d:prop><d:getetag>"e2"</d:getetag><c:calendar-data>{data}</c:calendar-data></d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>
+</d:multistatus>""")
+
+
+def test_ical_series_with_override_converts_to_google_shaped_events():
+    """A master and its overridden instance become linked events that survive a round trip."""
+    events = ical_to_events(SERIES, "standup")
+    master, moved = events
+
+    assert master["summary"] == "Standup, team"
+    assert master["start"] == {"dateTime": "2026-03-02T09:00:00+01:00", "timeZone": "Europe/Berlin"}
+    assert master["recurrence"] == ["RRULE:FREQ=WEEKLY;COUNT=4"]
+    assert master["attendees"] == [{"email": "jane@example.com", "displayName": "Doe, Jane", "responseStatus": "accepted"}]
+    assert moved["id"] == "standup_20260309T080000Z"
+    assert moved["recurringEventId"] == "standup"
+    assert moved["end"]["dateTime"] == "2026-03-09T10:30:00+01:00"
+    assert [event["summary"] for event in ical_to_events(events_to_ical(events), "standup")] == ["Standup, team", "Standup (moved)"]
+
+
+def test_incremental_sync_reports_changed_and_removed_objects():
+    """sync-collection changes are fetched with calendar-multiget and removals come back as cancelled events."""
+    server = FakeCalDAVServer()
+    provider = CalDAVProvider(None, server, "user-1", "account-1")
+    provider._credentials = {"calendar_home_url": "http://example.com/cal/", "username": "jane", "password": "secret"}
+
+    async def collect():
+        return [page async for page in provider.fetch_events("/cal/work/", sync_token="http://example.com/sync/1")]
+
+    pages = asyncio.run(collect())
+
+    assert [method for method, _, _ in server.requests] == ["REPORT", "REPORT"]
+    assert "calendar-multiget" in server.requests[1][2] and "/cal/work/standup.ics" in server.requests[1][2]
+    assert pages[-1]["next_sync_token"] == "http://example.com/sync/2"
+    items = pages[0]["items"]
+    assert items[0] == {"id": "lunch", "status": "cancelled"}
+    assert [(item["id"], item["etag"]) for item in items[1:]] == [("standup", '"e2"'), ("standup_20260309T080000Z", '"e2"')]
+
+
+def test_discovery_refuses_private_hosts_and_cross_host_redirects(monkeypatch):
+    """Server URLs resolving to internal addresses are rejected before any request, and redirects stay on one host."""
+    addresses = {"caldav.example.com": "93.184.216.34", "metadata.example.com": "169.254.169.254"}
+    monkeypatch.setattr(socket, "getaddrinfo", lambda host, port, *args, **kwargs: [(socket.AF_INET, socket.SOCK_STREAM, 6, "", (addresses[host], port))])
+    requests = []
+
+    class RedirectingServer:
+        async def request(self, method, url, **kwargs):
+            requests.append(url)
+            assert kwargs["follow_redirects"] is False
+            return FakeResponse(301, "", {"Location": "https://metadata.example.com/latest/"})
+
+    def discover(url):
+        with pytest.raises(ProviderError) as error:
+            asyncio.run(discover_calendar_home(RedirectingServer(), url, "jane", "secret"))
+        return error.value.message
+
+    assert discover("https://metadata.example.com/") == "CalDAV server address is not allowed"
+    assert discover("http://caldav.example.com/") == "CalDAV servers must use HTTPS"
+    assert requests == []
+    assert discover("https://caldav.example.com/") == "CalDAV server redirected to another host"
+    assert requests == ["https://caldav.example.com/"]
+
+    stored = encrypt_secret("secret")
+    assert stored != "secret" and decrypt_secret(stored) == "secret" and decrypt_secret("legacy") == "legacy"
+
+
+def test_connections_go_to_the_checked_address_even_if_dns_changes(monkeypatch):
+    """Each connection resolves and checks the host itself, so a rebinding answer is refused instead of connected to."""
+    answers = iter(["93.184.216.34", "127.0.0.1"])
+    monkeypatch.setattr(socket, "getaddrinfo", lambda host, port, *args, **kwargs: [(socket.AF_INET, socket.SOCK_STREAM, 6, "", (next(answers), port))])
+    connected = []
+
+    class RecordingBackend:
+        async def connect_tcp(self, host, port, **kwargs):
+            connected.append((host, port))
+            return "stream"
+
+    backend = PublicAddressBackend()
+    backend._backend = RecordingBackend()
+
+    assert asyncio.run(backend.connect_tcp("caldav.example.com", 443)) == "stream"
+    with pytest.raises(ProviderError) as rebound:
+        asyncio.run(backend.connect_tcp("caldav.example.com", 443))
+    assert rebound.value.message == "CalDAV server address is not allowed"
+    assert connected == [("93.184.216.34", 443)]
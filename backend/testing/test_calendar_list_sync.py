@@ -0,0 +1,89 @@
+"""Calendar list sync tests - 2 tests covering incremental deletions and full-listing pruning."""
+import asyncio
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.sync import CalendarListSync
+
+STORED = [
+    {"id": "uuid-work", "google_calendar_id": "work@example.com"},
+    {"id": "uuid-old", "google_calendar_id": "old@example.com"},
+]
+
+
+class RecordingSupabase:
+    def __init__(self, sync_token=None):
+        self.sync_token = sync_token
+        self.deleted: dict[str, list] = {}
+        self.upserts: dict[str, list] = {}
+
+    def table(self, name):
+        supabase = self
+
+        class Chain(FakeTableChain):
+            def delete(self):
+                self._deleting = True
+                return self
+
+            def in_(self, column, values):
+                if getattr(self, "_deleting", False):
+                    supabase.deleted.setdefault(name, []).extend(values)
+                return self
+
+            def upsert(self, data, **kwargs):
+                supabase.upserts.setdefault(name, []).append(data)
+                return self
+
+        if name == "google_calendars":
+            return Chain(STORED)
+        if name == "google_account_sync_state" and self.sync_token:
+            return Chain([{"calendar_list_sync_token": self.sync_token}])
+        return Chain([])
+
+
+class FakeClient:
+    def __init__(self, items):
+        self.items = items
+        self.sync_tokens = []
+
+    async def fetch_calendar_list(self, sync_token=None):
+        self.sync_tokens.append(sync_token)
+        yield {"items": self.items, "next_sync_token": "next-token"}
+
+
+def _run(supabase, items):
+    sync = CalendarListSync(supabase, None, "user-1", "account-1")
+    sync.provider = FakeClient(items)
+    asyncio.run(sync.run())
+    return sync
+
+
+def test_incremental_sync_removes_deleted_calendars():
+    """Entries flagged deleted are removed along with their events, sync state and completions; new entries are upserted."""
+    supabase = RecordingSupabase(sync_token="old-token")
+    sync = _run(supabase, [
+        {"id": "old@example.com", "deleted": True},
+        {"id": "new@example.com", "summary": "New", "accessRole": "owner"},
+    ])
+
+    assert sync.provider.sync_tokens == ["old-token"]
+    assert sync.removed == ["uuid-old"]
+    assert supabase.deleted["events"] == ["uuid-old"]
+    assert supabase.deleted["calendar_sync_state"] == supabase.deleted["completed_events"] == ["uuid-old"]
+    assert supabase.deleted["google_calendars"] == ["uuid-old"]
+    assert supabase.upserts["google_calendars"][0][0]["google_calendar_id"] == "new@example.com"
+    assert {"google_account_id": "account-1", "calendar_list_sync_token": "next-token"} in supabase.upserts["google_account_sync_state"]
+
+
+def test_full_sync_prunes_calendars_missing_from_listing():
+    """Without a sync token the listing is authoritative and unlisted calendars are pruned."""
+    supabase = RecordingSupabase()
+    sync = _run(supabase, [{"id": "work@example.com", "summary": "Work"}])
+
+    assert sync.provider.sync_tokens == [None]
+    assert sync.removed == ["uuid-old"]
+    assert supabase.deleted["google_calendars"] == ["uuid-old"]
@@ -0,0 +1,76 @@
+"""Watch channel tests - 2 tests covering channel stop/clear and webhook resource validation."""
+import asyncio
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar import channels
+from app.calendar.helpers import GoogleAPIError
+from app.routers import calendar as calendar_router
+
+
+def test_stop_calendar_channels_stops_and_clears_each_channel():
+    """Every stored channel is stopped with Google, a 404 is tolerated, and its row is cleared."""
+    stored = [
+        {"google_calendar_id": "cal-1", "webhook_channel_id": "ch-1", "webhook_resource_id": "res-1"},
+        {"google_calendar_id": "cal-2", "webhook_channel_id": "ch-2", "webhook_resource_id": "res-2"},
+    ]
+    updates = []
+
+    class RecordingChain(FakeTableChain):
+        def update(self, data):
+            updates.append(data)
+            return self
+
+    class FakeSupabase:
+        def table(self, name):
+            return RecordingChain(stored)
+
+    class FakeClient:
+        def __init__(self):
+            self.stopped = []
+
+        async def stop_watch_channel(self, channel_id, resource_id):
+            self.stopped.append((channel_id, resource_id))
+            if channel_id == "ch-2":
+                raise GoogleAPIError(404, "Channel not found")
+
+    client = FakeClient()
+    asyncio.run(channels.stop_calendar_channels(client, FakeSupabase(), ["cal-1", "cal-2"]))
+
+    assert client.stopped == [("ch-1", "res-1"), ("ch-2", "res-2")]
+    assert updates == [channels.CLEARED_CHANNEL, channels.CLEARED_CHANNEL]
+
+
+def test_webhook_rejects_mismatched_resource_id(client, monkeypatch):
+    """Notifications must carry the resource id stored for the channel."""
+    sync_state = {
+        "google_calendar_id": "cal-1",
+        "webhook_channel_token": "secret",
+        "webhook_resource_id": "res-1",
+        "google_calendars": {"google_accounts": {"user_id": "user-1"}},
+    }
+    enqueued = []
+
+    class FakeSupabase:
+        def table(self, name):
+            return FakeTableChain([sync_state])
+
+    monkeypatch.setattr(calendar_router, "get_supabase_client", lambda: FakeSupabase())
+    monkeypatch.setattr(calendar_router, "enqueue_sync_job", lambda *args: enqueued.append(args))
+
+    headers = {
+        "X-Goog-Channel-Id": "ch-1",
+        "X-Goog-Channel-Token": "secret",
+        "X-Goog-Resource-State": "exists",
+    }
+    r = client.post("/calendar/webhook", headers={**headers, "X-Goog-Resource-Id": "res-other"})
+    assert r.status_code == 401
+    assert enqueued == []
+
+    r = client.post("/calendar/webhook", headers={**headers, "X-Goog-Resource-Id": "res-1"})
+    assert r.status_code == 200
+    assert len(enqueued) == 1
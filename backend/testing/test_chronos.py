@@ -0,0 +1,141 @@
+"""Chronos calendar tests - 3 tests covering instance exceptions stored in the events table, series moves between local calendars and recoloring."""
+import asyncio
+import sys
+from datetime import datetime, timezone
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar.providers.chronos import ChronosProvider
+from app.calendar.recurrence import expand_events
+from app.models.event import Event, EventPatch
+from app.routers.calendar import ChronosCalendarPatch, update_chronos_calendar
+
+CONFLICT_KEYS = {
+    "events": ("googleCalendarId", "googleEventId", "source"),
+    "google_calendars": ("google_account_id", "google_calendar_id"),
+}
+
+
+class Table:
+    def __init__(self, store, name):
+        self.store, self.name, self.filters, self.rows, self.values = store, name, [], None, None
+
+    def select(self, *args):
+        return self
+
+    def eq(self, column, value):
+        self.filters.append((column, value))
+        return self
+
+    def limit(self, count):
+        return self
+
+    def upsert(self, rows, on_conflict=None):
+        keys = CONFLICT_KEYS[self.name]
+        table = self.store.tables.setdefault(self.name, [])
+        for row in rows if isinstance(rows, list) else [rows]:
+            existing = next((item for item in table if all(item.get(key) == row.get(key) for key in keys)), None)
+            if existing is None:
+                table.append({"id": f"{self.name}-{len(table) + 1}", **row})
+            else:
+                existing.update(row)
+        self.rows = rows
+        return self
+
+    def update(self, values):
+        self.values = values
+        return self
+
+    def execute(self):
+        if self.rows is None:
+            table = self.store.tables.get(self.name, [])
+            self.rows = [row for row in table if all(row.get(column) == value for column, value in self.filters)]
+            for row in self.rows if self.values else []:
+                row.update(self.values)
+        return type("Result", (), {"data": self.rows})()
+
+
+class Store:
+    def __init__(self):
+        self.tables = {
+            "google_calendars": [
+                {"id": "cal-work", "google_account_id": "account-1", "google_calendar_id": "work", "color": "#3b82f6"},
+                {"id": "cal-home", "google_account_id": "account-1", "google_calendar_id": "home", "color": "#22c55e"},
+            ],
+        }
+
+    def table(self, name):
+        return Table(self, name)
+
+    def events(self, calendar_id):
+        return [row for row in self.tables.get("events", []) if row["googleCalendarId"] == calendar_id]
+
+
+def create_standup(provider):
+    return asyncio.run(provider.create_event("work", Event(
+        summary="Standup",
+        start={"dateTime": "2026-03-02T09:00:00+00:00", "timeZone": "UTC"},
+        end={"dateTime": "2026-03-02T09:15:00+00:00", "timeZone": "UTC"},
+        recurrence=["RRULE:FREQ=DAILY;COUNT=3"],
+    )))
+
+
+def test_instance_edits_and_deletes_become_stored_exceptions():
+    """Editing or deleting one occurrence materializes an exception row that expands like a synced series."""
+    store = Store()
+    provider = ChronosProvider(store, None, "user-1", "account-1")
+    master = create_standup(provider)
+
+    moved = asyncio.run(provider.edit_event(
+        "work", f"{master['id']}_20260303T090000Z",
+        EventPatch(start={"dateTime": "2026-03-03T10:00:00+00:00"}, end={"dateTime": "2026-03-03T10:15:00+00:00"}),
+        etag=master["etag"],
+    ))
+    asyncio.run(provider.delete_event("work", f"{master['id']}_20260304T090000Z"))
+
+    rows = store.events("cal-work")
+    masters = [row for row in rows if row["recurrence"]]
+    exceptions = [row for row in rows if row["recurringEventId"]]
+    instances = expand_events([], masters, exceptions, datetime(2026, 3, 1, tzinfo=timezone.utc), datetime(2026, 3, 8, tzinfo=timezone.utc), [])
+
+    assert moved["recurringEventId"] == master["id"] and moved["originalStartTime"]["dateTime"] == "2026-03-03T09:00:00+00:00"
+    assert {row["googleEventId"]: row["status"] for row in exceptions} == {
+        f"{master['id']}_20260303T090000Z": "confirmed",
+        f"{master['id']}_20260304T090000Z": "cancelled",
+    }
+    assert [instance["startAt"] for instance in instances] == ["2026-03-02T09:00:00+00:00", "2026-03-03T10:00:00+00:00"]
+
+
+def test_series_move_carries_exceptions_to_destination_calendar():
+    """Moving a series copies its exceptions in the destination color, and deleting the moved master cancels them."""
+    store = Store()
+    provider = ChronosProvider(store, None, "user-1", "account-1")
+    master = create_standup(provider)
+    asyncio.run(provider.edit_event("work", f"{master['id']}_20260303T090000Z", EventPatch(summary="Retro")))
+
+    moved = asyncio.run(provider.move_event("work", master["id"], "home"))
+    asyncio.run(provider.delete_event("home", master["id"], etag=moved["etag"]))
+
+    assert moved["id"] == master["id"] and moved["etag"] != master["etag"]
+    assert sorted(row["summary"] for row in store.events("cal-home")) == ["Retro", "Standup"]
+    assert {row["status"] for row in store.events("cal-home")} == {"cancelled"}
+    assert {row["colorId"] for row in store.events("cal-home")} == {"#22c55e"}
+
+
+def test_recolor_stamps_the_recolored_events_for_delta_clients():
+    """Events carrying the old calendar color take the new one with fresh updatedAt and syncedAt stamps."""
+    store = Store()
+    provider = ChronosProvider(store, None, "user-1", "account-1")
+    master = create_standup(provider)
+    before = store.events("cal-work")[0]["updatedAt"]
+    calendar = {**store.tables["google_calendars"][0], "provider": ChronosProvider.source}
+
+    asyncio.run(update_chronos_calendar(
+        ChronosCalendarPatch(color="#ef4444"), current_user={"id": "user-1"}, supabase=store, verified_calendar=calendar,
+    ))
+
+    [row] = store.events("cal-work")
+    assert row["googleEventId"] == master["id"] and row["colorId"] == "#ef4444"
+    assert row["updatedAt"] > before and row["syncedAt"] == row["updatedAt"]
@@ -0,0 +1,47 @@
+"""Coordination backend tests - 2 tests covering TTL key claims and semaphore limits."""
+import asyncio
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.core.coordination import MemoryCoordination
+
+
+def test_claim_is_exclusive_until_expiry_or_release():
+    """A live key blocks further claims; release or expiry frees it again."""
+    async def scenario():
+        coordination = MemoryCoordination()
+        assert await coordination.claim("sync-rate:user", 60)
+        assert not await coordination.claim("sync-rate:user", 60)
+        await coordination.release("sync-rate:user")
+        assert await coordination.claim("sync-rate:user", 60)
+
+        await coordination.touch("webhook-suppress:cal", 0.01)
+        assert await coordination.exists("webhook-suppress:cal")
+        await asyncio.sleep(0.02)
+        assert not await coordination.exists("webhook-suppress:cal")
+
+    asyncio.run(scenario())
+
+
+def test_semaphore_caps_concurrent_holders():
+    """No more than `limit` callers hold the same semaphore key at once."""
+    async def scenario():
+        coordination = MemoryCoordination()
+        active = 0
+        peak = 0
+
+        async def worker():
+            nonlocal active, peak
+            async with coordination.semaphore("google-account:acc", 2, 60):
+                active += 1
+                peak = max(peak, active)
+                await asyncio.sleep(0.01)
+                active -= 1
+
+        await asyncio.gather(*(worker() for _ in range(6)))
+        return peak
+
+    assert asyncio.run(scenario()) == 2
@@ -0,0 +1,113 @@
+"""Event conflict tests - 3 tests covering If-Match forwarding, Google's 412 and the 409 returned to clients."""
+import asyncio
+import sys
+from pathlib import Path
+
+import pytest
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.google_client import GoogleAPIClient
+from app.calendar.helpers import GoogleAPIError, ProviderError
+from app.core.coordination import MemoryCoordination
+from app.core.exceptions import EventConflictError
+from app.models.event import EventPatch
+from app.routers import calendar as calendar_router
+from app.routers.calendar import event_delete, event_update
+
+CALENDAR = {"id": "cal-1", "google_calendar_id": "work@example.com", "google_account_id": "account-1", "color": None}
+CURRENT = {"id": "evt-1", "etag": '"e2"', "summary": "Moved by someone else"}
+
+
+def test_edits_and_deletes_forward_if_match():
+    """The client's etag is sent as If-Match, and no precondition is sent without one."""
+    sent = []
+
+    async def fake_request(method, base_url, endpoint, params=None, json=None, extra_headers=None):
+        sent.append((method, extra_headers))
+        return {}
+
+    client = GoogleAPIClient(None, None, "user-1", "account-1")
+    client._request = fake_request
+
+    async def run():
+        await client.edit_event("work@example.com", "evt-1", EventPatch(summary="Standup"), etag='"e1"')
+        await client.edit_event("work@example.com", "evt-1", EventPatch(summary="Standup"))
+        await client.delete_event("work@example.com", "evt-1", etag='"e1"')
+
+    asyncio.run(run())
+    assert sent == [("PATCH", {"If-Match": '"e1"'}), ("PATCH", None), ("DELETE", {"If-Match": '"e1"'})]
+
+
+def test_precondition_failed_is_raised_without_retrying():
+    """A 412 from Google surfaces at once as a GoogleAPIError instead of being retried."""
+    class FakeResponse:
+        status_code = 412
+        headers = {}
+
+    class FakeHttp:
+        calls = 0
+
+        async def request(self, *args, **kwargs):
+            FakeHttp.calls += 1
+            return FakeResponse()
+
+    client = GoogleAPIClient(None, FakeHttp(), "user-1", "account-1")
+
+    async def token():
+        return "token"
+
+    client._get_valid_access_token = token
+    with pytest.raises(GoogleAPIError) as failed:
+        asyncio.run(client.edit_event("work@example.com", "evt-1", EventPatch(summary="Standup"), etag='"e1"'))
+    assert failed.value.status_code == 412 and FakeHttp.calls == 1
+
+
+def test_conflicts_return_409_with_the_current_event(monkeypatch):
+    """Updates and deletes that lose the race answer 409 with the fresh event, which is also written to the cache."""
+    coordination = MemoryCoordination()
+    monkeypatch.setattr(calendar_router, "get_coordination", lambda: coordination)
+
+    class ConflictingProvider:
+        deleted = False
+
+        async def edit_event(self, calendar_id, event_id, event, etag=None):
+            raise ProviderError(412, "Event was modified by another client")
+
+        async def delete_event(self, calendar_id, event_id, etag=None):
+            raise ProviderError(412, "Event was modified by another client")
+
+        async def get_event(self, calendar_id, event_id):
+            if self.deleted:
+                raise ProviderError(404, "Not found")
+            return CURRENT
+
+        def transform_events(self, events, calendar):
+            return [{"googleEventId": event["id"], "etag": event["etag"], "summary": event["summary"]} for event in events]
+
+    provider = ConflictingProvider()
+    monkeypatch.setattr(calendar_router, "provider_for_calendar", lambda *args: provider)
+    upserted = []
+
+    class RecordingChain(FakeTableChain):
+        def upsert(self, data, **kwargs):
+            upserted.extend(data)
+            return super().upsert(data, **kwargs)
+
+    class Supabase:
+        def table(self, name):
+            return RecordingChain()
+
+    common = dict(calendar_id="cal-1", event_id="evt-1", current_user={"id": "user-1"}, supabase=Supabase(), verified_calendar=CALENDAR, http=None)
+    with pytest.raises(EventConflictError) as update_conflict:
+        asyncio.run(event_update(**common, event_body=EventPatch(summary="Standup"), if_match='"e1"'))
+    provider.deleted = True
+    with pytest.raises(EventConflictError) as delete_conflict:
+        asyncio.run(event_delete(**common, if_match='"e1"'))
+
+    assert update_conflict.value.status_code == 409
+    assert update_conflict.value.detail["current"] == {"googleEventId": "evt-1", "etag": '"e2"', "summary": "Moved by someone else"}
+    assert upserted == [update_conflict.value.detail["current"]]
+    assert delete_conflict.value.detail["current"] is None
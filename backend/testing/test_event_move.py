@@ -0,0 +1,127 @@
+"""Event move tests - 3 tests covering same-account moves, cross-account copy-then-delete and series exceptions."""
+import asyncio
+import sys
+from pathlib import Path
+
+import pytest
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.helpers import GoogleAPIError
+from app.calendar.move import move_event
+
+SOURCE = {"id": "uuid-work", "google_calendar_id": "work@example.com", "google_account_id": "account-1", "access_role": "owner"}
+SAME_ACCOUNT = {"id": "uuid-team", "google_calendar_id": "team@example.com", "google_account_id": "account-1", "access_role": "writer"}
+OTHER_ACCOUNT = {"id": "uuid-home", "google_calendar_id": "home@example.com", "google_account_id": "account-2", "access_role": "owner"}
+
+EVENT = {
+    "id": "evt-1",
+    "etag": '"e1"',
+    "iCalUID": "evt-1@google.com",
+    "htmlLink": "https://calendar.google.com/event?eid=1",
+    "summary": "Planning",
+    "start": {"dateTime": "2026-03-02T09:00:00Z"},
+    "end": {"dateTime": "2026-03-02T10:00:00Z"},
+    "recurrence": ["RRULE:FREQ=WEEKLY"],
+    "attendees": [{"email": "a@example.com"}],
+    "reminders": {"useDefault": False, "overrides": [{"method": "popup", "minutes": 5}]},
+    "conferenceData": {"conferenceId": "abc-defg-hij"},
+}
+
+
+class Supabase:
+    def __init__(self, exceptions=None):
+        self.exceptions = exceptions or []
+
+    def table(self, name):
+        return FakeTableChain(self.exceptions if name == "events" else [])
+
+
+class FakeClient:
+    source = "google"
+    supports_move = True
+
+    def __init__(self, fail_delete=False):
+        self.calls = []
+        self.fail_delete = fail_delete
+
+    async def get_event(self, calendar_id, event_id):
+        return dict(EVENT)
+
+    async def move_event(self, calendar_id, event_id, destination):
+        self.calls.append(("move", calendar_id, event_id, destination))
+        return {**EVENT, "organizer": {"email": destination}}
+
+    async def insert_event(self, calendar_id, body):
+        self.calls.append(("insert", calendar_id, body))
+        return {**body, "id": "evt-copy"}
+
+    async def edit_event(self, calendar_id, event_id, patch, etag=None):
+        self.calls.append(("edit", calendar_id, event_id, patch.model_dump(exclude_none=True)))
+        return {"id": event_id, **patch.model_dump(exclude_none=True)}
+
+    async def delete_event(self, calendar_id, event_id, etag=None):
+        self.calls.append(("delete", calendar_id, event_id))
+        if self.fail_delete:
+            raise GoogleAPIError(500, "Google server error")
+
+
+def test_same_account_move_uses_events_move():
+    """Calendars on one account are moved in place, unless the provider has no move of its own and copies instead."""
+    client = FakeClient()
+    moved = asyncio.run(move_event(client, client, Supabase(), SOURCE, SAME_ACCOUNT, "evt-1"))
+
+    assert client.calls == [("move", "work@example.com", "evt-1", "team@example.com")]
+    assert moved["id"] == "evt-1"
+
+    client.supports_move = False
+    client.calls.clear()
+    moved = asyncio.run(move_event(client, client, Supabase(), SOURCE, SAME_ACCOUNT, "evt-1"))
+    assert [call[0] for call in client.calls] == ["insert", "delete"]
+    assert moved["id"] == "evt-copy"
+
+
+def test_cross_account_move_copies_then_deletes_and_rolls_back_on_failure():
+    """The copy keeps attendees, reminders, conference data and recurrence; a failed delete removes the copy."""
+    source, destination = FakeClient(), FakeClient()
+    moved = asyncio.run(move_event(source, destination, Supabase(), SOURCE, OTHER_ACCOUNT, "evt-1"))
+
+    _, calendar_id, body = destination.calls[0]
+    assert calendar_id == "home@example.com"
+    assert "id" not in body and "iCalUID" not in body and "etag" not in body
+    for field in ("attendees", "reminders", "conferenceData", "recurrence"):
+        assert body[field] == EVENT[field]
+    assert source.calls == [("delete", "work@example.com", "evt-1")]
+    assert moved["id"] == "evt-copy"
+
+    failing, destination = FakeClient(fail_delete=True), FakeClient()
+    with pytest.raises(GoogleAPIError):
+        asyncio.run(move_event(failing, destination, Supabase(), SOURCE, OTHER_ACCOUNT, "evt-1"))
+    assert destination.calls[-1] == ("delete", "home@example.com", "evt-copy")
+
+
+def test_cross_account_series_move_carries_exceptions():
+    """Cancelled occurrences become EXDATEs on the copy and modified ones are replayed onto the copy's instances."""
+    exceptions = Supabase([
+        {"googleEventId": "evt-1_20260309T090000Z", "status": "cancelled", "originalStartTime": {"dateTime": "2026-03-09T09:00:00Z"}},
+        {
+            "googleEventId": "evt-1_20260316T090000Z",
+            "status": "confirmed",
+            "summary": "Planning (moved)",
+            "colorId": "#039be5",
+            "originalStartTime": {"dateTime": "2026-03-16T09:00:00Z"},
+            "start": {"dateTime": "2026-03-16T11:00:00Z"},
+            "end": {"dateTime": "2026-03-16T12:00:00Z"},
+        },
+    ])
+    source, destination = FakeClient(), FakeClient()
+    asyncio.run(move_event(source, destination, exceptions, SOURCE, OTHER_ACCOUNT, "evt-1"))
+
+    assert destination.calls[0][2]["recurrence"] == ["RRULE:FREQ=WEEKLY", "EXDATE:20260309T090000Z"]
+    _, calendar_id, instance_id, patch = destination.calls[1]
+    assert (calendar_id, instance_id) == ("home@example.com", "evt-copy_20260316T090000Z")
+    assert patch["summary"] == "Planning (moved)" and patch["start"] == {"dateTime": "2026-03-16T11:00:00Z"}
+    assert "colorId" not in patch
+    assert source.calls == [("delete", "work@example.com", "evt-1")]
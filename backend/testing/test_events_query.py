@@ -0,0 +1,111 @@
+"""Events query tests - 2 tests covering cursor pagination and updatedSince deltas."""
+import asyncio
+import sys
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.routers.calendar import DELTA_SYNC_OVERLAP_SECONDS, list_events, query_events
+
+SYNCED = "2026-03-01T12:00:00+00:00"
+
+
+def row(row_id, synced_at=SYNCED, **fields):
+    return {
+        "id": row_id, "googleCalendarId": "cal-1", "source": "google", "recurrence": None,
+        "recurringEventId": None, "status": "confirmed", "syncedAt": synced_at, **fields,
+    }
+
+
+class FilteringChain(FakeTableChain):
+    """Applies the handful of PostgREST filters query_events uses to an in-memory table."""
+
+    def __init__(self, data):
+        super().__init__(list(data))
+        self._negate = False
+
+    def _keep(self, predicate):
+        negate, self._negate = self._negate, False
+        self.data = [item for item in self.data if predicate(item) != negate]
+        return self
+
+    @property
+    def not_(self):
+        self._negate = True
+        return self
+
+    def in_(self, column, values):
+        return self._keep(lambda item: item.get(column) in values)
+
+    def is_(self, column, value):
+        return self._keep(lambda item: item.get(column) is None)
+
+    def neq(self, column, value):
+        return self._keep(lambda item: item.get(column) != value)
+
+    def gt(self, column, value):
+        return self._keep(lambda item: str(item[column]) > value)
+
+    def gte(self, column, value):
+        return self._keep(lambda item: datetime.fromisoformat(item[column]) >= datetime.fromisoformat(value))
+
+    def order(self, column, **kwargs):
+        self.data.sort(key=lambda item: str(item[column]))
+        return self
+
+    def limit(self, count):
+        self.data = self.data[:count]
+        return self
+
+
+class Supabase:
+    def __init__(self, tables):
+        self.tables = tables
+
+    def table(self, name):
+        return FilteringChain(self.tables.get(name, []))
+
+
+def test_cursor_pagination_walks_every_row_once():
+    """Pages interleave singles, masters and exceptions by id, skip cancelled rows and end without an empty page."""
+    rows = [
+        row("01"), row("02", recurrence=["RRULE:FREQ=DAILY"]), row("03", recurringEventId="g-02"),
+        row("04"), row("05"), row("06", recurringEventId="g-02"), row("07", status="cancelled"),
+    ]
+    supabase = Supabase({"events": rows})
+
+    pages, cursor = [], None
+    while True:
+        events, masters, exceptions, cursor = query_events(supabase, ["cal-1"], cursor=cursor, limit=2)
+        pages.append(sorted(item["id"] for item in [*events, *masters, *exceptions]))
+        if cursor is None:
+            break
+
+    assert pages == [["01", "02"], ["03", "04"], ["05", "06"]]
+
+
+def test_delta_returns_late_rows_and_cancellations_inside_the_overlap():
+    """updatedSince includes cancelled rows, and serverTime trails the clock so rows committed mid-request are refetched."""
+    since = datetime(2026, 3, 1, 12, tzinfo=timezone.utc)
+    supabase = Supabase({
+        "google_calendars": [{"id": "cal-1"}],
+        "events": [
+            row("01", "2026-03-01T11:59:59+00:00"),
+            row("02", status="cancelled"),
+            row("03", "2026-03-01T12:00:01+00:00", recurringEventId="g-09", status="cancelled"),
+        ],
+    })
+
+    before = datetime.now(timezone.utc)
+    response = asyncio.run(list_events(
+        current_user={"id": "user-1"}, supabase=supabase, calendar_ids=None, time_min=None, time_max=None,
+        updated_since=since, cursor=None, limit=100,
+    ))
+
+    assert [item["id"] for item in response["events"]] == ["02"]
+    assert [item["id"] for item in response["exceptions"]] == ["03"]
+    server_time = datetime.fromisoformat(response["serverTime"])
+    assert server_time <= before - timedelta(seconds=DELTA_SYNC_OVERLAP_SECONDS) + timedelta(seconds=1)
@@ -0,0 +1,75 @@
+"""iCalendar export tests - 2 tests covering series serialization from events rows and VTIMEZONE generation."""
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar.export import export_ical
+from app.calendar.helpers import transform_events
+from app.calendar.ical import ical_to_events, parse_ical
+
+BERLIN = "Europe/Berlin"
+
+
+def rows(calendar_id, events):
+    return [{"id": f"{calendar_id}-{index}", **row} for index, row in enumerate(transform_events(events, calendar_id, "account-1"))]
+
+
+MASTER = {
+    "id": "standup",
+    "iCalUID": "standup@example.com",
+    "summary": "Standup",
+    "start": {"dateTime": "2026-03-02T09:00:00+01:00", "timeZone": BERLIN},
+    "end": {"dateTime": "2026-03-02T09:15:00+01:00", "timeZone": BERLIN},
+    "recurrence": ["RRULE:FREQ=DAILY;COUNT=5"],
+    "organizer": {"email": "lead@example.com", "displayName": "Lead"},
+    "attendees": [{"email": "dev@example.com", "responseStatus": "accepted"}],
+    "reminders": {"useDefault": False, "overrides": [{"method": "popup", "minutes": 10}]},
+}
+
+
+def test_export_folds_cancelled_occurrences_and_keeps_overrides():
+    """Cancelled exceptions become EXDATEs on the master while moved ones stay RECURRENCE-ID overrides."""
+    events = rows("cal-work", [
+        MASTER,
+        {"id": "standup_20260303T080000Z", "recurringEventId": "standup", "status": "cancelled", "originalStartTime": {"dateTime": "2026-03-03T09:00:00+01:00", "timeZone": BERLIN}},
+        {
+            "id": "standup_20260304T080000Z",
+            "iCalUID": "standup@example.com",
+            "recurringEventId": "standup",
+            "summary": "Standup (late)",
+            "originalStartTime": {"dateTime": "2026-03-04T09:00:00+01:00", "timeZone": BERLIN},
+            "start": {"dateTime": "2026-03-04T11:00:00+01:00", "timeZone": BERLIN},
+            "end": {"dateTime": "2026-03-04T11:15:00+01:00", "timeZone": BERLIN},
+        },
+    ])
+
+    text = export_ical(events, "Work")
+    master, moved = ical_to_events(text, "standup")
+
+    assert text.count("BEGIN:VEVENT") == 2 and "X-WR-CALNAME:Work" in text
+    assert master["recurrence"] == ["RRULE:FREQ=DAILY;COUNT=5", "EXDATE;TZID=Europe/Berlin:20260303T090000"]
+    assert master["organizer"]["email"] == "lead@example.com" and master["attendees"][0]["responseStatus"] == "accepted"
+    assert master["reminders"]["overrides"] == [{"method": "popup", "minutes": 10}]
+    assert moved["summary"] == "Standup (late)" and moved["originalStartTime"]["timeZone"] == BERLIN
+
+
+def test_export_emits_one_vtimezone_per_referenced_zone():
+    """Each referenced zone gets a VTIMEZONE with its DST observances, and invitations shared by two calendars appear once."""
+    tokyo = {
+        "id": "sync",
+        "summary": "Sync",
+        "start": {"dateTime": "2026-06-01T10:00:00+09:00", "timeZone": "Asia/Tokyo"},
+        "end": {"dateTime": "2026-06-01T11:00:00+09:00", "timeZone": "Asia/Tokyo"},
+    }
+    calendar = parse_ical(export_ical([*rows("cal-work", [MASTER, tokyo]), *rows("cal-team", [MASTER])])).components[0]
+
+    zones = {zone.value("TZID"): zone for zone in calendar.components if zone.name == "VTIMEZONE"}
+    assert sorted(zones) == ["Asia/Tokyo", BERLIN]
+    assert [component.name for component in calendar.components].index("VEVENT") == 2
+    assert sum(component.name == "VEVENT" for component in calendar.components) == 2
+    daylight = next(observance for observance in zones[BERLIN].components if observance.value("DTSTART") == "20260329T020000")
+    assert daylight.name == "DAYLIGHT"
+    assert (daylight.value("TZOFFSETFROM"), daylight.value("TZOFFSETTO")) == ("+0100", "+0200")
+    assert [observance.value("TZOFFSETTO") for observance in zones["Asia/Tokyo"].components] == ["+0900"]
@@ -0,0 +1,102 @@
+"""Fake Google tests - 2 tests covering paged sync with token expiry and token refresh with injected faults."""
+import asyncio
+import sys
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+import pytest
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.google_client import APIBaseURL, GoogleAPIClient
+from app.calendar.helpers import GoogleAPIError
+from app.calendar.retry import RetryPolicy
+from app.config import get_settings
+from app.fake_google import FakeGoogle
+
+
+class TokenStore:
+    def __init__(self, access_token, refresh_token):
+        self.row = {
+            "access_token": access_token,
+            "refresh_token": refresh_token,
+            "expires_at": (datetime.now(timezone.utc) + timedelta(hours=1)).isoformat(),
+        }
+
+    def table(self, name):
+        store = self
+
+        class Chain(FakeTableChain):
+            def update(self, data):
+                store.row.update(data)
+                return self
+
+        return Chain(dict(self.row))
+
+
+@pytest.fixture
+def google(monkeypatch):
+    settings = get_settings()
+    monkeypatch.setattr(settings, "GOOGLE_CALENDAR_API_URL", "http://fake-google/calendar/v3")
+    monkeypatch.setattr(settings, "GOOGLE_PEOPLE_API_URL", "http://fake-google/people/v1")
+    monkeypatch.setattr(settings, "GOOGLE_OAUTH_URL", "http://fake-google/oauth")
+    return FakeGoogle(page_size=2)
+
+
+def _client(google, http):
+    store = TokenStore(*google.issue_tokens())
+    return GoogleAPIClient(store, http, "user-1", "account-1", retry_policy=RetryPolicy(base_delay=0)), store
+
+
+async def _collect(client, **kwargs):
+    pages = []
+    async for page in client.fetch_events("work@example.com", **kwargs):
+        pages.append(page)
+    return pages
+
+
+def test_paged_sync_then_incremental_then_expired_token(google):
+    """Full listings page through every event, sync tokens return only changes, and expired tokens raise 410."""
+    google.add_calendar("work@example.com", "Work")
+    for i in range(3):
+        google.add_event("work@example.com", {"id": f"evt-{i}", "summary": f"Event {i}", "start": {"dateTime": "2026-03-02T09:00:00Z"}, "end": {"dateTime": "2026-03-02T10:00:00Z"}})
+
+    async def scenario():
+        async with google.http_client() as http:
+            client, _ = _client(google, http)
+            full = await _collect(client)
+            google.update_event("work@example.com", "evt-1", summary="Renamed")
+            incremental = await _collect(client, sync_token=full[-1]["next_sync_token"])
+            google.expire_sync_tokens()
+            with pytest.raises(GoogleAPIError) as expired:
+                await _collect(client, sync_token=incremental[-1]["next_sync_token"])
+            return full, incremental, expired.value
+
+    full, incremental, expired = asyncio.run(scenario())
+
+    assert [len(page["items"]) for page in full] == [2, 1]
+    assert full[0]["next_page_token"] and full[-1]["next_sync_token"]
+    assert [item["summary"] for item in incremental[0]["items"]] == ["Renamed"]
+    assert expired.status_code == 410
+
+
+def test_access_token_refresh_and_injected_server_errors(google):
+    """A rejected access token is refreshed through the fake OAuth endpoint and injected 5xx responses are retried."""
+    google.add_calendar("work@example.com", "Work", primary=True)
+
+    async def scenario():
+        async with google.http_client() as http:
+            client, store = _client(google, http)
+            old_token = store.row["access_token"]
+            google.expire_access_tokens()
+            google.inject(503, path="/calendarList")
+            response = await client._request("GET", APIBaseURL.CALENDAR, "/users/me/calendarList")
+            return response, old_token, store.row["access_token"]
+
+    response, old_token, new_token = asyncio.run(scenario())
+
+    assert [item["id"] for item in response["items"]] == ["work@example.com"]
+    assert new_token != old_token
+    assert ("POST", "/oauth/token") in google.requests
@@ -0,0 +1,94 @@
+"""Live update tests - 3 tests covering per-user fan-out, truncation, queue overflow and the cross-worker relay."""
+import sys
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.core import live_updates
+from app.core.live_updates import (
+    MAX_PUSHED_EVENTS,
+    PostgresRelay,
+    publish_calendar_changed,
+    subscribe,
+    unsubscribe,
+)
+
+
+def test_publish_reaches_only_the_users_subscribers():
+    """Each subscriber of the user gets the change, other users get nothing, large batches drop their rows."""
+    first = subscribe("user-a")
+    second = subscribe("user-a")
+    other = subscribe("user-b")
+    try:
+        publish_calendar_changed("user-a", "cal-1", [{"googleEventId": "e1"}])
+        publish_calendar_changed("user-a", "cal-1", [{"googleEventId": str(i)} for i in range(MAX_PUSHED_EVENTS + 1)])
+
+        for queue in (first, second):
+            assert queue.get_nowait() == ("calendar_changed", {"calendar_id": "cal-1", "events": [{"googleEventId": "e1"}]})
+            assert queue.get_nowait() == ("calendar_changed", {"calendar_id": "cal-1", "events": None})
+        assert other.empty()
+    finally:
+        unsubscribe("user-a", first)
+        unsubscribe("user-a", second)
+        unsubscribe("user-b", other)
+    assert "user-a" not in live_updates._subscribers
+
+
+def test_full_queue_is_replaced_with_resync():
+    """A subscriber that falls behind is told to resync instead of blocking publishers."""
+    queue = subscribe("user-slow")
+    try:
+        for _ in range(live_updates.LIVE_QUEUE_SIZE + 1):
+            publish_calendar_changed("user-slow", "cal-1", None)
+        assert queue.get_nowait() == ("resync", {})
+        assert queue.empty()
+    finally:
+        unsubscribe("user-slow", queue)
+
+
+class MessagesTable(FakeTableChain):
+    """An in-memory live_update_messages table that assigns ids and honours the relay's created_at filter."""
+
+    def __init__(self, rows):
+        super().__init__()
+        self.rows = rows
+        self.since = None
+
+    def insert(self, values):
+        self.rows.append({"id": len(self.rows) + 1, "created_at": datetime.now(timezone.utc).isoformat(), **values})
+        return self
+
+    def gte(self, column, value):
+        self.since = value
+        return self
+
+    def execute(self):
+        self.data = [row for row in self.rows if self.since is None or row["created_at"] >= self.since]
+        return self
+
+
+class Supabase:
+    def __init__(self):
+        self.rows = []
+
+    def table(self, name):
+        return MessagesTable(self.rows)
+
+
+def test_relay_delivers_other_workers_updates_once():
+    """Messages from other workers reach local subscribers once, late commits in the overlap are still picked up."""
+    supabase = Supabase()
+    first, second = PostgresRelay(supabase), PostgresRelay(supabase)
+
+    first.send("user-a", "todos_changed", {"resource": "lists"})
+    second.send("user-a", "todos_changed", {"resource": "todos"})
+    assert [message["data"] for message in second.poll()] == [{"resource": "lists"}]
+    assert second.poll() == []
+
+    late = (datetime.now(timezone.utc) - timedelta(seconds=5)).isoformat()
+    supabase.rows.append({"id": 3, "origin": first.origin, "user_id": "user-a", "event": "calendars_changed", "data": {"removed": []}, "created_at": late})
+    assert [message["id"] for message in second.poll()] == [3]
+    assert [message["id"] for message in first.poll()] == [2]
@@ -0,0 +1,156 @@
+"""Microsoft Graph tests - 4 tests covering recurrence pattern conversion, delta sync and cancelled occurrences against the fake Graph server, and malformed webhook payloads."""
+import asyncio
+import sys
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+import pytest
+from fastapi import HTTPException
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.providers.microsoft import MicrosoftProvider, graph_event_to_event, graph_recurrence_to_rrule, rrule_to_graph_recurrence
+from app.calendar.retry import RetryPolicy
+from app.config import get_settings
+from app.fake_graph import FakeGraph
+from app.routers import calendar as calendar_router
+
+START = {"dateTime": "2026-03-02T09:00:00+01:00", "timeZone": "Europe/Berlin"}
+STANDUP = {
+    "id": "standup",
+    "type": "seriesMaster",
+    "subject": "Standup",
+    "originalStartTimeZone": "W. Europe Standard Time",
+    "start": {"dateTime": "2026-03-02T08:00:00.0000000", "timeZone": "UTC"},
+    "end": {"dateTime": "2026-03-02T08:15:00.0000000", "timeZone": "UTC"},
+    "recurrence": {"pattern": {"type": "daily", "interval": 1}, "range": {"type": "numbered", "startDate": "2026-03-02", "numberOfOccurrences": 5}},
+}
+
+
+class TokenStore:
+    def __init__(self, access_token, refresh_token, tables=None):
+        self.tables = tables or {}
+        self.row = {
+            "access_token": access_token,
+            "refresh_token": refresh_token,
+            "expires_at": (datetime.now(timezone.utc) + timedelta(hours=1)).isoformat(),
+        }
+
+    def table(self, name):
+        store = self
+        if name != "google_account_tokens":
+            return FakeTableChain(self.tables.get(name, []))
+
+        class Chain(FakeTableChain):
+            def update(self, data):
+                store.row.update(data)
+                return self
+
+        return Chain(dict(self.row))
+
+
+@pytest.fixture
+def graph(monkeypatch):
+    settings = get_settings()
+    monkeypatch.setattr(settings, "MICROSOFT_GRAPH_API_URL", "http://fake-graph/v1.0")
+    monkeypatch.setattr(settings, "MICROSOFT_OAUTH_URL", "http://fake-graph/oauth")
+    return FakeGraph(page_size=2)
+
+
+def test_recurrence_patterns_convert_to_rrule_and_back():
+    """Weekly, relative monthly and end-dated Graph patterns map to RRULEs that convert back to the same pattern."""
+    weekly = {
+        "pattern": {"type": "weekly", "interval": 2, "daysOfWeek": ["monday", "wednesday"], "firstDayOfWeek": "monday"},
+        "range": {"type": "numbered", "startDate": "2026-03-02", "numberOfOccurrences": 6},
+    }
+    monthly = {
+        "pattern": {"type": "relativeMonthly", "interval": 1, "daysOfWeek": ["friday"], "index": "last"},
+        "range": {"type": "endDate", "startDate": "2026-03-02", "endDate": "2026-06-30"},
+    }
+
+    assert graph_recurrence_to_rrule(weekly, START) == ["RRULE:FREQ=WEEKLY;BYDAY=MO,WE;WKST=MO;INTERVAL=2;COUNT=6"]
+    assert graph_recurrence_to_rrule(monthly, START) == ["RRULE:FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20260630T215959Z"]
+    assert rrule_to_graph_recurrence(graph_recurrence_to_rrule(weekly, START), START) == {**weekly, "range": {**weekly["range"], "recurrenceTimeZone": "Europe/Berlin"}}
+    assert rrule_to_graph_recurrence(graph_recurrence_to_rrule(monthly, START), START)["range"]["endDate"] == "2026-06-30"
+
+
+def test_delta_sync_pages_series_masters_and_removals(graph):
+    """A full delta sync resolves series masters from occurrences, and the delta link later reports removals as cancelled."""
+    graph.add_calendar("work", "Work", default=True)
+    graph.add_event("work", {
+        "id": "lunch",
+        "subject": "Lunch",
+        "start": {"dateTime": "2026-03-02T11:00:00.0000000", "timeZone": "UTC"},
+        "end": {"dateTime": "2026-03-02T12:00:00.0000000", "timeZone": "UTC"},
+    })
+    graph.add_event("work", STANDUP)
+    graph.add_event("work", {"id": "retro", "subject": "Retro", "start": {"dateTime": "2026-03-03T15:00:00", "timeZone": "UTC"}, "end": {"dateTime": "2026-03-03T16:00:00", "timeZone": "UTC"}})
+
+    async def run():
+        async with graph.http_client() as http:
+            provider = MicrosoftProvider(TokenStore(*graph.issue_tokens()), http, "user-1", "account-1", retry_policy=RetryPolicy(base_delay=0))
+            full = [page async for page in provider.fetch_events("work")]
+            graph.delete_event("lunch")
+            incremental = [page async for page in provider.fetch_events("work", sync_token=full[-1]["next_sync_token"])]
+            return full, incremental
+
+    full, incremental = asyncio.run(run())
+
+    assert len(full) == 2 and full[0]["next_page_token"] and full[0]["next_sync_token"] is None
+    items = [item for page in full for item in page["items"]]
+    assert [item["id"] for item in items] == ["lunch", "retro", "standup"]
+    assert items[2]["start"] == {"dateTime": "2026-03-02T09:00:00+01:00", "timeZone": "Europe/Berlin"}
+    assert items[2]["recurrence"] == ["RRULE:FREQ=DAILY;COUNT=5"]
+    assert incremental[0]["items"] == [{"id": "lunch", "status": "cancelled"}]
+
+
+def test_deleted_and_cancelled_occurrences_reach_their_series(graph):
+    """A deleted occurrence becomes an EXDATE on its re-read master, and a cancelled one a cancelled exception keyed by its start."""
+    graph.add_calendar("work", "Work", default=True)
+    graph.add_event("work", STANDUP)
+    store = TokenStore(*graph.issue_tokens(), tables={"google_calendars": [{"id": "cal-1"}], "events": [{"googleEventId": "standup"}]})
+
+    async def run():
+        async with graph.http_client() as http:
+            provider = MicrosoftProvider(store, http, "user-1", "account-1", retry_policy=RetryPolicy(base_delay=0))
+            full = [page async for page in provider.fetch_events("work")]
+            graph.cancel_occurrence("standup", "2026-03-04")
+            return [page async for page in provider.fetch_events("work", sync_token=full[-1]["next_sync_token"])]
+
+    items = [item for page in asyncio.run(run()) for item in page["items"]]
+    assert items[0]["id"] == "standup"
+    assert items[0]["recurrence"] == ["RRULE:FREQ=DAILY;COUNT=5", "EXDATE:20260304T080000Z"]
+
+    cancelled = graph_event_to_event({
+        "id": "AAMkOccurrence=",
+        "type": "occurrence",
+        "seriesMasterId": "standup",
+        "isCancelled": True,
+        "originalStart": "2026-03-05T08:00:00Z",
+        "start": {"dateTime": "2026-03-05T08:00:00.0000000", "timeZone": "UTC"},
+        "end": {"dateTime": "2026-03-05T08:15:00.0000000", "timeZone": "UTC"},
+    })
+    assert (cancelled["id"], cancelled["status"], cancelled["recurringEventId"]) == ("standup_20260305T080000Z", "cancelled", "standup")
+
+
+def test_malformed_webhook_payloads_are_rejected_with_400(monkeypatch):
+    """Bodies that are not an object, or whose value is not a list of objects, are a client error rather than a crash."""
+    monkeypatch.setattr(calendar_router, "get_supabase_client", lambda: TokenStore("access", "refresh"))
+
+    class FakeRequest:
+        def __init__(self, body):
+            self.body = body
+
+        async def json(self):
+            return self.body
+
+    def status(body):
+        with pytest.raises(HTTPException) as rejected:
+            asyncio.run(calendar_router.receive_microsoft_webhook(FakeRequest(body), background_tasks=None, validationToken=None))
+        return rejected.value.status_code
+
+    assert [status(body) for body in ([], "value", {"value": {}}, {"value": ["sub-1"]})] == [400, 400, 400, 400]
+    response = asyncio.run(calendar_router.receive_microsoft_webhook(FakeRequest({"value": [{"subscriptionId": 7}]}), background_tasks=None, validationToken=None))
+    assert response.status_code == 202
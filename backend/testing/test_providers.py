@@ -0,0 +1,86 @@
+"""Calendar provider tests - 2 tests covering provider resolution and syncing through a registered provider."""
+import asyncio
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.helpers import ProviderError
+from app.calendar.providers import PROVIDERS, CalendarProvider, GoogleProvider, get_provider, provider_for_calendar, register_provider
+from app.calendar.sync import Sync
+
+CALENDAR = {"id": "uuid-work", "google_calendar_id": "work", "google_account_id": "account-1", "color": "7"}
+
+
+class ExampleProvider(CalendarProvider):
+    source = "example"
+
+    async def fetch_calendar_list(self, sync_token=None):
+        yield {"items": [], "next_sync_token": None}
+
+    async def fetch_events(self, calendar_id, page_token=None, sync_token=None, time_min=None, time_max=None):
+        yield {
+            "items": [{"id": "evt-1", "summary": "Standup", "start": {"dateTime": "2026-03-02T09:00:00Z"}, "end": {"dateTime": "2026-03-02T09:15:00Z"}}],
+            "next_page_token": None,
+            "next_sync_token": "example-token",
+        }
+
+    async def get_event(self, calendar_id, event_id):
+        return {}
+
+    async def create_event(self, calendar_id, event):
+        return {}
+
+    async def edit_event(self, calendar_id, event_id, event, etag=None):
+        return {}
+
+    async def delete_event(self, calendar_id, event_id, etag=None):
+        return None
+
+
+class RecordingSupabase:
+    def __init__(self):
+        self.events: list[dict] = []
+
+    def table(self, name):
+        supabase = self
+
+        class Chain(FakeTableChain):
+            def upsert(self, data, **kwargs):
+                if name == "events":
+                    supabase.events.extend(data)
+                return self
+
+        if name == "google_calendars":
+            return Chain([{**CALENDAR, "google_accounts": {"user_id": "user-1", "provider": "example"}}])
+        return Chain([])
+
+
+def test_provider_resolution_defaults_to_google_and_rejects_unknown_sources():
+    """Calendars without a provider use Google, and unregistered providers raise a provider error."""
+    provider = provider_for_calendar(None, None, "user-1", CALENDAR)
+
+    try:
+        get_provider(None, None, "user-1", "account-1", "nope")
+    except ProviderError as e:
+        error = e
+
+    assert isinstance(provider, GoogleProvider) and provider.account_id == "account-1"
+    assert error.status_code == 400
+
+
+def test_sync_goes_through_the_calendar_provider():
+    """A registered provider is picked from the calendar's account and its source is stamped on stored events."""
+    register_provider(ExampleProvider)
+    supabase = RecordingSupabase()
+    try:
+        sync = Sync(supabase, None, "user-1", CALENDAR["id"])
+        asyncio.run(sync.run())
+    finally:
+        PROVIDERS.pop(ExampleProvider.source)
+
+    assert isinstance(sync.provider, ExampleProvider)
+    assert sync.error is None
+    assert [(event["googleEventId"], event["source"], event["colorId"]) for event in supabase.events] == [("evt-1", "example", "7")]
@@ -0,0 +1,73 @@
+"""Quota manager tests - 4 tests covering priority ordering, queue-time metrics, multi-token batch requests and the per-worker share."""
+import asyncio
+import sys
+from pathlib import Path
+from types import SimpleNamespace
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar import quota as quota_module
+from app.calendar.quota import QuotaManager, RequestPriority, get_quota_manager
+
+
+def test_interactive_requests_jump_the_queue():
+    """Once the burst is spent, queued interactive requests are granted before earlier background ones."""
+    async def scenario():
+        quota = QuotaManager(rate_per_second=50, burst=1)
+        order = []
+
+        async def request(name, priority):
+            await quota.acquire(priority)
+            order.append(name)
+
+        await quota.acquire(RequestPriority.SYNC)
+        background = [asyncio.create_task(request(f"background-{i}", RequestPriority.BACKGROUND)) for i in range(2)]
+        await asyncio.sleep(0)
+        interactive = asyncio.create_task(request("interactive", RequestPriority.INTERACTIVE))
+        await asyncio.gather(*background, interactive)
+        return order
+
+    assert asyncio.run(scenario())[0] == "interactive"
+
+
+def test_snapshot_reports_queue_time_per_priority():
+    """Queued requests are counted with their wait time; a zero rate disables budgeting."""
+    async def scenario():
+        quota = QuotaManager(rate_per_second=100, burst=1)
+        await quota.acquire(RequestPriority.SYNC)
+        waited = await quota.acquire(RequestPriority.SYNC)
+        return waited, quota.snapshot()
+
+    waited, snapshot = asyncio.run(scenario())
+    sync_stats = snapshot["priorities"]["sync"]
+    assert waited > 0
+    assert sync_stats["requests"] == 2 and sync_stats["queued"] == 1
+    assert sync_stats["max_wait_seconds"] == waited
+    assert asyncio.run(QuotaManager(rate_per_second=0, burst=1).acquire()) == 0.0
+
+
+def test_batch_requests_spend_a_token_per_operation():
+    """A batch takes one token per operation, and one larger than the burst waits for a full bucket then leaves debt."""
+    async def scenario():
+        quota = QuotaManager(rate_per_second=100, burst=4)
+        await quota.acquire(RequestPriority.SYNC, tokens=3)
+        after_batch = quota.snapshot()["available_tokens"]
+        waited = await quota.acquire(RequestPriority.SYNC, tokens=10)
+        return after_batch, waited, quota.snapshot()["available_tokens"]
+
+    after_batch, waited, after_oversized = asyncio.run(scenario())
+    assert after_batch < 1.1
+    assert waited > 0 and after_oversized < -5
+
+
+def test_each_worker_gets_its_share_of_the_project_quota(monkeypatch):
+    """The configured rate and burst are split across WEB_CONCURRENCY workers so together they stay within budget."""
+    settings = SimpleNamespace(GOOGLE_QUOTA_REQUESTS_PER_SECOND=10.0, GOOGLE_QUOTA_BURST=20, WEB_CONCURRENCY=4)
+    monkeypatch.setattr(quota_module, "get_settings", lambda: settings)
+    get_quota_manager.cache_clear()
+    try:
+        snapshot = get_quota_manager().snapshot()
+    finally:
+        get_quota_manager.cache_clear()
+    assert (snapshot["rate_per_second"], snapshot["burst"]) == (2.5, 5)
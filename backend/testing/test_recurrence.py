@@ -0,0 +1,98 @@
+"""Recurrence expansion tests - 3 tests covering virtual ids, exceptions/cancellations, DST and completions."""
+import sys
+from datetime import datetime, timezone
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar.recurrence import expand_events
+
+
+def _utc(*args):
+    return datetime(*args, tzinfo=timezone.utc)
+
+
+def test_expands_timed_series_with_exceptions():
+    """Moved and cancelled exceptions replace their instances, remaining ids match Google's scheme."""
+    master = {
+        "googleEventId": "abc",
+        "summary": "Standup",
+        "start": {"dateTime": "2025-01-06T09:00:00Z", "timeZone": "UTC"},
+        "end": {"dateTime": "2025-01-06T09:30:00Z", "timeZone": "UTC"},
+        "recurrence": ["RRULE:FREQ=DAILY;COUNT=5", "EXDATE:20250109T090000Z"],
+    }
+    moved = {
+        "googleEventId": "abc_20250107T090000Z",
+        "recurringEventId": "abc",
+        "originalStartTime": {"dateTime": "2025-01-07T09:00:00Z"},
+        "start": {"dateTime": "2025-01-07T11:00:00Z"},
+        "end": {"dateTime": "2025-01-07T11:30:00Z"},
+        "status": "confirmed",
+    }
+    cancelled = {
+        "googleEventId": "abc_20250108T090000Z",
+        "recurringEventId": "abc",
+        "originalStartTime": {"dateTime": "2025-01-08T09:00:00Z"},
+        "status": "cancelled",
+    }
+
+    instances = expand_events([], [master], [moved, cancelled], _utc(2025, 1, 1), _utc(2025, 2, 1))
+
+    assert [i["googleEventId"] for i in instances] == [
+        "abc_20250106T090000Z",
+        "abc_20250107T090000Z",
+        "abc_20250110T090000Z",
+    ]
+    assert instances[1]["start"]["dateTime"] == "2025-01-07T11:00:00Z"
+    assert instances[1]["isVirtual"] is False
+    assert instances[0]["isVirtual"] is True
+    assert instances[0]["recurringEventId"] == "abc"
+    assert instances[0]["end"]["dateTime"] == "2025-01-06T09:30:00.000Z"
+
+
+def test_expands_in_master_time_zone_across_dst():
+    """Weekly local-time series keeps its wall clock time when DST changes."""
+    master = {
+        "googleEventId": "weekly",
+        "start": {"dateTime": "2025-03-03T09:00:00-05:00", "timeZone": "America/New_York"},
+        "end": {"dateTime": "2025-03-03T10:00:00-05:00", "timeZone": "America/New_York"},
+        "recurrence": ["RRULE:FREQ=WEEKLY;UNTIL=20250317T235959Z"],
+    }
+
+    instances = expand_events([], [master], [], _utc(2025, 3, 1), _utc(2025, 4, 1))
+
+    assert [i["googleEventId"] for i in instances] == [
+        "weekly_20250303T140000Z",
+        "weekly_20250310T130000Z",
+        "weekly_20250317T130000Z",
+    ]
+
+
+def test_all_day_series_and_completions():
+    """All-day ids use the date form, completions mark instances and single events."""
+    master = {
+        "googleEventId": "bday",
+        "start": {"date": "2025-01-01"},
+        "end": {"date": "2025-01-02"},
+        "recurrence": ["RRULE:FREQ=WEEKLY;UNTIL=20250115"],
+    }
+    single = {
+        "googleEventId": "one-off",
+        "start": {"dateTime": "2025-01-03T12:00:00Z"},
+        "end": {"dateTime": "2025-01-03T13:00:00Z"},
+        "status": "confirmed",
+    }
+    completions = [
+        {"master_event_id": "bday", "instance_start": "2025-01-08"},
+        {"master_event_id": "one-off", "instance_start": "2025-01-03T12:00:00Z"},
+    ]
+
+    instances = expand_events([single], [master], [], _utc(2025, 1, 1), _utc(2025, 1, 31), completions)
+
+    by_id = {i["googleEventId"]: i for i in instances}
+    assert list(by_id) == ["bday_20250101", "one-off", "bday_20250108", "bday_20250115"]
+    assert by_id["bday_20250108"]["completed"] is True
+    assert by_id["bday_20250101"]["completed"] is False
+    assert by_id["bday_20250115"]["start"] == {"date": "2025-01-15"}
+    assert by_id["one-off"]["completed"] is True
@@ -0,0 +1,82 @@
+"""Retry policy tests - 3 tests covering Retry-After handling and the per-account circuit breaker shared across workers."""
+import asyncio
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar import google_client, retry
+from app.calendar.google_client import APIBaseURL, GoogleAPIClient
+from app.calendar.helpers import GoogleAPIError
+from app.core.coordination import MemoryCoordination
+
+
+def test_backoff_honours_retry_after_and_jitters_otherwise():
+    """Retry-After wins when present; otherwise delays are jittered and capped."""
+    policy = retry.RetryPolicy(base_delay=1.0, max_delay=4.0)
+
+    assert retry.parse_retry_after("7") == 7.0
+    assert retry.parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT") == 0.0
+    assert retry.parse_retry_after("soon") is None
+    assert policy.backoff(0, retry_after=7.0) == 7.0
+    assert all(0 <= policy.backoff(10) <= 4.0 for _ in range(50))
+
+
+def test_circuit_breaker_short_circuits_after_repeated_server_errors(monkeypatch):
+    """Repeated 5xx responses open the account's circuit and later calls fail fast as retryable."""
+    coordination = MemoryCoordination()
+    breaker = retry.CircuitBreaker(failure_threshold=2, cooldown_seconds=60, failure_window_seconds=60)
+    monkeypatch.setattr(retry, "get_coordination", lambda: coordination)
+    monkeypatch.setattr(google_client, "get_coordination", lambda: coordination)
+    monkeypatch.setattr(google_client, "google_circuit_breaker", breaker)
+
+    class FakeResponse:
+        status_code = 503
+        headers = {"Retry-After": "0"}
+
+    class FakeHttp:
+        calls = 0
+
+        async def request(self, *args, **kwargs):
+            FakeHttp.calls += 1
+            return FakeResponse()
+
+    client = GoogleAPIClient(None, FakeHttp(), "user-1", "account-1")
+
+    async def token():
+        return "token"
+
+    client._get_valid_access_token = token
+
+    async def scenario():
+        errors = []
+        for _ in range(2):
+            try:
+                await client._request("GET", APIBaseURL.CALENDAR, "/calendars/primary")
+            except GoogleAPIError as e:
+                errors.append(e)
+        return errors
+
+    errors = asyncio.run(scenario())
+
+    assert FakeHttp.calls == 2
+    assert errors[0].status_code == 503 and errors[0].retryable
+    assert "paused" in errors[1].message and errors[1].retryable
+
+
+def test_circuit_breaker_counts_failures_from_every_worker(monkeypatch):
+    """Failures recorded by different workers add up to the threshold, and old failures age out of the window."""
+    coordination = MemoryCoordination()
+    monkeypatch.setattr(retry, "get_coordination", lambda: coordination)
+    workers = [retry.CircuitBreaker(failure_threshold=3, cooldown_seconds=60, failure_window_seconds=60) for _ in range(3)]
+    forgetful = retry.CircuitBreaker(failure_threshold=2, cooldown_seconds=60, failure_window_seconds=0)
+
+    async def scenario():
+        opened = [await worker.record_failure("account-1") for worker in workers]
+        aged_out = [await forgetful.record_failure("account-2") for _ in range(2)]
+        return opened, await workers[0].is_open("account-1"), aged_out
+
+    opened, is_open, aged_out = asyncio.run(scenario())
+    assert opened == [False, False, True] and is_open
+    assert aged_out == [False, False]
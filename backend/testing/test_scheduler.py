@@ -0,0 +1,158 @@
+"""Scheduler tests - 5 tests covering job claims, enqueueing while a job runs, stale job recovery, the calendar list pass and webhooks without a scheduler."""
+import asyncio
+import sys
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+from types import SimpleNamespace
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar import scheduler
+from app.calendar.scheduler import JobStatus, SyncScheduler, claim_job, enqueue_sync_job, finish_job, recover_stale_jobs
+from app.core.coordination import MemoryCoordination
+from app.routers import calendar as calendar_router
+
+
+class JobsQuery(FakeTableChain):
+    """Applies eq/neq/lt filters to the jobs table when executed, like a conditional PostgREST update."""
+
+    def __init__(self, rows, action, values=None):
+        super().__init__()
+        self.rows = rows
+        self.action = action
+        self.values = values
+        self.filters = []
+
+    def eq(self, column, value):
+        self.filters.append(lambda row: row.get(column) == value)
+        return self
+
+    def neq(self, column, value):
+        self.filters.append(lambda row: row.get(column) != value)
+        return self
+
+    def lt(self, column, value):
+        self.filters.append(lambda row: row.get(column) is not None and row[column] < value)
+        return self
+
+    def execute(self):
+        if self.action == "upsert":
+            exists = any(row["google_calendar_id"] == self.values["google_calendar_id"] for row in self.rows)
+            self.data = [] if exists else [{"rerun_requested": False, **self.values}]
+            self.rows.extend(self.data)
+            return self
+        self.data = [row for row in self.rows if all(check(row) for check in self.filters)]
+        for row in self.data:
+            row.update(self.values)
+        return self
+
+
+class Supabase:
+    def __init__(self, *jobs):
+        self.jobs = [{"google_calendar_id": "cal-1", "user_id": "user-1", "rerun_requested": False, **job} for job in jobs]
+
+    def table(self, name):
+        rows = self.jobs
+
+        class Builder:
+            def update(self, values):
+                return JobsQuery(rows, "update", values)
+
+            def upsert(self, values, **kwargs):
+                return JobsQuery(rows, "upsert", values)
+
+        return Builder()
+
+
+def test_a_job_is_claimed_by_one_worker_only():
+    """The claim is conditional on the status the worker saw, so a second worker loses the race."""
+    supabase = Supabase({"status": JobStatus.QUEUED})
+    seen = dict(supabase.jobs[0])
+
+    first = claim_job(supabase, seen, "worker-a")
+    second = claim_job(supabase, seen, "worker-b")
+
+    assert first["locked_by"] == "worker-a" and second is None
+    assert supabase.jobs[0]["status"] == JobStatus.RUNNING
+
+
+def test_enqueue_while_running_flags_a_rerun_instead_of_requeueing():
+    """A running job stays claimed; finishing it honours the rerun flag, and enqueueing a missing job creates it."""
+    supabase = Supabase({"status": JobStatus.QUEUED})
+    job = claim_job(supabase, dict(supabase.jobs[0]), "worker-a")
+
+    enqueue_sync_job(supabase, "cal-1", "user-1", "webhook")
+    assert supabase.jobs[0]["status"] == JobStatus.RUNNING and supabase.jobs[0]["rerun_requested"] is True
+    assert claim_job(supabase, {"google_calendar_id": "cal-1", "status": JobStatus.QUEUED}, "worker-b") is None
+
+    finish_job(supabase, dict(job), None)
+    assert supabase.jobs[0]["status"] == JobStatus.QUEUED and supabase.jobs[0]["rerun_requested"] is False
+    assert supabase.jobs[0]["last_success_at"] is not None and supabase.jobs[0]["locked_by"] is None
+
+    enqueue_sync_job(supabase, "cal-2", "user-1", "manual")
+    assert [row["google_calendar_id"] for row in supabase.jobs] == ["cal-1", "cal-2"]
+
+
+def test_stale_jobs_are_recovered_and_their_old_worker_cannot_finish_them():
+    """Jobs running past the lease are re-queued, and the late result of the abandoned run is discarded."""
+    started = (datetime.now(timezone.utc) - timedelta(hours=1)).isoformat()
+    supabase = Supabase({"status": JobStatus.RUNNING, "locked_by": "worker-a", "started_at": started})
+    abandoned = dict(supabase.jobs[0])
+
+    recover_stale_jobs(supabase)
+    assert supabase.jobs[0]["status"] == JobStatus.QUEUED and supabase.jobs[0]["locked_by"] is None
+
+    claim_job(supabase, dict(supabase.jobs[0]), "worker-b")
+    finish_job(supabase, abandoned, {"code": "500", "message": "boom"})
+    assert supabase.jobs[0]["status"] == JobStatus.RUNNING and supabase.jobs[0]["locked_by"] == "worker-b"
+
+
+def test_calendar_list_pass_runs_on_one_worker_and_fans_out(monkeypatch):
+    """Only the worker holding the lease syncs calendar lists, running accounts concurrently up to the limit."""
+    coordination = MemoryCoordination()
+    monkeypatch.setattr(scheduler, "get_coordination", lambda: coordination)
+    monkeypatch.setattr(scheduler, "get_syncable_accounts", lambda supabase: [{"id": f"account-{n}", "user_id": "user-1"} for n in range(5)])
+    synced, running, peak = [], set(), []
+
+    async def run_calendar_list_sync(user_id, account_id):
+        running.add(account_id)
+        peak.append(len(running))
+        await asyncio.sleep(0)
+        running.discard(account_id)
+        synced.append(account_id)
+
+    monkeypatch.setattr(scheduler, "run_calendar_list_sync", run_calendar_list_sync)
+
+    async def run():
+        workers = [SyncScheduler(), SyncScheduler()]
+        for worker in workers:
+            await worker._start_calendar_list_sync(None)
+        await asyncio.gather(*(worker._calendar_list_task for worker in workers if worker._calendar_list_task))
+        return [worker._calendar_list_task is not None for worker in workers]
+
+    assert asyncio.run(run()) == [True, False]
+    assert sorted(synced) == [f"account-{n}" for n in range(5)]
+    assert max(peak) == scheduler.SyncSchedulerConfig.MAX_CONCURRENT_CALENDAR_LIST_SYNCS
+
+
+def test_webhooks_sync_inline_when_the_scheduler_is_disabled(monkeypatch):
+    """With SYNC_SCHEDULER_ENABLED off no worker claims queued jobs, so a webhook syncs the calendar after responding."""
+    coordination = MemoryCoordination()
+    monkeypatch.setattr(calendar_router, "get_coordination", lambda: coordination)
+    monkeypatch.setattr(calendar_router, "get_settings", lambda: SimpleNamespace(SYNC_SCHEDULER_ENABLED=False))
+    supabase = Supabase()
+
+    class BackgroundTasks:
+        tasks = []
+
+        def add_task(self, func, *args):
+            self.tasks.append((func, *args))
+
+    background_tasks = BackgroundTasks()
+    sync_state = {"google_calendar_id": "cal-1", "google_calendars": {"google_accounts": {"user_id": "user-1"}}}
+    asyncio.run(calendar_router.enqueue_webhook_sync(supabase, sync_state, background_tasks))
+
+    assert background_tasks.tasks == [(scheduler.run_calendar_sync, "user-1", "cal-1")]
+    assert supabase.jobs == []
@@ -0,0 +1,91 @@
+"""Series split tests - 2 tests covering the exception at the split point and COUNT/UNTIL truncation."""
+import asyncio
+import sys
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.series import following_recurrence, split_series, truncate_recurrence
+from app.models.event import EventDateTime, EventPatch
+
+MASTER = {
+    "id": "standup",
+    "summary": "Standup",
+    "start": {"dateTime": "2026-03-02T09:00:00+00:00", "timeZone": "UTC"},
+    "end": {"dateTime": "2026-03-02T09:15:00+00:00", "timeZone": "UTC"},
+    "recurrence": ["RRULE:FREQ=DAILY;COUNT=5"],
+}
+
+
+def exception(day, **fields):
+    return {
+        "googleEventId": f"standup_202603{day:02d}T090000Z",
+        "recurringEventId": "standup",
+        "originalStartTime": {"dateTime": f"2026-03-{day:02d}T09:00:00+00:00"},
+        "status": "confirmed",
+        **fields,
+    }
+
+
+class FakeClient:
+    source = "google"
+
+    def __init__(self):
+        self.created = None
+        self.edits = []
+
+    async def get_event(self, calendar_id, event_id):
+        return MASTER
+
+    async def create_event(self, calendar_id, event):
+        self.created = event.model_dump(exclude_none=True)
+        return {**self.created, "id": "standup2"}
+
+    async def edit_event(self, calendar_id, event_id, patch, etag=None):
+        self.edits.append((event_id, patch.model_dump(exclude_none=True)))
+        return {"id": event_id, **patch.model_dump(exclude_none=True)}
+
+
+class Supabase:
+    def __init__(self, events):
+        self.events = events
+
+    def table(self, name):
+        return FakeTableChain(self.events if name == "events" else [])
+
+
+def test_split_point_exception_takes_the_new_patch():
+    """The exception at the split instance is cancelled rather than replayed over the user's edit; later ones migrate."""
+    client = FakeClient()
+    supabase = Supabase([
+        exception(4, summary="Old title", start={"dateTime": "2026-03-04T10:00:00+00:00"}, end={"dateTime": "2026-03-04T10:15:00+00:00"}),
+        exception(5, summary="Demo day"),
+    ])
+
+    result = asyncio.run(split_series(
+        client, supabase, {"id": "cal-1", "google_calendar_id": "work"}, "standup",
+        EventDateTime(dateTime="2026-03-04T09:00:00+00:00"), EventPatch(summary="Standup v2"),
+    ))
+
+    assert client.created["summary"] == "Standup v2"
+    assert client.created["recurrence"] == ["RRULE:FREQ=DAILY;COUNT=3"]
+    assert [event_id for event_id, _ in client.edits] == ["standup2_20260305T090000Z", "standup"]
+    assert client.edits[0][1]["summary"] == "Demo day"
+    assert client.edits[1][1]["recurrence"] == ["RRULE:FREQ=DAILY;UNTIL=20260304T085959Z"]
+    assert sorted(result["cancelled"]) == ["standup_20260304T090000Z", "standup_20260305T090000Z"]
+
+
+def test_count_and_until_rules_are_split_around_the_split_point():
+    """COUNT is divided between the two series, UNTIL moves with the offset, and all-day series end the day before."""
+    start = datetime(2026, 3, 2, 9, tzinfo=timezone.utc)
+    split_at = datetime(2026, 3, 4, 9, tzinfo=timezone.utc)
+    counted = ["RRULE:FREQ=DAILY;COUNT=5", "EXDATE:20260303T090000Z,20260305T090000Z"]
+    until = ["RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20260401T090000Z"]
+
+    assert truncate_recurrence(counted, split_at, False) == ["RRULE:FREQ=DAILY;UNTIL=20260304T085959Z", "EXDATE:20260303T090000Z"]
+    assert following_recurrence(counted, start, split_at, timedelta(0), False, []) == ["RRULE:FREQ=DAILY;COUNT=3", "EXDATE:20260305T090000Z"]
+    assert following_recurrence(until, start, split_at, timedelta(hours=1), False, []) == ["RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20260401T100000Z"]
+    assert truncate_recurrence(["RRULE:FREQ=DAILY"], datetime(2026, 3, 4, tzinfo=timezone.utc), True) == ["RRULE:FREQ=DAILY;UNTIL=20260303"]
@@ -0,0 +1,50 @@
+"""Sync session tests - 2 tests covering cancellation across workers and session ownership."""
+import asyncio
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar import sync_sessions
+from app.calendar.sync_sessions import cancel_session, end_session, start_session
+from app.core.coordination import MemoryCoordination
+
+
+def test_cancel_reaches_a_session_running_on_another_worker(monkeypatch):
+    """A DELETE handled by a worker without the session flags it, and the owning worker stops its syncs."""
+    coordination = MemoryCoordination()
+    monkeypatch.setattr(sync_sessions, "get_coordination", lambda: coordination)
+    monkeypatch.setattr(sync_sessions, "CANCEL_POLL_SECONDS", 0.01)
+
+    async def run():
+        session = await start_session("user-1", ["cal-1"], 60)
+        session.tasks = [asyncio.create_task(asyncio.sleep(60))]
+        sync_sessions._sessions.pop(session.id)
+
+        async def owner():
+            await session.watch_cancel()
+            await session.wait_cancelled()
+            await end_session(session)
+
+        owning_worker = asyncio.create_task(owner())
+        assert await cancel_session(session.id, "user-1") is True
+        await owning_worker
+        return session
+
+    session = asyncio.run(run())
+    assert session.cancelled and session.tasks[0].cancelled()
+
+
+def test_cancel_is_refused_for_other_users_and_finished_sessions(monkeypatch):
+    """Only the user who started a live session can cancel it."""
+    coordination = MemoryCoordination()
+    monkeypatch.setattr(sync_sessions, "get_coordination", lambda: coordination)
+
+    async def run():
+        session = await start_session("user-1", ["cal-1"], 60)
+        refused = await cancel_session(session.id, "user-2")
+        await end_session(session)
+        return refused, await cancel_session(session.id, "user-1")
+
+    assert asyncio.run(run()) == (False, False)
@@ -0,0 +1,66 @@
+"""Sync status tests - 2 tests covering failure tracking and per-calendar health."""
+import sys
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from conftest import FakeTableChain
+from app.calendar.sync import Sync
+from app.routers.calendar import sync_health
+
+
+class RecordingSupabase:
+    def __init__(self, state):
+        self.state = state
+        self.upserts: list[dict] = []
+
+    def table(self, name):
+        supabase = self
+
+        class Chain(FakeTableChain):
+            def select(self, *args, **kwargs):
+                return self
+
+            def upsert(self, data, **kwargs):
+                supabase.upserts.append(data)
+                return self
+
+        chain = Chain([self.state])
+        chain.count = 42
+        return chain
+
+
+def test_save_status_tracks_failures_and_resets_on_success():
+    """Errors are persisted with an incremented failure count; a clean run clears them and records the event count."""
+    supabase = RecordingSupabase({"consecutive_failures": 2})
+    sync = Sync(supabase, None, "user-1", "cal-1")
+    sync.error = {"code": "403", "message": "Forbidden"}
+    sync._save_status("cal-1")
+
+    failed = supabase.upserts[-1]
+    assert failed["consecutive_failures"] == 3
+    assert failed["last_error_code"] == "403"
+    assert failed["last_error_message"] == "Forbidden"
+
+    sync.error = None
+    sync._save_status("cal-1")
+
+    succeeded = supabase.upserts[-1]
+    assert succeeded["consecutive_failures"] == 0
+    assert succeeded["last_error_code"] is None
+    assert succeeded["event_count"] == 42
+
+
+def test_sync_health_classification():
+    """Health reflects failure streaks first, then how long ago the last success was."""
+    now = datetime.now(timezone.utc)
+    recent = (now - timedelta(minutes=5)).isoformat()
+    old = (now - timedelta(days=1)).isoformat()
+
+    assert sync_health({}, now) == "never_synced"
+    assert sync_health({"last_success_at": recent}, now) == "healthy"
+    assert sync_health({"last_success_at": old}, now) == "stale"
+    assert sync_health({"last_success_at": recent, "consecutive_failures": 1}, now) == "degraded"
+    assert sync_health({"last_success_at": recent, "consecutive_failures": 3}, now) == "failing"
@@ -0,0 +1,134 @@
+"""Sync stream tests - 4 tests covering Last-Event-ID resumes, the rate limit they bypass and cancelling a running sync."""
+import asyncio
+import json
+import sys
+import uuid
+from pathlib import Path
+
+import pytest
+from fastapi import HTTPException
+
+ROOT = Path(__file__).resolve().parents[1]
+sys.path.insert(0, str(ROOT))
+
+from app.calendar import sync_sessions
+from app.core.coordination import MemoryCoordination
+from app.routers import calendar as calendar_router
+from app.routers.calendar import SyncRequest, cancel_sync, parse_last_event_id, sync_calendars
+
+USER = {"id": "user-1"}
+CALENDAR_ID = uuid.uuid4()
+
+
+class FakeRequest:
+    def __init__(self, disconnected=False):
+        self.disconnected = disconnected
+
+    async def is_disconnected(self):
+        return self.disconnected
+
+
+class FakeSync:
+    def __init__(self, supabase, http, user_id, calendar_id, queue):
+        self.calendar_id = calendar_id
+        self.queue = queue
+
+    async def run(self):
+        await self.queue.put({"type": "events", "calendar_id": self.calendar_id, "events": []})
+        await self.queue.put({"type": "calendar_done", "calendar_id": self.calendar_id})
+
+
+class BlockingSync(FakeSync):
+    async def run(self):
+        try:
+            await asyncio.Event().wait()
+        finally:
+            await self.queue.put({"type": "calendar_done", "calendar_id": self.calendar_id})
+
+
+def use_fakes(monkeypatch, sync_class=FakeSync):
+    coordination = MemoryCoordination()
+    monkeypatch.setattr(calendar_router, "get_coordination", lambda: coordination)
+    monkeypatch.setattr(sync_sessions, "get_coordination", lambda: coordination)
+    monkeypatch.setattr(calendar_router, "Sync", sync_class)
+    monkeypatch.setattr(calendar_router, "create_supabase_client", lambda: None)
+    monkeypatch.setattr(calendar_router, "get_latest_sync_at", lambda supabase, calendar_ids: "2026-03-01T12:00:00+00:00")
+
+
+async def open_stream(request, last_event_id=None):
+    response = await sync_calendars(
+        request, body=SyncRequest(calendar_ids=[CALENDAR_ID]), current_user=USER, supabase=None, http=None,
+        last_event_id=last_event_id,
+    )
+    return response.body_iterator
+
+
+async def read_all(stream):
+    return [chunk async for chunk in stream]
+
+
+async def read_session_id(stream):
+    frame = await anext(stream)
+    return json.loads(frame.split("data: ")[1])["session_id"]
+
+
+def test_interrupted_stream_resumes_numbering_without_the_rate_limit(monkeypatch):
+    """A dropped stream can reconnect at once with Last-Event-ID and continues the event ids; a fresh sync is still limited."""
+    use_fakes(monkeypatch)
+
+    async def run():
+        dropped = await read_all(await open_stream(FakeRequest(disconnected=True)))
+        resumed = await read_all(await open_stream(FakeRequest(), last_event_id="4"))
+        with pytest.raises(HTTPException) as limited:
+            await open_stream(FakeRequest())
+        return dropped, resumed, limited.value.status_code
+
+    dropped, resumed, status = asyncio.run(run())
+    assert [chunk.split("\n")[0] for chunk in dropped] == ["event: session"]
+    assert [chunk.split("\n")[0] for chunk in resumed[1:]] == ["id: 5", "id: 6"]
+    assert '"resumed": true' in resumed[-1] and '"cancelled": false' in resumed[-1]
+    assert status == 429
+
+
+def test_last_event_id_must_be_a_non_negative_integer():
+    """Malformed resume ids are rejected rather than restarting numbering from an arbitrary point."""
+    assert parse_last_event_id(None) is None
+    assert parse_last_event_id("12") == 12
+    for value in ("-1", "abc"):
+        with pytest.raises(HTTPException) as rejected:
+            parse_last_event_id(value)
+        assert rejected.value.status_code == 400
+
+
+def test_delete_stops_the_running_sync_and_the_stream_reports_it(monkeypatch):
+    """Cancelling the session stops its calendar syncs, and the stream completes with cancelled set instead of hanging."""
+    use_fakes(monkeypatch, BlockingSync)
+
+    async def run():
+        stream = await open_stream(FakeRequest())
+        session_id = await read_session_id(stream)
+        rest = asyncio.create_task(read_all(stream))
+        await asyncio.sleep(0)
+        response = await cancel_sync(session_id, current_user=USER)
+        return response.status_code, await rest
+
+    status, frames = asyncio.run(run())
+    assert status == 204
+    assert '"calendars_synced": 1' in frames[-1] and '"cancelled": true' in frames[-1]
+
+
+def test_delete_is_a_404_for_other_users_and_finished_sessions(monkeypatch):
+    """A session id only cancels for the user who started it, and not at all once the stream has ended."""
+    use_fakes(monkeypatch)
+
+    async def run():
+        stream = await open_stream(FakeRequest())
+        session_id = await read_session_id(stream)
+        with pytest.raises(HTTPException) as other_user:
+            await cancel_sync(session_id, current_user={"id": "user-2"})
+        await read_all(stream)
+        with pytest.raises(HTTPException) as finished:
+            await cancel_sync(session_id, current_user=USER)
+        return other_user.value.status_code, finished.value.status_code
+
+    assert asyncio.run(run()) == (404, 404)
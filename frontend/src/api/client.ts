@@ -95,7 +95,7 @@ export function getCsrfToken(): string | null {
   return getCookie(CSRF_COOKIE_NAME);
 }
 
-class ApiError extends Error {
+export class ApiError extends Error {
   constructor(
     message: string,
     public status: number,
@@ -106,6 +106,23 @@ class ApiError extends Error {
   }
 }
 
+// fetch rejects with a bare TypeError when the request never reaches the server.
+export class NetworkError extends Error {
+  constructor(cause: unknown) {
+    super(cause instanceof Error ? cause.message : "Network request failed");
+    this.name = "NetworkError";
+  }
+}
+
+async function send(url: string, init: RequestInit): Promise<Response> {
+  try {
+    return await fetch(url, init);
+  } catch (error) {
+    if (error instanceof TypeError) throw new NetworkError(error);
+    throw error;
+  }
+}
+
 function getErrorDetail(details: unknown): string | null {
   if (
     typeof details !== "object" ||
@@ -152,7 +169,7 @@ async function request<T>(
       }
     }
 
-    const response = await fetch(url, {
+    const response = await send(url, {
       ...init,
       credentials: "include",
       headers,
@@ -175,7 +192,7 @@ async function request<T>(
         detail &&
         detail.includes("CSRF")
       ) {
-        const csrfResponse = await fetch(`${API_BASE_URL}/auth/csrf`, {
+        const csrfResponse = await send(`${API_BASE_URL}/auth/csrf`, {
           method: "GET",
           credentials: "include",
         });
@@ -226,11 +243,13 @@ export const api = {
     endpoint: string,
     data?: unknown,
     params?: Record<string, string>,
+    headers?: HeadersInit,
   ) =>
     request<T>(endpoint, {
       method: "POST",
       body: data ? JSON.stringify(data) : undefined,
       params,
+      headers,
     }),
 
   put: <T>(endpoint: string, data?: unknown) =>
@@ -239,11 +258,13 @@ export const api = {
       body: data ? JSON.stringify(data) : undefined,
     }),
 
-  patch: <T>(endpoint: string, data?: unknown) =>
+  patch: <T>(endpoint: string, data?: unknown, headers?: HeadersInit) =>
     request<T>(endpoint, {
       method: "PATCH",
       body: data ? JSON.stringify(data) : undefined,
+      headers,
     }),
 
-  delete: <T>(endpoint: string) => request<T>(endpoint, { method: "DELETE" }),
+  delete: <T>(endpoint: string, headers?: HeadersInit) =>
+    request<T>(endpoint, { method: "DELETE", headers }),
 };
@@ -1,5 +1,34 @@
-import { api } from "./client";
-import type { CalendarEvent, EventCompletion } from "../types";
+import { api, ApiError } from "./client";
+import type { CalendarEvent, EventCompletion, EventDateTime } from "../types";
+
+export interface EventConflictDetail {
+  message: string;
+  current: CalendarEvent | null;
+}
+
+export function getEventConflict(error: unknown): EventConflictDetail | null {
+  if (!(error instanceof ApiError) || error.status !== 409) return null;
+  const details = error.details as { detail?: EventConflictDetail } | undefined;
+  return details?.detail ?? { message: error.message, current: null };
+}
+
+export interface SeriesSplitResponse {
+  master: CalendarEvent;
+  series: CalendarEvent | null;
+  exceptions: CalendarEvent[];
+}
+
+export type BatchOperation =
+  | { method: "create"; event: Partial<CalendarEvent> }
+  | { method: "update"; eventId: string; patch: Partial<CalendarEvent>; etag?: string }
+  | { method: "delete"; eventId: string; etag?: string };
+
+export interface BatchResult {
+  status: number;
+  eventId: string | null;
+  event?: CalendarEvent;
+  error?: string;
+}
 
 export const eventsApi = {
   create: (calendarId: string, event: Partial<CalendarEvent>) =>
@@ -9,14 +38,49 @@ export const eventsApi = {
     calendarId: string,
     eventId: string,
     event: Partial<CalendarEvent>,
+    etag?: string,
   ) =>
     api.patch<CalendarEvent>(
       `/calendar/${calendarId}/events/${eventId}`,
       event,
+      etag ? { "If-Match": etag } : undefined,
+    ),
+
+  splitSeries: (
+    calendarId: string,
+    masterEventId: string,
+    instanceStart: EventDateTime,
+    event: Partial<CalendarEvent>,
+  ) =>
+    api.post<SeriesSplitResponse>(
+      `/calendar/${calendarId}/events/${masterEventId}/split`,
+      { instanceStart, event },
     ),
 
-  delete: (calendarId: string, eventId: string) =>
-    api.delete<void>(`/calendar/${calendarId}/events/${eventId}`),
+  move: (
+    calendarId: string,
+    eventId: string,
+    destinationCalendarId: string,
+    etag?: string,
+  ) =>
+    api.post<CalendarEvent>(
+      `/calendar/${calendarId}/events/${eventId}/move`,
+      { destinationCalendarId },
+      undefined,
+      etag ? { "If-Match": etag } : undefined,
+    ),
+
+  delete: (calendarId: string, eventId: string, etag?: string) =>
+    api.delete<void>(
+      `/calendar/${calendarId}/events/${eventId}`,
+      etag ? { "If-Match": etag } : undefined,
+    ),
+
+  batch: (calendarId: string, operations: BatchOperation[]) =>
+    api.post<{ results: BatchResult[] }>(
+      `/calendar/${calendarId}/events:batch`,
+      { operations },
+    ),
 
   toggleCompletion: (completion: EventCompletion & { completed: boolean }) =>
     api.post<{ completed: boolean }>("/calendar/complete-event", completion),
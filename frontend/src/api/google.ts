@@ -1,15 +1,25 @@
 import { api } from './client'
-import type { GoogleAccount, GoogleCalendar, CalendarEvent, EventCompletion } from '../types'
+import type { BackfillStatus, CalDAVCredentials, CalendarSyncStatus, ChronosCalendarInput, GoogleAccount, GoogleCalendar, CalendarEvent, EventCompletion } from '../types'
 
 interface EventsResponse {
   events: CalendarEvent[]
   masters: CalendarEvent[]
   exceptions: CalendarEvent[]
   completions: EventCompletion[]
+  nextCursor: string | null
+  serverTime: string
+}
+
+export interface EventsQuery {
+  timeMin?: string
+  timeMax?: string
+  updatedSince?: string
+  cursor?: string
 }
 
 interface SyncStatusResponse {
   lastSyncAt: string | null
+  calendars: CalendarSyncStatus[]
 }
 
 export interface Contact {
@@ -25,8 +35,12 @@ export const googleApi = {
   getCalendars: () =>
     api.get<{ calendars: GoogleCalendar[] }>('/calendar/calendars'),
 
-  getEvents: (calendarIds?: string[]) => {
-    const params = calendarIds?.length ? { calendar_ids: calendarIds.join(',') } : undefined
+  getEvents: (calendarIds?: string[], query: EventsQuery = {}) => {
+    const params: Record<string, string> = {}
+    if (calendarIds?.length) params.calendar_ids = calendarIds.join(',')
+    for (const [key, value] of Object.entries(query)) {
+      if (value) params[key] = value
+    }
     return api.get<EventsResponse>('/calendar/events', params)
   },
 
@@ -35,6 +49,33 @@ export const googleApi = {
       `/calendar/accounts/${googleAccountId}/refresh-calendars`
     ),
 
+  connectCalDAV: (credentials: CalDAVCredentials) =>
+    api.post<{ account: Pick<GoogleAccount, 'id' | 'email' | 'name' | 'provider'> }>(
+      '/calendar/accounts/caldav',
+      credentials
+    ),
+
+  connectMicrosoft: () =>
+    api.get<{ redirectUrl: string }>('/auth/microsoft/connect'),
+
+  createCalendar: (calendar: ChronosCalendarInput) =>
+    api.post<GoogleCalendar>('/calendar/calendars', calendar),
+
+  updateCalendar: (calendarId: string, changes: Partial<ChronosCalendarInput>) =>
+    api.patch<GoogleCalendar>(`/calendar/calendars/${calendarId}`, changes),
+
+  deleteCalendar: (calendarId: string) =>
+    api.delete<void>(`/calendar/calendars/${calendarId}`),
+
+  setBackfill: (calendarId: string, enabled: boolean) =>
+    api.put<{ backfill_enabled: boolean; backfill_status: BackfillStatus | null }>(
+      `/calendar/${calendarId}/backfill`,
+      { enabled }
+    ),
+
+  cancelSync: (sessionId: string) =>
+    api.delete<void>(`/calendar/sync/${sessionId}`),
+
   getSyncStatus: (calendarIds?: string[]) => {
     const params = calendarIds?.length ? { calendar_ids: calendarIds.join(',') } : undefined
     return api.get<SyncStatusResponse>('/calendar/sync-status', params)
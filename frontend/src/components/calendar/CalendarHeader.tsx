@@ -5,6 +5,7 @@ import { useClickOutside } from '../../hooks/useClickOutside'
 import { formatMonthYear } from '../../lib/date'
 import { CalendarVisibilityPanel } from './CalendarVisibilityPanel'
 import { SyncButton } from './SyncButton'
+import { OutboxStatus } from './OutboxStatus'
 import type { CalendarView } from '../../types'
 
 const views: { value: CalendarView; label: string; shortcut: string }[] = [
@@ -66,6 +67,7 @@ export function CalendarHeader() {
       </div>
 
       <div className="flex items-center gap-3">
+        <OutboxStatus />
         <SyncButton />
         <CalendarVisibilityPanel />
 
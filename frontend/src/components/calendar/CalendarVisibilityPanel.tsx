@@ -1,8 +1,17 @@
 import { useState, useRef, useEffect, useMemo, useCallback } from 'react'
-import { ChevronDown, ChevronRight, AlertCircle } from 'lucide-react'
-import { useGoogleCalendars, useGroupedCalendars, useClickOutside } from '../../hooks'
+import { ChevronDown, ChevronRight, AlertCircle, History, Plus, Trash2 } from 'lucide-react'
+import {
+  useGoogleCalendars,
+  useGroupedCalendars,
+  useClickOutside,
+  useSetCalendarBackfill,
+  useCalendarSyncStatus,
+  useCreateCalendar,
+  useUpdateCalendar,
+  useDeleteCalendar,
+} from '../../hooks'
 import { useCalendarsStore } from '../../stores'
-import type { GoogleCalendar } from '../../types'
+import type { CalendarSyncStatus, GoogleCalendar } from '../../types'
 
 export function CalendarVisibilityPanel() {
   const [isOpen, setIsOpen] = useState(false)
@@ -13,6 +22,12 @@ export function CalendarVisibilityPanel() {
   const { data: calendars, isLoading } = useGoogleCalendars()
   const groupedCalendars = useGroupedCalendars(calendars)
   const { isVisible, toggleVisibility } = useCalendarsStore()
+  const { data: syncStatuses } = useCalendarSyncStatus(isOpen)
+
+  const syncStatusById = useMemo(
+    () => new Map((syncStatuses ?? []).map((status) => [status.calendarId, status])),
+    [syncStatuses]
+  )
 
   useEffect(() => {
     if (initializedRef.current || !calendars) return
@@ -95,16 +110,50 @@ export function CalendarVisibilityPanel() {
                 onToggleExpand={() => toggleAccountExpanded(accountId)}
                 isVisible={isVisible}
                 onToggleVisibility={toggleVisibility}
+                syncStatusById={syncStatusById}
               />
             ))}
           </div>
 
+          <NewCalendarForm />
         </div>
       )}
     </div>
   )
 }
 
+function NewCalendarForm() {
+  const [name, setName] = useState('')
+  const createCalendar = useCreateCalendar()
+
+  const submit = (e: React.FormEvent) => {
+    e.preventDefault()
+    const trimmed = name.trim()
+    if (!trimmed) return
+    createCalendar.mutate({ name: trimmed }, { onSuccess: () => setName('') })
+  }
+
+  return (
+    <form onSubmit={submit} className="flex items-center gap-2 p-2 border-t border-gray-100">
+      <input
+        value={name}
+        onChange={(e) => setName(e.target.value)}
+        placeholder="New calendar"
+        maxLength={200}
+        className="flex-1 min-w-0 px-2 py-1 text-xs border border-gray-200 rounded focus:outline-none focus:border-gray-400"
+      />
+      <button
+        type="submit"
+        disabled={!name.trim() || createCalendar.isPending}
+        title="Create calendar"
+        className="p-1 rounded text-gray-500 hover:bg-gray-100 disabled:text-gray-300 transition-colors"
+      >
+        <Plus size={14} />
+      </button>
+    </form>
+  )
+}
+
 interface AccountSectionProps {
   account: { id: string; email: string; name: string; needs_reauth: boolean }
   calendars: GoogleCalendar[]
@@ -112,6 +161,7 @@ interface AccountSectionProps {
   onToggleExpand: () => void
   isVisible: (id: string) => boolean
   onToggleVisibility: (id: string) => void
+  syncStatusById: Map<string, CalendarSyncStatus>
 }
 
 function AccountSection({
@@ -121,6 +171,7 @@ function AccountSection({
   onToggleExpand,
   isVisible,
   onToggleVisibility,
+  syncStatusById,
 }: AccountSectionProps) {
   return (
     <div className="border-b border-gray-50 last:border-b-0">
@@ -149,6 +200,7 @@ function AccountSection({
               calendar={calendar}
               isVisible={isVisible(calendar.id)}
               onToggle={() => onToggleVisibility(calendar.id)}
+              syncStatus={syncStatusById.get(calendar.id)}
             />
           ))}
         </div>
@@ -161,9 +213,80 @@ interface CalendarRowProps {
   calendar: GoogleCalendar
   isVisible: boolean
   onToggle: () => void
+  syncStatus?: CalendarSyncStatus
+}
+
+const HEALTH_COLORS = {
+  failing: 'text-red-500',
+  degraded: 'text-amber-500',
+  stale: 'text-gray-400',
+  never_synced: 'text-gray-300',
+} as const
+
+function formatSyncAge(timestamp: string | null) {
+  if (!timestamp) return 'never'
+  const minutes = Math.round((Date.now() - new Date(timestamp).getTime()) / 60000)
+  if (minutes < 1) return 'just now'
+  if (minutes < 60) return `${minutes}m ago`
+  const hours = Math.round(minutes / 60)
+  return hours < 48 ? `${hours}h ago` : `${Math.round(hours / 24)}d ago`
+}
+
+function describeSyncStatus(status: CalendarSyncStatus) {
+  const lines = [`Last synced ${formatSyncAge(status.lastSuccessAt)}`]
+  if (status.consecutiveFailures > 0) {
+    lines.push(
+      `Failed ${status.consecutiveFailures}x: ${status.lastErrorCode ?? ''} ${status.lastErrorMessage ?? ''}`.trim()
+    )
+  }
+  if (status.eventCount !== null) lines.push(`${status.eventCount} events`)
+  if (status.webhookExpiresAt) {
+    lines.push(`Live updates until ${new Date(status.webhookExpiresAt).toLocaleString()}`)
+  }
+  return lines.join('\n')
 }
 
-function CalendarRow({ calendar, isVisible, onToggle }: CalendarRowProps) {
+const BACKFILL_LABELS = {
+  past: 'Syncing older events',
+  future: 'Syncing future events',
+  complete: 'Full history synced',
+  disabled: 'Older history off',
+} as const
+
+function CalendarRow({ calendar, isVisible, onToggle, syncStatus }: CalendarRowProps) {
+  const setBackfill = useSetCalendarBackfill()
+  const updateCalendar = useUpdateCalendar()
+  const deleteCalendar = useDeleteCalendar()
+  const isLocal = calendar.google_accounts?.provider === 'chronos'
+  const syncState = calendar.calendar_sync_state
+  const backfillEnabled = syncState?.backfill_enabled ?? true
+  const backfillLabel = syncState?.backfill_status
+    ? BACKFILL_LABELS[syncState.backfill_status]
+    : 'Older history on'
+
+  const toggleBackfill = (e: React.MouseEvent) => {
+    e.preventDefault()
+    e.stopPropagation()
+    setBackfill.mutate({ calendarId: calendar.id, enabled: !backfillEnabled })
+  }
+
+  const rename = (e: React.MouseEvent) => {
+    if (!isLocal) return
+    e.preventDefault()
+    const name = window.prompt('Rename calendar', calendar.name)?.trim()
+    if (name && name !== calendar.name) {
+      updateCalendar.mutate({ calendarId: calendar.id, changes: { name } })
+    }
+  }
+
+  const remove = (e: React.MouseEvent) => {
+    e.preventDefault()
+    e.stopPropagation()
+    if (window.confirm(`Delete "${calendar.name}" and all of its events?`)) {
+      deleteCalendar.mutate(calendar.id)
+    }
+  }
+
   return (
     <label className="flex items-center gap-2 px-3 py-1.5 pl-8 hover:bg-gray-50 cursor-pointer transition-colors">
       <input
@@ -192,10 +315,52 @@ function CalendarRow({ calendar, isVisible, onToggle }: CalendarRowProps) {
           </svg>
         )}
       </div>
-      <span className="text-xs text-gray-700 truncate flex-1">{calendar.name}</span>
+      <span
+        className="text-xs text-gray-700 truncate flex-1"
+        title={isLocal ? 'Double-click to rename' : syncStatus ? describeSyncStatus(syncStatus) : undefined}
+        onDoubleClick={rename}
+      >
+        {calendar.name}
+      </span>
+      {syncStatus && syncStatus.health !== 'healthy' && (
+        <AlertCircle size={12} className={HEALTH_COLORS[syncStatus.health]} />
+      )}
       {calendar.is_primary && (
         <span className="text-[10px] text-gray-400 uppercase tracking-wide">Primary</span>
       )}
+      {isLocal ? (
+        <>
+          <input
+            type="color"
+            value={calendar.color || '#818cf8'}
+            onClick={(e) => e.stopPropagation()}
+            onChange={(e) => updateCalendar.mutate({ calendarId: calendar.id, changes: { color: e.target.value } })}
+            title="Calendar color"
+            className="w-4 h-4 p-0 border-0 bg-transparent cursor-pointer"
+          />
+          <button
+            type="button"
+            onClick={remove}
+            disabled={deleteCalendar.isPending}
+            title="Delete calendar"
+            className="p-0.5 rounded text-gray-400 hover:bg-gray-100 hover:text-red-500 transition-colors"
+          >
+            <Trash2 size={12} />
+          </button>
+        </>
+      ) : (
+        <button
+          type="button"
+          onClick={toggleBackfill}
+          disabled={setBackfill.isPending}
+          title={`${backfillLabel} (click to turn ${backfillEnabled ? 'off' : 'on'})`}
+          className={`p-0.5 rounded hover:bg-gray-100 transition-colors ${
+            backfillEnabled ? 'text-gray-500' : 'text-gray-300'
+          }`}
+        >
+          <History size={12} />
+        </button>
+      )}
     </label>
   )
 }
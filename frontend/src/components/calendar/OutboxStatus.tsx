@@ -0,0 +1,75 @@
+import { useState } from 'react'
+import { AlertTriangle, CloudOff } from 'lucide-react'
+import { useOutbox } from '../../hooks'
+import type { DexieOutboxEntry } from '../../lib/db'
+
+function describeEntry(entry: DexieOutboxEntry): string {
+  const payload = (entry.payload ?? {}) as { summary?: string; title?: string }
+  const name = payload.summary || payload.title || (entry.entity === 'event' ? 'Event' : 'Todo')
+  const verb = entry.action === 'create' ? 'Create' : entry.action === 'update' ? 'Edit' : 'Delete'
+  return `${verb} "${name}"`
+}
+
+export function OutboxStatus() {
+  const { pending, unsynced, retry, discard } = useOutbox()
+  const [open, setOpen] = useState(false)
+
+  if (pending.length === 0 && unsynced.length === 0) return null
+
+  if (unsynced.length === 0) {
+    return (
+      <span
+        className="flex items-center gap-1.5 px-2 py-1 text-xs text-gray-500"
+        title="Changes made offline are waiting to sync"
+      >
+        <CloudOff size={14} />
+        <span className="hidden sm:inline">{pending.length} pending</span>
+      </span>
+    )
+  }
+
+  return (
+    <div className="relative">
+      <button
+        onClick={() => setOpen((o) => !o)}
+        className="flex items-center gap-1.5 px-2 py-1 text-xs text-amber-600 hover:bg-amber-50 rounded transition-colors"
+        title="Some offline changes could not be synced"
+      >
+        <AlertTriangle size={14} />
+        <span className="hidden sm:inline">{unsynced.length} not synced</span>
+      </button>
+      {open && (
+        <div className="absolute right-0 top-full mt-1 w-72 bg-white border border-gray-200 rounded-lg shadow-lg z-50 py-1">
+          {unsynced.map((entry) => (
+            <div key={entry.id} className="px-3 py-2 border-b border-gray-100 last:border-b-0">
+              <div className="text-sm font-medium text-gray-700 truncate">{describeEntry(entry)}</div>
+              {entry.error && <div className="text-xs text-gray-500 truncate">{entry.error}</div>}
+              <div className="flex gap-2 mt-1.5">
+                <button
+                  onClick={() => retry(entry.id!)}
+                  className="text-xs text-gray-600 hover:text-gray-900"
+                >
+                  Retry
+                </button>
+                {entry.server && entry.action !== 'create' && (
+                  <button
+                    onClick={() => retry(entry.id!, true)}
+                    className="text-xs text-gray-600 hover:text-gray-900"
+                  >
+                    Overwrite
+                  </button>
+                )}
+                <button
+                  onClick={() => discard(entry.id!)}
+                  className="text-xs text-red-500 hover:text-red-700"
+                >
+                  Discard
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  )
+}
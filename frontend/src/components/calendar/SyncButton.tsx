@@ -1,20 +1,24 @@
 import { RefreshCw } from 'lucide-react'
 import { useEventsContext } from '../../contexts/EventsContext'
+import { useSyncStore } from '../../stores'
 
 export function SyncButton() {
   const { isSyncing, sync } = useEventsContext()
+  const stopSync = useSyncStore((state) => state.stopSync)
 
   const handleSync = () => {
-    if (isSyncing) return
+    if (isSyncing) {
+      stopSync()
+      return
+    }
     sync()
   }
 
   return (
     <button
       onClick={handleSync}
-      disabled={isSyncing}
-      className="flex items-center gap-1.5 px-2 py-1 text-xs text-gray-500 hover:text-gray-700 hover:bg-gray-50 rounded transition-colors disabled:opacity-50"
-      title="Sync calendars"
+      className="flex items-center gap-1.5 px-2 py-1 text-xs text-gray-500 hover:text-gray-700 hover:bg-gray-50 rounded transition-colors"
+      title={isSyncing ? 'Stop sync' : 'Sync calendars'}
     >
       <RefreshCw
         size={14}
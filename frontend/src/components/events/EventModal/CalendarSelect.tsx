@@ -0,0 +1,34 @@
+import type { GoogleCalendar } from "../../../types";
+
+interface CalendarSelectProps {
+  calendars: GoogleCalendar[];
+  currentCalendarId: string;
+  onMove: (destinationCalendarId: string) => void;
+  disabled?: boolean;
+}
+
+export function CalendarSelect({ calendars, currentCalendarId, onMove, disabled }: CalendarSelectProps) {
+  const writable = calendars.filter(
+    (calendar) => calendar.id === currentCalendarId || calendar.access_role !== "reader",
+  );
+  if (writable.length < 2) return null;
+
+  return (
+    <select
+      value={currentCalendarId}
+      disabled={disabled}
+      onChange={(e) => {
+        if (e.target.value !== currentCalendarId) onMove(e.target.value);
+      }}
+      title="Move to calendar"
+      className="max-w-[140px] truncate rounded border border-gray-200 bg-white px-1.5 py-0.5 text-xs text-gray-600 hover:bg-gray-50"
+    >
+      {writable.map((calendar) => (
+        <option key={calendar.id} value={calendar.id}>
+          {calendar.name}
+          {calendar.account_email ? ` (${calendar.account_email})` : ""}
+        </option>
+      ))}
+    </select>
+  );
+}
@@ -0,0 +1,42 @@
+interface EventConflictDialogProps {
+  action: "edit" | "delete";
+  onOverwrite: () => void;
+  onMerge: () => void;
+  onDiscard: () => void;
+}
+
+export function EventConflictDialog({
+  action,
+  onOverwrite,
+  onMerge,
+  onDiscard,
+}: EventConflictDialogProps) {
+  const options = action === "edit"
+    ? [
+        { label: "Review their changes and merge mine", onClick: onMerge },
+        { label: "Overwrite with my changes", onClick: onOverwrite },
+        { label: "Keep their version", onClick: onDiscard },
+      ]
+    : [
+        { label: "Delete anyway", onClick: onOverwrite },
+        { label: "Keep the updated event", onClick: onDiscard },
+      ];
+
+  return (
+    <div className="flex flex-col gap-1 py-1">
+      <span className="px-3 text-xs font-medium text-gray-500">
+        This event was changed on another device
+      </span>
+      {options.map(({ label, onClick }) => (
+        <button
+          key={label}
+          type="button"
+          onClick={onClick}
+          className="w-full px-3 py-2 text-left text-sm font-medium text-gray-700 hover:bg-gray-50 rounded-lg transition-colors"
+        >
+          {label}
+        </button>
+      ))}
+    </div>
+  );
+}
@@ -18,6 +18,8 @@ import {
 import {
   useCreateEvent,
   useUpdateEvent,
+  useSplitSeries,
+  useMoveEvent,
   useDeleteEvent,
   useToggleEventCompletion,
 } from "../../../hooks";
@@ -38,15 +40,19 @@ import { ColorPicker } from "./ColorPicker";
 import { ReminderPicker } from "./ReminderPicker";
 import { RsvpButton } from "./RsvpButton";
 import { DeleteButton } from "./DeleteButton";
+import { CalendarSelect } from "./CalendarSelect";
 import { RecurrenceScopeDialog } from "./RecurrenceScopeDialog";
+import { EventConflictDialog } from "./EventConflictDialog";
 
 const LOCAL_TIMEZONE = Intl.DateTimeFormat().resolvedOptions().timeZone;
 
 export function EventModal() {
-  const { selectedEventId, selectEvent } =
+  const { selectedEventId, selectEvent, eventConflict, setEventConflict } =
     useCalendarStore();
   const createEvent = useCreateEvent();
   const updateEvent = useUpdateEvent();
+  const splitSeries = useSplitSeries();
+  const moveEvent = useMoveEvent();
   const deleteEvent = useDeleteEvent();
   const toggleCompletion = useToggleEventCompletion();
   const { events } = useEventsContext();
@@ -338,9 +344,10 @@ export function EventModal() {
     setShowDeleteConfirm(false);
     setScopeAction(null);
     pendingFormDataRef.current = null;
+    setEventConflict(null);
     selectEvent(null);
     form.reset();
-  }, [selectEvent, form]);
+  }, [selectEvent, setEventConflict, form]);
 
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
@@ -406,6 +413,21 @@ export function EventModal() {
     const calendarId = existingEvent?.googleCalendarId || data.calendarId || defaultCalendarId;
     if (!calendarId) return;
     const eventData = prepareEventData(data);
+    if (scope === "following" && existingEvent) {
+      const instanceStart = existingEvent.isVirtual
+        ? existingEvent.start
+        : existingEvent.originalStartTime ?? existingEvent.start;
+      const { recurrence, ...fields } = eventData;
+      const recurrenceChanged = !!form.formState.dirtyFields.recurrence && !!recurrence?.length;
+      splitSeries.mutate({
+        googleCalendarId: calendarId,
+        masterEventId: masterId,
+        instanceStart,
+        event: recurrenceChanged ? { ...fields, recurrence } : fields,
+      });
+      handleClose();
+      return;
+    }
     const eventId = resolveEventIdForScope(scope);
     updateEvent.mutate({
       googleCalendarId: calendarId,
@@ -414,7 +436,7 @@ export function EventModal() {
       currentEvent: existingEvent,
     });
     handleClose();
-  }, [existingEvent, defaultCalendarId, prepareEventData, updateEvent, resolveEventIdForScope, handleClose]);
+  }, [existingEvent, defaultCalendarId, prepareEventData, updateEvent, splitSeries, masterId, form.formState.dirtyFields.recurrence, resolveEventIdForScope, handleClose]);
 
   const handleSubmit = form.handleSubmit((data: EventFormData) => {
     const calendarId = data.calendarId || defaultCalendarId;
@@ -444,6 +466,19 @@ export function EventModal() {
     handleClose();
   });
 
+  const canMove = !!existingEvent && !existingEvent.isVirtual && !existingEvent.recurringEventId;
+
+  const handleMove = (destinationCalendarId: string) => {
+    if (!existingEvent) return;
+    moveEvent.mutate({
+      googleCalendarId: existingEvent.googleCalendarId,
+      eventId: existingEvent.googleEventId,
+      destinationCalendarId,
+      currentEvent: existingEvent,
+    });
+    handleClose();
+  };
+
   const handleDeleteClick = (e: React.MouseEvent) => {
     e.preventDefault();
     if (isRecurringInstance) {
@@ -457,7 +492,7 @@ export function EventModal() {
     const calendarId = existingEvent?.googleCalendarId || defaultCalendarId;
     if (!calendarId) return;
     const eventId = resolveEventIdForScope(scope);
-    deleteEvent.mutate({ googleCalendarId: calendarId, eventId });
+    deleteEvent.mutate({ googleCalendarId: calendarId, eventId, currentEvent: existingEvent });
     handleClose();
   }, [existingEvent, defaultCalendarId, deleteEvent, resolveEventIdForScope, handleClose]);
 
@@ -465,7 +500,7 @@ export function EventModal() {
     if (!isNew && activeEventId) {
       const calendarId = existingEvent?.googleCalendarId || defaultCalendarId;
       if (!calendarId) return;
-      deleteEvent.mutate({ googleCalendarId: calendarId, eventId: activeEventId });
+      deleteEvent.mutate({ googleCalendarId: calendarId, eventId: activeEventId, currentEvent: existingEvent });
       setShowDeleteConfirm(false);
       handleClose();
     }
@@ -481,6 +516,36 @@ export function EventModal() {
     pendingFormDataRef.current = null;
   }, [scopeAction, submitWithScope, deleteWithScope]);
 
+  const activeConflict =
+    eventConflict && eventConflict.eventId === activeEventId ? eventConflict : null;
+
+  const handleConflictOverwrite = useCallback(() => {
+    if (!activeConflict) return;
+    const { action, googleCalendarId, eventId, local } = activeConflict;
+    if (action === "edit" && local) {
+      updateEvent.mutate({ googleCalendarId, eventId, event: local, currentEvent: existingEvent, force: true });
+    } else if (action === "delete") {
+      deleteEvent.mutate({ googleCalendarId, eventId, force: true });
+    }
+    handleClose();
+  }, [activeConflict, existingEvent, updateEvent, deleteEvent, handleClose]);
+
+  const handleConflictMerge = useCallback(() => {
+    if (!activeConflict?.local) return;
+    const fields = form.getValues();
+    for (const [key, value] of Object.entries(activeConflict.local)) {
+      if (key in fields && value !== undefined) {
+        form.setValue(key as keyof EventFormData, value as never, { shouldDirty: true });
+      }
+    }
+    setIsEditing(true);
+    setEventConflict(null);
+  }, [activeConflict, form, setEventConflict]);
+
+  const handleConflictDiscard = useCallback(() => {
+    setEventConflict(null);
+  }, [setEventConflict]);
+
   const handleScopeCancel = useCallback(() => {
     setScopeAction(null);
     pendingFormDataRef.current = null;
@@ -600,7 +665,16 @@ export function EventModal() {
           customRecurrenceRef={customRecurrenceRef}
         />
 
-        {scopeAction ? (
+        {activeConflict ? (
+          <div className="px-2 py-1">
+            <EventConflictDialog
+              action={activeConflict.action}
+              onOverwrite={handleConflictOverwrite}
+              onMerge={handleConflictMerge}
+              onDiscard={handleConflictDiscard}
+            />
+          </div>
+        ) : scopeAction ? (
           <div className="px-2 py-1">
             <RecurrenceScopeDialog
               action={scopeAction}
@@ -642,6 +716,14 @@ export function EventModal() {
             </div>
             {!isNew && (
               <div className="flex items-center gap-2">
+                {existingEvent && canMove && calendars && (
+                  <CalendarSelect
+                    calendars={calendars}
+                    currentCalendarId={existingEvent.googleCalendarId}
+                    onMove={handleMove}
+                    disabled={moveEvent.isPending}
+                  />
+                )}
                 <RsvpButton
                   isOpen={rsvpOpen}
                   onToggle={() => setRsvpOpen((o) => !o)}
@@ -1,6 +1,8 @@
 export {
   useCreateEvent,
   useUpdateEvent,
+  useSplitSeries,
+  useMoveEvent,
   useDeleteEvent,
   useToggleEventCompletion,
 } from './useEvents'
@@ -10,3 +12,4 @@ export { useClickOutside } from './useClickOutside'
 export { useEventsLive } from './useEventsLive'
 export { useCalendarSync } from './useCalendarSync'
 export { useContactsHydrate, useContactSearch } from './useContacts'
+export { useOutbox, useOutboxReplay } from './useOutbox'
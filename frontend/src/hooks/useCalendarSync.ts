@@ -1,10 +1,17 @@
 import { useCallback, useEffect, useRef, useState } from "react";
+import { useQueryClient } from "@tanstack/react-query";
 import {
   db,
   upsertEvents,
   setLastSyncAt,
   getLastSyncAt,
   completionToDexie,
+  getEventsSyncedThrough,
+  setEventsSyncedThrough,
+  replaceCalendarEvents,
+  applyEventsDelta,
+  removeCalendarData,
+  type DexieCompletion,
 } from "../lib/db";
 import type { CalendarEvent } from "../types";
 import { useSyncStore } from "../stores";
@@ -16,17 +23,42 @@ import {
   withAuthSignal,
 } from "../api/client";
 import { googleApi } from "../api/google";
+import { googleKeys, listKeys, todoKeys } from "../lib/queryKeys";
+import {
+  electSyncLeader,
+  openSyncChannel,
+  type SyncChannel,
+  type SyncMessage,
+  type SyncProgress,
+} from "../lib/syncLeader";
 import { hydrateContacts } from "./useContacts";
+import { useLiveUpdates } from "./useLiveUpdates";
 
 const POLL_INTERVAL_MS = 10 * 60 * 1000;
 const MAX_FOREGROUND_SYNC_ATTEMPTS = 5;
 const FOREGROUND_SYNC_RETRY_DELAY_MS = 1000;
+const MAX_STREAM_RESUME_ATTEMPTS = 3;
+const STREAM_RESUME_DELAY_MS = 1000;
+
+interface SSESessionPayload {
+  session_id: string;
+}
+
+interface SSECalendarPayload {
+  calendar_id: string;
+}
 
 interface SSEEventsPayload {
   calendar_id: string;
   events: CalendarEvent[];
 }
 
+interface SyncRequest {
+  calendarIds: string[];
+  resolve: () => void;
+  reject: (reason: Error) => void;
+}
+
 interface UseCalendarSyncOptions {
   calendarIds: string[];
   enabled?: boolean;
@@ -36,13 +68,10 @@ interface UseCalendarSyncOptions {
 interface UseCalendarSyncResult {
   isLoading: boolean;
   isSyncing: boolean;
+  isLeader: boolean;
   error: string | null;
   lastSyncAt: Date | null;
-  progress: {
-    eventsLoaded: number;
-    calendarsComplete: number;
-    totalCalendars: number;
-  };
+  progress: SyncProgress;
   sync: () => Promise<void>;
 }
 
@@ -53,7 +82,8 @@ export function useCalendarSync({
 }: UseCalendarSyncOptions): UseCalendarSyncResult {
   const [isLoading, setIsLoading] = useState(true);
   const [lastSyncAt, setLastSyncAtState] = useState<Date | null>(null);
-  const [progress, setProgress] = useState({
+  const [isLeader, setIsLeader] = useState(false);
+  const [progress, setProgress] = useState<SyncProgress>({
     eventsLoaded: 0,
     calendarsComplete: 0,
     totalCalendars: 0,
@@ -68,6 +98,7 @@ export function useCalendarSync({
     resetStopFlag,
   } = useSyncStore();
   const isSyncing = isSyncingFn();
+  const queryClient = useQueryClient();
 
   const pollRef = useRef<ReturnType<typeof setInterval> | null>(null);
   const initKeyRef = useRef<string | null>(null);
@@ -75,10 +106,22 @@ export function useCalendarSync({
   const lastKnownSyncRef = useRef<number>(0);
   const smartPollRef = useRef<ReturnType<typeof setInterval> | null>(null);
   const syncAbortControllerRef = useRef<AbortController | null>(null);
+  const syncSessionIdRef = useRef<string | null>(null);
   const syncPromiseRef = useRef<Promise<void> | null>(null);
+  const syncingIdsRef = useRef<string[]>([]);
+  const syncRequestsRef = useRef<SyncRequest[]>([]);
   const rejectSyncRef = useRef<((reason: Error) => void) | null>(null);
+  const channelRef = useRef<SyncChannel | null>(null);
+  const isLeaderRef = useRef(false);
 
   calendarIdsRef.current = calendarIds;
+  isLeaderRef.current = isLeader;
+
+  const broadcast = useCallback((message: SyncMessage) => {
+    if (isLeaderRef.current) {
+      channelRef.current?.post(message);
+    }
+  }, []);
 
   const processEvents = useCallback(async (payload: SSEEventsPayload) => {
     if (payload.events.length > 0) {
@@ -93,16 +136,61 @@ export function useCalendarSync({
     }
   }, []);
 
+  const cancelServerSync = useCallback(() => {
+    const sessionId = syncSessionIdRef.current;
+    syncSessionIdRef.current = null;
+    if (sessionId) {
+      void googleApi.cancelSync(sessionId).catch(() => {});
+    }
+  }, []);
+
   const resetInFlightSync = useCallback(() => {
     syncAbortControllerRef.current = null;
+    syncSessionIdRef.current = null;
     syncPromiseRef.current = null;
     rejectSyncRef.current = null;
   }, []);
 
-  const sync = useCallback(async () => {
-    const ids = calendarIdsRef.current;
+  const hydrateFromSupabase = useCallback(async (ids: string[]) => {
+    const updatedSince = await getEventsSyncedThrough(ids);
+    const allEvents: CalendarEvent[] = [];
+    const dexieCompletions: DexieCompletion[] = [];
+    let serverTime: string | null = null;
+    let cursor: string | undefined;
+
+    do {
+      const response = await googleApi.getEvents(ids, { updatedSince, cursor });
+      serverTime ??= response.serverTime;
+      allEvents.push(
+        ...response.events,
+        ...response.masters,
+        ...response.exceptions,
+      );
+      dexieCompletions.push(...(response.completions ?? []).map(completionToDexie));
+      cursor = response.nextCursor ?? undefined;
+    } while (cursor);
+
+    if (updatedSince) {
+      await applyEventsDelta(ids, allEvents, dexieCompletions);
+    } else {
+      await replaceCalendarEvents(ids, allEvents, dexieCompletions);
+    }
+    if (serverTime) {
+      await setEventsSyncedThrough(ids, serverTime);
+    }
+
+    return { count: allEvents.length };
+  }, []);
+
+  const sync = useCallback(async (requestedIds: string[] = []) => {
+    const ids = [...new Set([...calendarIdsRef.current, ...requestedIds])];
     if (!ids.length) return;
-    if (syncPromiseRef.current) return syncPromiseRef.current;
+    // A running sync only answers the request if it covers every calendar asked for.
+    while (syncPromiseRef.current) {
+      const running = syncPromiseRef.current;
+      if (ids.every((id) => syncingIdsRef.current.includes(id))) return running;
+      await running.catch(() => {});
+    }
 
     const failSync = (
       reject: (reason: Error) => void,
@@ -112,6 +200,7 @@ export function useCalendarSync({
       if (uiError) {
         setError(uiError);
       }
+      broadcast({ type: "error", calendarIds: ids, message: uiError });
       completeSync();
       resetInFlightSync();
       reject(new Error(reason));
@@ -119,7 +208,9 @@ export function useCalendarSync({
 
     try {
       closeSyncStream();
+      syncingIdsRef.current = ids;
       startSync(ids);
+      broadcast({ type: "started", calendarIds: ids });
       setProgress({
         eventsLoaded: 0,
         calendarsComplete: 0,
@@ -136,8 +227,20 @@ export function useCalendarSync({
         let shouldRetry = false;
         let completed = false;
         let csrfTokenOverride = getCsrfToken();
+        let lastEventId: string | null = null;
+        let resumeAttempts = 0;
+        const completedCalendarIds = new Set<string>();
+        const resumedCalendarIds = new Set<string>();
 
         const processEvent = async (eventName: string, data: string) => {
+          if (eventName === "session") {
+            try {
+              const payload: SSESessionPayload = JSON.parse(data);
+              syncSessionIdRef.current = payload.session_id;
+            } catch {}
+            return;
+          }
+
           if (eventName === "events") {
             try {
               const payload: SSEEventsPayload = JSON.parse(data);
@@ -156,6 +259,10 @@ export function useCalendarSync({
           }
 
           if (eventName === "sync_token") {
+            try {
+              const payload: SSECalendarPayload = JSON.parse(data);
+              completedCalendarIds.add(payload.calendar_id);
+            } catch {}
             calendarsComplete++;
             setProgress((p) => ({ ...p, calendarsComplete }));
             return;
@@ -193,6 +300,9 @@ export function useCalendarSync({
                 ? new Date(payload.last_sync_at)
                 : new Date();
               void (async () => {
+                if (resumedCalendarIds.size > 0) {
+                  await hydrateFromSupabase([...resumedCalendarIds]);
+                }
                 await setLastSyncAt(syncedAt);
                 setLastSyncAtState(syncedAt);
                 lastKnownSyncRef.current = syncedAt.getTime();
@@ -201,10 +311,13 @@ export function useCalendarSync({
                   calendarsComplete: payload.calendars_synced,
                   totalCalendars: ids.length,
                 });
+                broadcast({ type: "complete", calendarIds: ids, lastSyncAt: syncedAt.toISOString() });
+                queryClient.invalidateQueries({ queryKey: googleKeys.syncStatus() });
                 completeSync();
                 resetInFlightSync();
                 resolve();
               })().catch((err) => {
+                broadcast({ type: "error", calendarIds: ids });
                 completeSync();
                 resetInFlightSync();
                 reject(
@@ -215,6 +328,7 @@ export function useCalendarSync({
               });
               return;
             } catch (err) {
+              broadcast({ type: "error", calendarIds: ids });
               completeSync();
               resetInFlightSync();
               reject(
@@ -226,9 +340,29 @@ export function useCalendarSync({
           }
         };
 
+        const resumeOrFail = async () => {
+          const pendingIds = ids.filter((id) => !completedCalendarIds.has(id));
+          if (
+            lastEventId === null ||
+            pendingIds.length === 0 ||
+            resumeAttempts >= MAX_STREAM_RESUME_ATTEMPTS
+          ) {
+            failSync(reject, "SSE connection closed", "Connection lost");
+            return;
+          }
+          resumeAttempts += 1;
+          for (const id of pendingIds) resumedCalendarIds.add(id);
+          await new Promise((r) =>
+            setTimeout(r, STREAM_RESUME_DELAY_MS * 2 ** (resumeAttempts - 1)),
+          );
+          if (abortController.signal.aborted) return;
+          await readStream(false, false, pendingIds);
+        };
+
         const readStream = async (
           hasRetriedCsrf: boolean,
           hasRetriedAuth: boolean,
+          calendarIdsToSync: string[] = ids,
         ) => {
           const requestAuthSignal = withAuthSignal();
           try {
@@ -238,11 +372,14 @@ export function useCalendarSync({
             }
 
             headers.set("Content-Type", "application/json");
+            if (lastEventId !== null) {
+              headers.set("Last-Event-ID", lastEventId);
+            }
             const response = await fetch(url, {
               method: "POST",
               credentials: "include",
               headers,
-              body: JSON.stringify({ calendar_ids: ids }),
+              body: JSON.stringify({ calendar_ids: calendarIdsToSync }),
               signal: withAuthSignal(abortController.signal),
             });
 
@@ -277,7 +414,7 @@ export function useCalendarSync({
                 });
                 if (csrfResponse.ok) {
                   csrfTokenOverride = getCsrfToken();
-                  return readStream(true, hasRetriedAuth);
+                  return readStream(true, hasRetriedAuth, calendarIdsToSync);
                 }
               }
               if (response.status === 401) {
@@ -285,7 +422,7 @@ export function useCalendarSync({
                   const refreshed = await refreshAuthSession();
                   if (refreshed) {
                     csrfTokenOverride = getCsrfToken();
-                    return readStream(hasRetriedCsrf, true);
+                    return readStream(hasRetriedCsrf, true, calendarIdsToSync);
                   }
                 }
                 notifyUnauthorizedIfActive(requestAuthSignal);
@@ -328,9 +465,14 @@ export function useCalendarSync({
               }
 
               let eventName = "message";
+              let eventId: string | null = null;
               const dataLines: string[] = [];
 
               for (const line of block.split("\n")) {
+                if (line.startsWith("id:")) {
+                  eventId = line.slice(3).trim();
+                  continue;
+                }
                 if (line.startsWith("event:")) {
                   eventName = line.slice(6).trim();
                   continue;
@@ -345,6 +487,9 @@ export function useCalendarSync({
               }
 
               await processEvent(eventName, dataLines.join("\n"));
+              if (eventId !== null) {
+                lastEventId = eventId;
+              }
             };
 
             try {
@@ -376,20 +521,20 @@ export function useCalendarSync({
                 return;
               }
 
-              failSync(reject, "SSE connection closed", "Connection lost");
+              await resumeOrFail();
               return;
             } finally {
               reader.releaseLock();
             }
 
             if (!completed && !shouldRetry && !abortController.signal.aborted) {
-              failSync(reject, "SSE connection closed", "Connection lost");
+              await resumeOrFail();
             }
           } catch (error) {
             if (abortController.signal.aborted) {
               return;
             }
-            failSync(reject, "SSE connection closed", "Connection lost");
+            await resumeOrFail();
           }
         };
 
@@ -409,29 +554,65 @@ export function useCalendarSync({
     setError,
     processEvents,
     resetInFlightSync,
+    hydrateFromSupabase,
+    broadcast,
+    queryClient,
   ]);
 
-  const hydrateFromSupabase = useCallback(async (ids: string[]) => {
-    const response = await googleApi.getEvents(ids);
-    const allEvents = [
-      ...response.events,
-      ...response.masters,
-      ...response.exceptions,
-    ];
-    const dexieCompletions = (response.completions ?? []).map(completionToDexie);
-
-    await db.transaction("rw", db.events, db.completedEvents, async () => {
-      await db.events.where("googleCalendarId").anyOf(ids).delete();
-      if (allEvents.length > 0) {
-        await db.events.bulkPut(allEvents);
-      }
-      await db.completedEvents.where("googleCalendarId").anyOf(ids).delete();
-      if (dexieCompletions.length > 0) {
-        await db.completedEvents.bulkPut(dexieCompletions);
-      }
+  const refreshTodos = useCallback(() => {
+    queryClient.invalidateQueries({ queryKey: todoKeys.all });
+    queryClient.invalidateQueries({ queryKey: listKeys.all });
+  }, [queryClient]);
+
+  const refreshCalendars = useCallback((removed: string[]) => {
+    void removeCalendarData(removed).catch(() => {});
+    queryClient.invalidateQueries({ queryKey: googleKeys.calendars() });
+  }, [queryClient]);
+
+  const isLive = useLiveUpdates(enabled && isLeader, {
+    onCalendarChanged: ({ calendar_id, events }) => {
+      const ids = calendarIdsRef.current;
+      if (!ids.includes(calendar_id)) return;
+      void (events ? upsertEvents(events) : hydrateFromSupabase(ids)).catch(() => {});
+    },
+    onCalendarsChanged: ({ removed }) => {
+      refreshCalendars(removed);
+      broadcast({ type: "calendars-changed", removed });
+    },
+    onTodosChanged: () => {
+      refreshTodos();
+      broadcast({ type: "todos-changed" });
+    },
+    onResync: () => {
+      void hydrateFromSupabase(calendarIdsRef.current).catch(() => {});
+      refreshTodos();
+      broadcast({ type: "todos-changed" });
+    },
+  });
+
+  const requestSync = useCallback(async () => {
+    if (isLeaderRef.current) {
+      return sync();
+    }
+    const channel = channelRef.current;
+    if (!channel) return;
+    const ids = calendarIdsRef.current;
+    return new Promise<void>((resolve, reject) => {
+      syncRequestsRef.current.push({ calendarIds: ids, resolve, reject });
+      channel.post({ type: "request-sync", calendarIds: ids });
     });
+  }, [sync]);
 
-    return { count: allEvents.length };
+  const settleSyncRequests = useCallback((calendarIds: string[], error?: Error) => {
+    syncRequestsRef.current = syncRequestsRef.current.filter((request) => {
+      if (!request.calendarIds.every((id) => calendarIds.includes(id))) return true;
+      if (error) {
+        request.reject(error);
+      } else {
+        request.resolve();
+      }
+      return false;
+    });
   }, []);
 
   const refreshFromSupabaseAndMaybeSync = useCallback(
@@ -465,6 +646,11 @@ export function useCalendarSync({
 
       const noDataYet = hydratedCount === 0 && existingLocalCount === 0;
 
+      if (!isLeaderRef.current) {
+        setIsLoading(noDataYet);
+        return;
+      }
+
       if (allowForegroundSync && noDataYet) {
         setIsLoading(true);
         for (
@@ -510,21 +696,97 @@ export function useCalendarSync({
   );
 
   useEffect(() => {
+    return electSyncLeader(() => setIsLeader(true));
+  }, []);
+
+  // Requests this tab sent to a leader that went away are now its own to run.
+  useEffect(() => {
+    if (!isLeader) return;
+    const requests = syncRequestsRef.current;
+    syncRequestsRef.current = [];
+    for (const request of requests) {
+      sync(request.calendarIds).then(request.resolve, request.reject);
+    }
+  }, [isLeader, sync]);
+
+  useEffect(() => {
+    const channel = openSyncChannel((message) => {
+      if (isLeaderRef.current) {
+        if (message.type === "request-sync") {
+          void sync(message.calendarIds).catch(() => {});
+        } else if (message.type === "stop") {
+          useSyncStore.getState().stopSync();
+        }
+        return;
+      }
+
+      switch (message.type) {
+        case "started":
+          startSync(message.calendarIds);
+          break;
+        case "progress":
+          setProgress(message.progress);
+          if (message.progress.eventsLoaded > 0) setIsLoading(false);
+          break;
+        case "complete": {
+          const syncedAt = new Date(message.lastSyncAt);
+          setLastSyncAtState(syncedAt);
+          lastKnownSyncRef.current = syncedAt.getTime();
+          setIsLoading(false);
+          completeSync();
+          settleSyncRequests(message.calendarIds);
+          break;
+        }
+        case "error":
+          if (message.message) setError(message.message);
+          completeSync();
+          settleSyncRequests(message.calendarIds, new Error(message.message ?? "Sync failed"));
+          break;
+        case "todos-changed":
+          refreshTodos();
+          break;
+        case "calendars-changed":
+          queryClient.invalidateQueries({ queryKey: googleKeys.calendars() });
+          break;
+      }
+    });
+    channelRef.current = channel;
     return () => {
+      channelRef.current = null;
+      channel.close();
+    };
+  }, [sync, startSync, completeSync, setError, refreshTodos, settleSyncRequests, queryClient]);
+
+  useEffect(() => {
+    if (isSyncing) broadcast({ type: "progress", progress });
+  }, [isSyncing, progress, broadcast]);
+
+  useEffect(() => {
+    return () => {
+      cancelServerSync();
       closeSyncStream();
       if (rejectSyncRef.current) {
         rejectSyncRef.current(new Error("Component unmounted"));
         rejectSyncRef.current = null;
       }
       syncPromiseRef.current = null;
+      for (const request of syncRequestsRef.current) {
+        request.reject(new Error("Component unmounted"));
+      }
+      syncRequestsRef.current = [];
     };
-  }, [closeSyncStream]);
+  }, [cancelServerSync, closeSyncStream]);
 
   useEffect(() => {
     if (!shouldStop) return;
 
+    if (!isLeaderRef.current) {
+      channelRef.current?.post({ type: "stop" });
+    }
+    cancelServerSync();
     closeSyncStream();
     if (rejectSyncRef.current) {
+      broadcast({ type: "error", calendarIds: syncingIdsRef.current });
       rejectSyncRef.current(new Error("Sync stopped"));
       rejectSyncRef.current = null;
     }
@@ -538,12 +800,12 @@ export function useCalendarSync({
       smartPollRef.current = null;
     }
     resetStopFlag();
-  }, [shouldStop, resetStopFlag, closeSyncStream]);
+  }, [shouldStop, resetStopFlag, cancelServerSync, closeSyncStream, broadcast]);
 
   useEffect(() => {
     if (!enabled || !calendarIds.length) return;
 
-    const nextInitKey = [...calendarIds].sort().join(",");
+    const nextInitKey = `${isLeader ? "leader" : "follower"}:${[...calendarIds].sort().join(",")}`;
     if (initKeyRef.current === nextInitKey) return;
     initKeyRef.current = nextInitKey;
 
@@ -561,10 +823,10 @@ export function useCalendarSync({
       setError(error instanceof Error ? error.message : "Sync failed");
       setIsLoading(false);
     });
-  }, [enabled, calendarIds, refreshFromSupabaseAndMaybeSync]);
+  }, [enabled, isLeader, calendarIds, refreshFromSupabaseAndMaybeSync]);
 
   useEffect(() => {
-    if (!enabled || !calendarIds.length || pollInterval <= 0) return;
+    if (!enabled || !isLeader || isLive || !calendarIds.length || pollInterval <= 0) return;
 
     pollRef.current = setInterval(() => {
       void sync().catch((error) => {
@@ -581,10 +843,10 @@ export function useCalendarSync({
         pollRef.current = null;
       }
     };
-  }, [calendarIds.length, enabled, pollInterval, sync]);
+  }, [calendarIds.length, enabled, isLeader, isLive, pollInterval, sync]);
 
   useEffect(() => {
-    if (!enabled || !calendarIds.length) return;
+    if (!enabled || !isLeader || isLive || !calendarIds.length) return;
 
     smartPollRef.current = setInterval(async () => {
       if (syncPromiseRef.current) return;
@@ -611,14 +873,15 @@ export function useCalendarSync({
         smartPollRef.current = null;
       }
     };
-  }, [enabled, calendarIds, hydrateFromSupabase]);
+  }, [enabled, isLeader, isLive, calendarIds, hydrateFromSupabase]);
 
   return {
     isLoading,
     isSyncing,
+    isLeader,
     error,
     lastSyncAt,
     progress,
-    sync,
+    sync: requestSync,
   };
 }
@@ -1,9 +1,18 @@
 import { QueryClient, useMutation, useQueryClient } from "@tanstack/react-query";
 import { toast } from "sonner";
-import { eventsApi } from "../api/events";
-import type { CalendarEvent, EventCompletion } from "../types";
+import { eventsApi, getEventConflict } from "../api/events";
+import type { CalendarEvent, EventCompletion, EventDateTime } from "../types";
 import { eventKeys } from "../lib";
 import { completionToDexie, db } from "../lib/db";
+import {
+  createTempId,
+  enqueueMutation,
+  hasQueuedMutations,
+  isOfflineError,
+} from "../lib/outbox";
+import { useCalendarStore } from "../stores";
+
+const OFFLINE_MESSAGE = "Saved offline. Changes will sync when you reconnect";
 
 type PreviousLists = [unknown, CalendarEvent[] | undefined][];
 
@@ -33,6 +42,32 @@ async function upsertEvent(event: CalendarEvent): Promise<void> {
   });
 }
 
+function knownEtag(eventId: string, currentEvent?: CalendarEvent, force?: boolean): string | undefined {
+  if (force || currentEvent?.googleEventId !== eventId) return undefined;
+  return currentEvent.etag;
+}
+
+async function reportConflict(
+  error: unknown,
+  action: "edit" | "delete",
+  googleCalendarId: string,
+  eventId: string,
+  local?: Partial<CalendarEvent>,
+): Promise<boolean> {
+  const conflict = getEventConflict(error);
+  if (!conflict) return false;
+  if (conflict.current) {
+    await upsertEvent(conflict.current);
+  }
+  const { setEventConflict, selectEvent } = useCalendarStore.getState();
+  setEventConflict({ action, googleCalendarId, eventId, local, server: conflict.current });
+  if (conflict.current && conflict.current.status !== "cancelled") {
+    selectEvent(eventId);
+  }
+  toast.error("This event was changed on another device");
+  return true;
+}
+
 export function useCreateEvent() {
   const queryClient = useQueryClient();
 
@@ -45,9 +80,10 @@ export function useCreateEvent() {
       event: Partial<CalendarEvent>;
       calendarColor?: string;
     }) => eventsApi.create(googleCalendarId, event),
+    networkMode: "always",
     onMutate: async ({ googleCalendarId, event, calendarColor }) => {
       const previousLists = await cancelAndSnapshot(queryClient);
-      const tempId = `temp-${Date.now()}`;
+      const tempId = createTempId();
       const now = new Date().toISOString();
 
       await db.events.add({
@@ -72,7 +108,18 @@ export function useCreateEvent() {
 
       return { tempId, previousLists };
     },
-    onError: async (_, { googleCalendarId }, context) => {
+    onError: async (error, { googleCalendarId, event }, context) => {
+      if (context?.tempId && isOfflineError(error)) {
+        await enqueueMutation({
+          entity: "event",
+          action: "create",
+          entityId: context.tempId,
+          googleCalendarId,
+          payload: event,
+        });
+        toast(OFFLINE_MESSAGE);
+        return;
+      }
       if (context?.previousLists) {
         restoreLists(queryClient, context.previousLists);
       }
@@ -101,16 +148,28 @@ export function useUpdateEvent() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({
+    mutationFn: async ({
       googleCalendarId,
       eventId,
       event,
+      currentEvent,
+      force,
     }: {
       googleCalendarId: string;
       eventId: string;
       event: Partial<CalendarEvent>;
       currentEvent?: CalendarEvent;
-    }) => eventsApi.update(googleCalendarId, eventId, event),
+      force?: boolean;
+    }) => {
+      const etag = knownEtag(eventId, currentEvent, force);
+      if (await hasQueuedMutations(eventId)) {
+        await enqueueMutation({ entity: "event", action: "update", entityId: eventId, googleCalendarId, payload: event, etag });
+        const base = currentEvent ?? (await findEvent(googleCalendarId, eventId));
+        return { ...base, ...event } as CalendarEvent;
+      }
+      return eventsApi.update(googleCalendarId, eventId, event, etag);
+    },
+    networkMode: "always",
     onMutate: async ({ googleCalendarId, eventId, event, currentEvent }) => {
       const previousLists = await cancelAndSnapshot(queryClient);
       await queryClient.cancelQueries({
@@ -153,7 +212,19 @@ export function useUpdateEvent() {
         previousEvent,
       };
     },
-    onError: (_, __, context) => {
+    onError: async (error, { googleCalendarId, eventId, event, currentEvent, force }, context) => {
+      if (isOfflineError(error)) {
+        await enqueueMutation({
+          entity: "event",
+          action: "update",
+          entityId: eventId,
+          googleCalendarId,
+          payload: event,
+          etag: knownEtag(eventId, currentEvent, force),
+        });
+        toast(OFFLINE_MESSAGE);
+        return;
+      }
       if (context?.previousDetail) {
         queryClient.setQueryData(
           eventKeys.detail(context.googleCalendarId, context.eventId),
@@ -161,6 +232,7 @@ export function useUpdateEvent() {
         );
       }
       restoreLists(queryClient, context?.previousLists ?? []);
+      if (await reportConflict(error, "edit", googleCalendarId, eventId, event)) return;
       if (context?.previousEvent) {
         db.events.put(context.previousEvent).catch((e) => {
           console.error("Dexie rollback failed:", e);
@@ -193,17 +265,95 @@ export function useUpdateEvent() {
   });
 }
 
-export function useDeleteEvent() {
+export function useSplitSeries() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({
+      googleCalendarId,
+      masterEventId,
+      instanceStart,
+      event,
+    }: {
+      googleCalendarId: string;
+      masterEventId: string;
+      instanceStart: EventDateTime;
+      event: Partial<CalendarEvent>;
+    }) => eventsApi.splitSeries(googleCalendarId, masterEventId, instanceStart, event),
+    onError: () => {
+      toast.error("Failed to update following events");
+    },
+    onSuccess: async ({ master, series, exceptions }) => {
+      await db.transaction("rw", db.events, async () => {
+        for (const event of [master, ...(series ? [series] : []), ...exceptions]) {
+          await upsertEvent(event);
+        }
+      });
+    },
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: eventKeys.lists() });
+    },
+  });
+}
+
+export function useMoveEvent() {
   const queryClient = useQueryClient();
 
   return useMutation({
     mutationFn: ({
       googleCalendarId,
       eventId,
+      destinationCalendarId,
+      currentEvent,
     }: {
       googleCalendarId: string;
       eventId: string;
-    }) => eventsApi.delete(googleCalendarId, eventId),
+      destinationCalendarId: string;
+      currentEvent?: CalendarEvent;
+    }) =>
+      eventsApi.move(googleCalendarId, eventId, destinationCalendarId, knownEtag(eventId, currentEvent)),
+    onError: async (error, { googleCalendarId, eventId }) => {
+      if (await reportConflict(error, "edit", googleCalendarId, eventId)) return;
+      toast.error("Failed to move event");
+    },
+    onSuccess: async (moved, { googleCalendarId, eventId }) => {
+      await db.transaction("rw", db.events, async () => {
+        const previous = await findEvent(googleCalendarId, eventId);
+        if (previous) {
+          await db.events.delete(previous.uuid!);
+        }
+        await upsertEvent(moved);
+      });
+    },
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: eventKeys.lists() });
+    },
+  });
+}
+
+export function useDeleteEvent() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: async ({
+      googleCalendarId,
+      eventId,
+      currentEvent,
+      force,
+    }: {
+      googleCalendarId: string;
+      eventId: string;
+      currentEvent?: CalendarEvent;
+      force?: boolean;
+    }) => {
+      const etag = knownEtag(eventId, currentEvent, force);
+      if (await hasQueuedMutations(eventId)) {
+        await enqueueMutation({ entity: "event", action: "delete", entityId: eventId, googleCalendarId, etag });
+        return;
+      }
+      await eventsApi.delete(googleCalendarId, eventId, etag);
+    },
+    networkMode: "always",
     onMutate: async ({ googleCalendarId, eventId }) => {
       const previousLists = await cancelAndSnapshot(queryClient);
       queryClient.setQueriesData<CalendarEvent[]>(
@@ -220,8 +370,20 @@ export function useDeleteEvent() {
 
       return { previousLists, previousEvent };
     },
-    onError: (_, __, context) => {
+    onError: async (error, { googleCalendarId, eventId, currentEvent, force }, context) => {
+      if (isOfflineError(error)) {
+        await enqueueMutation({
+          entity: "event",
+          action: "delete",
+          entityId: eventId,
+          googleCalendarId,
+          etag: knownEtag(eventId, currentEvent, force),
+        });
+        toast(OFFLINE_MESSAGE);
+        return;
+      }
       restoreLists(queryClient, context?.previousLists ?? []);
+      if (await reportConflict(error, "delete", googleCalendarId, eventId)) return;
       if (context?.previousEvent) {
         db.events.put(context.previousEvent).catch((e) => {
           console.error("Dexie rollback failed:", e);
@@ -1,8 +1,8 @@
 import { useMemo } from 'react'
-import { useQuery } from '@tanstack/react-query'
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query'
 import { googleApi } from '../api/google'
 import { googleKeys } from '../lib/queryKeys'
-import type { GoogleCalendar } from '../types'
+import type { ChronosCalendarInput, GoogleCalendar } from '../types'
 
 interface CalendarGroup {
   account: { id: string; email: string; name: string; needs_reauth: boolean }
@@ -25,6 +25,52 @@ export function useGoogleCalendars() {
   })
 }
 
+export function useCalendarSyncStatus(enabled = true) {
+  return useQuery({
+    queryKey: googleKeys.syncStatus(),
+    queryFn: () => googleApi.getSyncStatus().then((r) => r.calendars),
+    staleTime: 60 * 1000,
+    enabled,
+  })
+}
+
+export function useSetCalendarBackfill() {
+  const queryClient = useQueryClient()
+  return useMutation({
+    mutationFn: ({ calendarId, enabled }: { calendarId: string; enabled: boolean }) =>
+      googleApi.setBackfill(calendarId, enabled),
+    onSettled: () => queryClient.invalidateQueries({ queryKey: googleKeys.calendars() }),
+  })
+}
+
+export function useCreateCalendar() {
+  const queryClient = useQueryClient()
+  return useMutation({
+    mutationFn: (calendar: ChronosCalendarInput) => googleApi.createCalendar(calendar),
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: googleKeys.calendars() })
+      queryClient.invalidateQueries({ queryKey: googleKeys.accounts() })
+    },
+  })
+}
+
+export function useUpdateCalendar() {
+  const queryClient = useQueryClient()
+  return useMutation({
+    mutationFn: ({ calendarId, changes }: { calendarId: string; changes: Partial<ChronosCalendarInput> }) =>
+      googleApi.updateCalendar(calendarId, changes),
+    onSettled: () => queryClient.invalidateQueries({ queryKey: googleKeys.calendars() }),
+  })
+}
+
+export function useDeleteCalendar() {
+  const queryClient = useQueryClient()
+  return useMutation({
+    mutationFn: (calendarId: string) => googleApi.deleteCalendar(calendarId),
+    onSettled: () => queryClient.invalidateQueries({ queryKey: googleKeys.calendars() }),
+  })
+}
+
 export function useGroupedCalendars(calendars: GoogleCalendar[] | undefined) {
   return useMemo(() => {
     if (!calendars) return {}
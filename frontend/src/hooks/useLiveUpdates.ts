@@ -0,0 +1,58 @@
+import { useEffect, useRef, useState } from "react";
+import { getApiUrl } from "../api/client";
+import type { CalendarEvent } from "../types";
+
+export interface CalendarChangedPayload {
+  calendar_id: string;
+  events: CalendarEvent[] | null;
+}
+
+export interface CalendarsChangedPayload {
+  removed: string[];
+}
+
+interface LiveUpdateHandlers {
+  onCalendarChanged: (payload: CalendarChangedPayload) => void;
+  onCalendarsChanged: (payload: CalendarsChangedPayload) => void;
+  onTodosChanged: () => void;
+  onResync: () => void;
+}
+
+export function useLiveUpdates(enabled: boolean, handlers: LiveUpdateHandlers): boolean {
+  const [isConnected, setIsConnected] = useState(false);
+  const handlersRef = useRef(handlers);
+  handlersRef.current = handlers;
+
+  useEffect(() => {
+    if (!enabled || typeof EventSource === "undefined") return;
+
+    const source = new EventSource(`${getApiUrl()}/live`, { withCredentials: true });
+    let hasConnected = false;
+
+    source.addEventListener("ready", () => {
+      setIsConnected(true);
+      if (hasConnected) handlersRef.current.onResync();
+      hasConnected = true;
+    });
+    source.addEventListener("calendar_changed", (event) => {
+      try {
+        handlersRef.current.onCalendarChanged(JSON.parse((event as MessageEvent<string>).data));
+      } catch {}
+    });
+    source.addEventListener("calendars_changed", (event) => {
+      try {
+        handlersRef.current.onCalendarsChanged(JSON.parse((event as MessageEvent<string>).data));
+      } catch {}
+    });
+    source.addEventListener("todos_changed", () => handlersRef.current.onTodosChanged());
+    source.addEventListener("resync", () => handlersRef.current.onResync());
+    source.onerror = () => setIsConnected(false);
+
+    return () => {
+      source.close();
+      setIsConnected(false);
+    };
+  }, [enabled]);
+
+  return isConnected;
+}
@@ -0,0 +1,40 @@
+import { useCallback, useEffect } from "react";
+import { useQueryClient } from "@tanstack/react-query";
+import { useLiveQuery } from "dexie-react-hooks";
+import { db, type DexieOutboxEntry } from "../lib/db";
+import { discardOutboxEntry, replayOutbox, retryOutboxEntry } from "../lib/outbox";
+import { eventKeys, todoKeys } from "../lib/queryKeys";
+
+export function useOutboxReplay(): void {
+  const queryClient = useQueryClient();
+
+  const replay = useCallback(async () => {
+    const replayed = await replayOutbox();
+    if (replayed > 0) {
+      queryClient.invalidateQueries({ queryKey: eventKeys.lists() });
+      queryClient.invalidateQueries({ queryKey: todoKeys.lists() });
+    }
+  }, [queryClient]);
+
+  useEffect(() => {
+    replay();
+    window.addEventListener("online", replay);
+    return () => window.removeEventListener("online", replay);
+  }, [replay]);
+}
+
+export function useOutbox(): {
+  pending: DexieOutboxEntry[];
+  unsynced: DexieOutboxEntry[];
+  retry: (id: number, overwrite?: boolean) => Promise<number>;
+  discard: (id: number) => Promise<void>;
+} {
+  const entries = useLiveQuery(() => db.outbox.toArray(), [], []);
+
+  return {
+    pending: entries.filter((entry) => entry.status === "pending"),
+    unsynced: entries.filter((entry) => entry.status !== "pending"),
+    retry: retryOutboxEntry,
+    discard: discardOutboxEntry,
+  };
+}
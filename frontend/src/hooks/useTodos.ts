@@ -3,6 +3,13 @@ import { toast } from "sonner";
 import { todosApi } from "../api/todos";
 import { useAuth } from "../contexts/AuthContext";
 import { listKeys, todoKeys } from "../lib/queryKeys";
+import {
+  applyLocalTodo,
+  createTempId,
+  enqueueMutation,
+  hasQueuedMutations,
+  isOfflineError,
+} from "../lib/outbox";
 import type {
   Todo,
   TodoList,
@@ -12,6 +19,17 @@ import type {
   UpdateTodoListInput,
 } from "../types";
 
+const OFFLINE_MESSAGE = "Saved offline. Changes will sync when you reconnect";
+
+async function queueTodoMutation(
+  action: "update" | "delete",
+  id: string,
+  payload?: UpdateTodoInput,
+): Promise<void> {
+  await enqueueMutation({ entity: "todo", action, entityId: id, payload });
+  await applyLocalTodo(action, { id, ...payload });
+}
+
 export function useTodos(listId?: string) {
   const { user } = useAuth();
   return useQuery({
@@ -32,6 +50,7 @@ export function useCreateTodo() {
       if (!user) throw new Error("Not authenticated");
       return todosApi.createTodo(todo);
     },
+    networkMode: "always",
     onMutate: async (newTodo) => {
       await queryClient.cancelQueries({ queryKey: todoKeys.lists() });
 
@@ -50,7 +69,7 @@ export function useCreateTodo() {
       }
 
       const optimisticTodo: Todo = {
-        id: crypto.randomUUID(),
+        id: createTempId(),
         userId: user?.id || "",
         title: newTodo.title,
         completed: false,
@@ -71,9 +90,20 @@ export function useCreateTodo() {
         },
       );
 
-      return { previousTodos: allCachedTodos };
+      return { previousTodos: allCachedTodos, optimisticTodo };
     },
-    onError: (_, __, context) => {
+    onError: async (error, newTodo, context) => {
+      if (context?.optimisticTodo && isOfflineError(error)) {
+        await enqueueMutation({
+          entity: "todo",
+          action: "create",
+          entityId: context.optimisticTodo.id,
+          payload: newTodo,
+        });
+        await applyLocalTodo("create", context.optimisticTodo);
+        toast(OFFLINE_MESSAGE);
+        return;
+      }
       if (context?.previousTodos) {
         for (const [queryKey, data] of context.previousTodos) {
           queryClient.setQueryData(queryKey, data);
@@ -92,10 +122,15 @@ export function useUpdateTodo() {
   const { user } = useAuth();
 
   return useMutation({
-    mutationFn: ({ id, todo }: { id: string; todo: UpdateTodoInput }) => {
+    mutationFn: async ({ id, todo }: { id: string; todo: UpdateTodoInput }) => {
       if (!user) throw new Error("Not authenticated");
+      if (await hasQueuedMutations(id)) {
+        await queueTodoMutation("update", id, todo);
+        return null;
+      }
       return todosApi.updateTodo(id, todo);
     },
+    networkMode: "always",
     onMutate: async ({ id, todo }) => {
       await queryClient.cancelQueries({ queryKey: todoKeys.lists() });
       const previousTodos = queryClient.getQueryData(todoKeys.lists());
@@ -108,7 +143,12 @@ export function useUpdateTodo() {
 
       return { previousTodos };
     },
-    onError: (_, __, context) => {
+    onError: async (error, { id, todo }, context) => {
+      if (isOfflineError(error)) {
+        await queueTodoMutation("update", id, todo);
+        toast(OFFLINE_MESSAGE);
+        return;
+      }
       if (context?.previousTodos) {
         queryClient.setQueryData(todoKeys.lists(), context.previousTodos);
       }
@@ -125,8 +165,14 @@ export function useToggleTodo() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({ id, completed }: { id: string; completed: boolean }) =>
-      todosApi.updateTodo(id, { completed }),
+    mutationFn: async ({ id, completed }: { id: string; completed: boolean }) => {
+      if (await hasQueuedMutations(id)) {
+        await queueTodoMutation("update", id, { completed });
+        return null;
+      }
+      return todosApi.updateTodo(id, { completed });
+    },
+    networkMode: "always",
     onMutate: async ({ id, completed }) => {
       await queryClient.cancelQueries({ queryKey: todoKeys.lists() });
 
@@ -140,7 +186,12 @@ export function useToggleTodo() {
 
       return { previousTodos };
     },
-    onError: (_, __, context) => {
+    onError: async (error, { id, completed }, context) => {
+      if (isOfflineError(error)) {
+        await queueTodoMutation("update", id, { completed });
+        toast(OFFLINE_MESSAGE);
+        return;
+      }
       if (context?.previousTodos) {
         queryClient.setQueryData(todoKeys.lists(), context.previousTodos);
       }
@@ -157,7 +208,14 @@ export function useDeleteTodo() {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: (id: string) => todosApi.deleteTodo(id),
+    mutationFn: async (id: string) => {
+      if (await hasQueuedMutations(id)) {
+        await queueTodoMutation("delete", id);
+        return;
+      }
+      await todosApi.deleteTodo(id);
+    },
+    networkMode: "always",
     onMutate: async (id) => {
       await queryClient.cancelQueries({ queryKey: todoKeys.lists() });
 
@@ -170,7 +228,12 @@ export function useDeleteTodo() {
 
       return { previousTodos };
     },
-    onError: (_, __, context) => {
+    onError: async (error, id, context) => {
+      if (isOfflineError(error)) {
+        await queueTodoMutation("delete", id);
+        toast(OFFLINE_MESSAGE);
+        return;
+      }
       if (context?.previousTodos) {
         queryClient.setQueryData(todoKeys.lists(), context.previousTodos);
       }
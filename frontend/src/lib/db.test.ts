@@ -0,0 +1,42 @@
+import "fake-indexeddb/auto";
+import { beforeEach, describe, expect, it } from "vitest";
+import type { CalendarEvent } from "../types";
+import { applyEventsDelta, db, upsertEvents } from "./db";
+
+function event(overrides: Partial<CalendarEvent>): CalendarEvent {
+  return {
+    googleEventId: "evt-1",
+    googleCalendarId: "cal-1",
+    completed: false,
+    summary: "Standup",
+    start: { dateTime: "2026-03-02T09:00:00Z" },
+    end: { dateTime: "2026-03-02T09:30:00Z" },
+    status: "confirmed",
+    visibility: "default",
+    transparency: "opaque",
+    createdAt: "2026-03-01T00:00:00Z",
+    updatedAt: "2026-03-01T12:00:00Z",
+    ...overrides,
+  };
+}
+
+describe("applyEventsDelta", () => {
+  beforeEach(async () => {
+    await db.events.clear();
+    await upsertEvents([event({})]);
+  });
+
+  it("applies a server cancellation that kept the same updatedAt", async () => {
+    await applyEventsDelta(["cal-1"], [event({ status: "cancelled" })], []);
+
+    const stored = await db.events.where("[googleCalendarId+googleEventId]").equals(["cal-1", "evt-1"]).toArray();
+    expect(stored.map((e) => e.status)).toEqual(["cancelled"]);
+  });
+
+  it("still ignores stale rows outside delta mode", async () => {
+    await upsertEvents([event({ summary: "Old title", updatedAt: "2026-03-01T11:00:00Z" })]);
+
+    const stored = await db.events.toArray();
+    expect(stored.map((e) => e.summary)).toEqual(["Standup"]);
+  });
+});
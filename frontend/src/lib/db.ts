@@ -11,7 +11,7 @@ interface DexieSyncMeta {
   updatedAt: string;
 }
 
-interface DexieTodo {
+export interface DexieTodo {
   id: string;
   userId: string;
   title: string;
@@ -40,6 +40,20 @@ interface DexieTodoList {
   order: number;
 }
 
+export interface DexieOutboxEntry {
+  id?: number;
+  entity: "event" | "todo";
+  action: "create" | "update" | "delete";
+  entityId: string;
+  googleCalendarId?: string;
+  payload?: object;
+  etag?: string;
+  status: "pending" | "conflict" | "rejected";
+  error?: string;
+  server?: CalendarEvent | null;
+  createdAt: string;
+}
+
 export interface DexieContact {
   id?: number;
   email: string;
@@ -54,6 +68,7 @@ class ChronosDatabase extends Dexie {
   todoLists!: EntityTable<DexieTodoList, "id">;
   completedEvents!: EntityTable<DexieCompletion, "id">;
   contacts!: EntityTable<DexieContact, "id">;
+  outbox!: EntityTable<DexieOutboxEntry, "id">;
 
   constructor() {
     super("chronos");
@@ -105,12 +120,25 @@ class ChronosDatabase extends Dexie {
       completedEvents: "++id, [googleCalendarId+masterEventId+instanceStart], googleCalendarId",
       contacts: "++id, &email",
     });
+    this.version(8).stores({
+      events:
+        "++uuid, [googleCalendarId+googleEventId], googleCalendarId, googleAccountId, recurringEventId, [googleCalendarId+recurringEventId], recurrence",
+      syncMeta: "++id, key",
+      todos: "id, listId, userId, order",
+      todoLists: "id, userId, order",
+      completedEvents: "++id, [googleCalendarId+masterEventId+instanceStart], googleCalendarId",
+      contacts: "++id, &email",
+      outbox: "++id, status, entityId",
+    });
   }
 }
 
 export const db = new ChronosDatabase();
 
-export async function upsertEvents(events: CalendarEvent[]): Promise<void> {
+export async function upsertEvents(
+  events: CalendarEvent[],
+  { overwrite = false }: { overwrite?: boolean } = {},
+): Promise<void> {
   const keys = events.map(
     (e) => [e.googleCalendarId, e.googleEventId] as [string, string],
   );
@@ -128,7 +156,7 @@ export async function upsertEvents(events: CalendarEvent[]): Promise<void> {
         `${event.googleCalendarId}:${event.googleEventId}`,
       );
       if (!prev) return event;
-      if (prev.updatedAt && prev.updatedAt >= (event.updatedAt ?? "")) return null;
+      if (!overwrite && prev.updatedAt && prev.updatedAt >= (event.updatedAt ?? "")) return null;
       return { ...event, uuid: prev.uuid };
     })
     .filter((e): e is CalendarEvent => e !== null);
@@ -158,6 +186,64 @@ export async function setLastSyncAt(date: Date): Promise<void> {
   await setSyncMeta("lastSyncAt", date.toISOString());
 }
 
+function eventsSyncKey(calendarIds: string[]): string {
+  return `eventsSyncedThrough:${[...calendarIds].sort().join(",")}`;
+}
+
+export async function getEventsSyncedThrough(calendarIds: string[]): Promise<string | undefined> {
+  return getSyncMeta(eventsSyncKey(calendarIds));
+}
+
+export async function setEventsSyncedThrough(calendarIds: string[], serverTime: string): Promise<void> {
+  await setSyncMeta(eventsSyncKey(calendarIds), serverTime);
+}
+
+export async function replaceCalendarEvents(
+  calendarIds: string[],
+  events: CalendarEvent[],
+  completions: DexieCompletion[],
+): Promise<void> {
+  await db.transaction("rw", db.events, db.completedEvents, async () => {
+    await db.events
+      .where("googleCalendarId")
+      .anyOf(calendarIds)
+      .filter((event) => !event.googleEventId.startsWith("temp-"))
+      .delete();
+    if (events.length > 0) {
+      await db.events.bulkPut(events);
+    }
+    await db.completedEvents.where("googleCalendarId").anyOf(calendarIds).delete();
+    if (completions.length > 0) {
+      await db.completedEvents.bulkPut(completions);
+    }
+  });
+}
+
+export async function removeCalendarData(calendarIds: string[]): Promise<void> {
+  if (calendarIds.length === 0) return;
+  await db.transaction("rw", db.events, db.completedEvents, async () => {
+    await db.events.where("googleCalendarId").anyOf(calendarIds).delete();
+    await db.completedEvents.where("googleCalendarId").anyOf(calendarIds).delete();
+  });
+}
+
+export async function applyEventsDelta(
+  calendarIds: string[],
+  events: CalendarEvent[],
+  completions: DexieCompletion[],
+): Promise<void> {
+  await db.transaction("rw", db.events, db.completedEvents, async () => {
+    if (events.length > 0) {
+      // Server-side cancellations and moves only bump syncedAt, so the updatedAt guard would drop them.
+      await upsertEvents(events, { overwrite: true });
+    }
+    await db.completedEvents.where("googleCalendarId").anyOf(calendarIds).delete();
+    if (completions.length > 0) {
+      await db.completedEvents.bulkPut(completions);
+    }
+  });
+}
+
 export function completionToDexie(c: EventCompletion): DexieCompletion {
   return {
     googleCalendarId: c.google_calendar_id,
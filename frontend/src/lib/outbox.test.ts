@@ -0,0 +1,71 @@
+import "fake-indexeddb/auto";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { ApiError } from "../api/client";
+import { eventsApi } from "../api/events";
+import { db } from "./db";
+import { enqueueMutation, replayOutbox } from "./outbox";
+
+vi.mock("../api/events", async (importOriginal) => {
+  const actual = await importOriginal<typeof import("../api/events")>();
+  return { ...actual, eventsApi: { ...actual.eventsApi, update: vi.fn() } };
+});
+
+const update = vi.mocked(eventsApi.update);
+
+function queueUpdate(entityId: string, summary: string) {
+  return enqueueMutation({
+    entity: "event",
+    action: "update",
+    entityId,
+    googleCalendarId: "cal-1",
+    payload: { summary },
+    etag: '"e1"',
+  });
+}
+
+async function statuses() {
+  return (await db.outbox.orderBy("id").toArray()).map((entry) => [entry.entityId, entry.status]);
+}
+
+describe("replayOutbox", () => {
+  beforeEach(async () => {
+    vi.stubGlobal("navigator", { onLine: true });
+    update.mockReset();
+    await db.outbox.clear();
+    await db.events.clear();
+  });
+
+  it("leaves the entry pending when the server fails or the session lapsed", async () => {
+    await queueUpdate("evt-1", "Standup");
+
+    for (const status of [503, 401]) {
+      update.mockRejectedValueOnce(new ApiError("Unavailable", status));
+      expect(await replayOutbox()).toBe(0);
+    }
+
+    expect(await statuses()).toEqual([["evt-1", "pending"]]);
+  });
+
+  it("holds back later edits to an entity that conflicted", async () => {
+    await queueUpdate("evt-1", "Standup");
+    await queueUpdate("evt-1", "Standup moved");
+    await queueUpdate("evt-2", "Review");
+    update
+      .mockRejectedValueOnce(new ApiError("Event was modified", 409, { detail: { message: "Event was modified", current: null } }))
+      .mockResolvedValueOnce({ googleEventId: "evt-2", googleCalendarId: "cal-1" } as never);
+
+    expect(await replayOutbox()).toBe(1);
+
+    expect(update).toHaveBeenCalledTimes(2);
+    expect(await statuses()).toEqual([["evt-1", "conflict"], ["evt-1", "pending"]]);
+  });
+
+  it("rejects other failures instead of treating them as offline or as conflicts", async () => {
+    await queueUpdate("evt-1", "Standup");
+    update.mockRejectedValueOnce(new TypeError("payload.start is undefined"));
+
+    expect(await replayOutbox()).toBe(0);
+
+    expect(await statuses()).toEqual([["evt-1", "rejected"]]);
+  });
+});
@@ -0,0 +1,241 @@
+import { ApiError, NetworkError } from "../api/client";
+import { eventsApi, getEventConflict } from "../api/events";
+import { todosApi } from "../api/todos";
+import type { CalendarEvent, CreateTodoInput, Todo, UpdateTodoInput } from "../types";
+import { db, type DexieOutboxEntry } from "./db";
+
+const TEMP_ID_PREFIX = "temp-";
+const OUTBOX_LOCK = "chronos-outbox";
+
+type OutboxMutation = Omit<DexieOutboxEntry, "id" | "status" | "createdAt" | "error" | "server">;
+
+let replaying: Promise<number> | null = null;
+let inFlightId: number | undefined;
+
+export function createTempId(): string {
+  return `${TEMP_ID_PREFIX}${crypto.randomUUID()}`;
+}
+
+export function isTempId(id: string): boolean {
+  return id.startsWith(TEMP_ID_PREFIX);
+}
+
+export function isOfflineError(error: unknown): boolean {
+  return !navigator.onLine || error instanceof NetworkError;
+}
+
+// Worth replaying later as-is: the server was unreachable, the session lapsed or the server failed.
+function isTransientError(error: unknown): boolean {
+  if (isOfflineError(error)) return true;
+  if (!(error instanceof ApiError)) return false;
+  return error.status === 401 || error.status === 408 || error.status === 429 || error.status >= 500;
+}
+
+function isPreconditionError(error: unknown): boolean {
+  return error instanceof ApiError && (error.status === 409 || error.status === 412);
+}
+
+export async function hasQueuedMutations(entityId: string): Promise<boolean> {
+  if (isTempId(entityId)) return true;
+  return (await db.outbox.where("entityId").equals(entityId).count()) > 0;
+}
+
+export async function enqueueMutation(mutation: OutboxMutation): Promise<void> {
+  await db.transaction("rw", db.outbox, async () => {
+    const pendingCreate = isTempId(mutation.entityId)
+      ? await db.outbox
+          .where("entityId")
+          .equals(mutation.entityId)
+          .filter((entry) => entry.action === "create" && entry.status === "pending" && entry.id !== inFlightId)
+          .first()
+      : undefined;
+
+    if (pendingCreate && mutation.action === "update") {
+      await db.outbox.update(pendingCreate.id!, {
+        payload: { ...pendingCreate.payload, ...mutation.payload },
+      });
+      return;
+    }
+    if (pendingCreate && mutation.action === "delete") {
+      await db.outbox.where("entityId").equals(mutation.entityId).delete();
+      return;
+    }
+    await db.outbox.add({ ...mutation, status: "pending", createdAt: new Date().toISOString() });
+  });
+}
+
+export async function applyLocalTodo(action: OutboxMutation["action"], todo: Partial<Todo> & { id: string }): Promise<void> {
+  if (action === "delete") {
+    await db.todos.delete(todo.id);
+  } else if (action === "create") {
+    await db.todos.put(todo as Todo);
+  } else {
+    await db.todos.update(todo.id, todo);
+  }
+}
+
+async function putEvent(event: CalendarEvent, previousId = event.googleEventId): Promise<void> {
+  const existing = await db.events
+    .where("[googleCalendarId+googleEventId]")
+    .equals([event.googleCalendarId, previousId])
+    .first();
+  await db.events.put({ ...event, uuid: existing?.uuid });
+}
+
+async function remapEntity(tempId: string, serverId: string, etag?: string): Promise<void> {
+  await db.outbox
+    .where("entityId")
+    .equals(tempId)
+    .modify({ entityId: serverId, ...(etag ? { etag } : {}) });
+}
+
+// Later changes were queued against the etag this one replaced; without it they would all come back as 412.
+async function advanceEtag(entry: DexieOutboxEntry, etag?: string): Promise<void> {
+  if (!etag) return;
+  await db.outbox
+    .where("entityId")
+    .equals(entry.entityId)
+    .filter((queued) => queued.id !== entry.id && queued.status === "pending" && queued.etag !== undefined)
+    .modify({ etag });
+}
+
+function ignoreNotFound(error: unknown): void {
+  if (!(error instanceof ApiError) || error.status !== 404) throw error;
+}
+
+async function replayEventEntry(entry: DexieOutboxEntry): Promise<void> {
+  const calendarId = entry.googleCalendarId!;
+  const payload = entry.payload as Partial<CalendarEvent>;
+
+  if (entry.action === "create") {
+    const created = await eventsApi.create(calendarId, payload);
+    await db.transaction("rw", db.events, db.outbox, async () => {
+      await putEvent(created, entry.entityId);
+      await remapEntity(entry.entityId, created.googleEventId, created.etag);
+    });
+  } else if (entry.action === "update") {
+    const updated = await eventsApi.update(calendarId, entry.entityId, payload, entry.etag);
+    await db.transaction("rw", db.events, db.outbox, async () => {
+      await putEvent(updated);
+      await advanceEtag(entry, updated.etag);
+    });
+  } else {
+    await eventsApi.delete(calendarId, entry.entityId, entry.etag).catch(ignoreNotFound);
+  }
+}
+
+async function replayTodoEntry(entry: DexieOutboxEntry): Promise<void> {
+  if (entry.action === "create") {
+    const created = await todosApi.createTodo(entry.payload as unknown as CreateTodoInput);
+    await db.transaction("rw", db.todos, db.outbox, async () => {
+      await db.todos.delete(entry.entityId);
+      await db.todos.put(created);
+      await remapEntity(entry.entityId, created.id);
+    });
+  } else if (entry.action === "update") {
+    const updated = await todosApi.updateTodo(entry.entityId, entry.payload as UpdateTodoInput);
+    await db.todos.put(updated);
+  } else {
+    await todosApi.deleteTodo(entry.entityId).catch(ignoreNotFound);
+  }
+}
+
+async function markUnsynced(entry: DexieOutboxEntry, error: unknown): Promise<void> {
+  const conflict = getEventConflict(error);
+  const message = conflict?.message ?? (error instanceof Error ? error.message : "Failed to sync change");
+  const status = isPreconditionError(error) ? "conflict" : "rejected";
+
+  await db.transaction("rw", db.outbox, db.events, async () => {
+    if (conflict?.current) {
+      await putEvent(conflict.current);
+    }
+    const affected = entry.action === "create"
+      ? db.outbox.where("entityId").equals(entry.entityId)
+      : db.outbox.where("id").equals(entry.id!);
+    await affected.modify({ status, error: message, server: conflict?.current ?? null });
+  });
+}
+
+// Later changes to an entity stay queued behind one that needs the user's attention, so they
+// neither overwrite the server copy the user is looking at nor replay against a missing create.
+async function nextPendingEntry(): Promise<DexieOutboxEntry | undefined> {
+  const blocked = new Set(
+    (await db.outbox.where("status").anyOf("conflict", "rejected").toArray()).map((entry) => entry.entityId),
+  );
+  return db.outbox
+    .where("status")
+    .equals("pending")
+    .filter((entry) => !blocked.has(entry.entityId))
+    .first();
+}
+
+async function drainOutbox(): Promise<number> {
+  let replayed = 0;
+  while (navigator.onLine) {
+    const entry = await nextPendingEntry();
+    if (!entry) break;
+
+    inFlightId = entry.id;
+    try {
+      if (entry.entity === "event") {
+        await replayEventEntry(entry);
+      } else {
+        await replayTodoEntry(entry);
+      }
+      await db.outbox.delete(entry.id!);
+      replayed += 1;
+    } catch (error) {
+      if (isTransientError(error)) break;
+      await markUnsynced(entry, error);
+    } finally {
+      inFlightId = undefined;
+    }
+  }
+  return replayed;
+}
+
+export function replayOutbox(): Promise<number> {
+  if (!replaying) {
+    const run = navigator.locks
+      ? navigator.locks.request(OUTBOX_LOCK, drainOutbox)
+      : drainOutbox();
+    replaying = run.finally(() => {
+      replaying = null;
+    });
+  }
+  return replaying;
+}
+
+export async function retryOutboxEntry(id: number, overwrite = false): Promise<number> {
+  await db.outbox.update(id, {
+    status: "pending",
+    error: undefined,
+    server: undefined,
+    ...(overwrite ? { etag: undefined } : {}),
+  });
+  return replayOutbox();
+}
+
+export async function discardOutboxEntry(id: number): Promise<void> {
+  const entry = await db.outbox.get(id);
+  if (!entry) return;
+
+  await db.transaction("rw", db.outbox, db.events, db.todos, async () => {
+    if (entry.action === "create") {
+      await db.outbox.where("entityId").equals(entry.entityId).delete();
+      if (entry.entity === "event") {
+        await db.events
+          .where("[googleCalendarId+googleEventId]")
+          .equals([entry.googleCalendarId!, entry.entityId])
+          .delete();
+      } else {
+        await db.todos.delete(entry.entityId);
+      }
+      return;
+    }
+    await db.outbox.delete(id);
+    if (entry.server) {
+      await putEvent(entry.server);
+    }
+  });
+}
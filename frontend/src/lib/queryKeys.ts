@@ -9,6 +9,7 @@ export const googleKeys = {
   all: ['google'] as const,
   accounts: () => [...googleKeys.all, 'accounts'] as const,
   calendars: () => [...googleKeys.all, 'calendars'] as const,
+  syncStatus: () => [...googleKeys.all, 'syncStatus'] as const,
 }
 
 export const todoKeys = {
@@ -0,0 +1,61 @@
+const LEADER_LOCK = "chronos-sync-leader";
+const CHANNEL_NAME = "chronos-sync";
+
+export interface SyncProgress {
+  eventsLoaded: number;
+  calendarsComplete: number;
+  totalCalendars: number;
+}
+
+export type SyncMessage =
+  | { type: "started"; calendarIds: string[] }
+  | { type: "progress"; progress: SyncProgress }
+  | { type: "complete"; calendarIds: string[]; lastSyncAt: string }
+  | { type: "error"; calendarIds: string[]; message?: string }
+  | { type: "todos-changed" }
+  | { type: "calendars-changed"; removed: string[] }
+  | { type: "request-sync"; calendarIds: string[] }
+  | { type: "stop" };
+
+export interface SyncChannel {
+  post: (message: SyncMessage) => void;
+  close: () => void;
+}
+
+export function electSyncLeader(onElected: () => void): () => void {
+  if (typeof navigator === "undefined" || !navigator.locks) {
+    onElected();
+    return () => {};
+  }
+
+  const controller = new AbortController();
+  let release: (() => void) | null = null;
+
+  navigator.locks
+    .request(LEADER_LOCK, { signal: controller.signal }, () =>
+      new Promise<void>((resolve) => {
+        release = resolve;
+        onElected();
+      }),
+    )
+    .catch(() => {});
+
+  return () => {
+    controller.abort();
+    release?.();
+  };
+}
+
+export function openSyncChannel(onMessage: (message: SyncMessage) => void): SyncChannel {
+  if (typeof BroadcastChannel === "undefined") {
+    return { post: () => {}, close: () => {} };
+  }
+
+  const channel = new BroadcastChannel(CHANNEL_NAME);
+  channel.onmessage = (event: MessageEvent<SyncMessage>) => onMessage(event.data);
+
+  return {
+    post: (message) => channel.postMessage(message),
+    close: () => channel.close(),
+  };
+}
@@ -1,6 +1,6 @@
 import { create } from 'zustand'
 import { addMonths, subMonths, addWeeks, subWeeks, addDays, subDays } from 'date-fns'
-import type { CalendarView } from '../types'
+import type { CalendarView, EventConflict } from '../types'
 
 interface CalendarState {
   // Current view state
@@ -18,6 +18,9 @@ interface CalendarState {
   // Settings modal
   showSettings: boolean
 
+  // Edit rejected because the event changed elsewhere
+  eventConflict: EventConflict | null
+
   // Actions
   setView: (view: CalendarView) => void
   setCurrentDate: (date: Date) => void
@@ -25,6 +28,7 @@ interface CalendarState {
   toggleSidebar: () => void
   setSidebarWidth: (width: number) => void
   setShowSettings: (show: boolean) => void
+  setEventConflict: (conflict: EventConflict | null) => void
 
   // Navigation
   navigateToToday: () => void
@@ -40,6 +44,7 @@ export const useCalendarStore = create<CalendarState>((set, get) => ({
   sidebarOpen: true,
   sidebarWidth: 320,
   showSettings: false,
+  eventConflict: null,
 
   setView: (view) => set({ view }),
   setCurrentDate: (date) => set({ currentDate: date }),
@@ -51,6 +56,7 @@ export const useCalendarStore = create<CalendarState>((set, get) => ({
   toggleSidebar: () => set((state) => ({ sidebarOpen: !state.sidebarOpen })),
   setSidebarWidth: (width) => set({ sidebarWidth: width }),
   setShowSettings: (show) => set({ showSettings: show }),
+  setEventConflict: (conflict) => set({ eventConflict: conflict }),
 
   navigateToToday: () => set({ currentDate: new Date() }),
 
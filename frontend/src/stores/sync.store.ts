@@ -9,6 +9,7 @@ interface SyncState {
   shouldStop: boolean
 
   startSync: (calendarIds?: string[]) => void
+  stopSync: () => void
   completeSync: () => void
   setError: (error: string) => void
   isSyncing: (calendarId?: string) => boolean
@@ -31,6 +32,13 @@ export const useSyncStore = create<SyncState>()((set, get) => ({
         : state.syncingCalendarIds,
     })),
 
+  stopSync: () =>
+    set({
+      status: 'idle',
+      shouldStop: true,
+      syncingCalendarIds: [],
+    }),
+
   completeSync: () =>
     set({
       status: 'idle',
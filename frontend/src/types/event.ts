@@ -47,6 +47,15 @@ export interface CalendarEvent {
   updatedAt: string;
   htmlLink?: string;
   iCalUID?: string;
+  etag?: string;
+}
+
+export interface EventConflict {
+  action: "edit" | "delete";
+  googleCalendarId: string;
+  eventId: string;
+  local?: Partial<CalendarEvent>;
+  server: CalendarEvent | null;
 }
 
 export interface EventDateTime {
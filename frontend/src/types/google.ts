@@ -1,10 +1,42 @@
+export type CalendarProviderSource = 'google' | 'caldav' | 'microsoft' | 'chronos'
+
 export interface GoogleAccount {
   id: string
   email: string
   name: string
+  provider?: CalendarProviderSource
   needs_reauth: boolean
 }
 
+export interface CalDAVCredentials {
+  server_url: string
+  username: string
+  password: string
+}
+
+export type BackfillStatus = 'past' | 'future' | 'complete' | 'disabled'
+
+export interface CalendarSyncState {
+  backfill_enabled: boolean
+  backfill_status: BackfillStatus | null
+  backfill_events_synced: number
+}
+
+export type SyncHealth = 'healthy' | 'stale' | 'degraded' | 'failing' | 'never_synced'
+
+export interface CalendarSyncStatus {
+  calendarId: string
+  lastSyncAt: string | null
+  lastSuccessAt: string | null
+  lastErrorAt: string | null
+  lastErrorCode: string | null
+  lastErrorMessage: string | null
+  consecutiveFailures: number
+  eventCount: number | null
+  webhookExpiresAt: string | null
+  health: SyncHealth
+}
+
 export interface GoogleCalendar {
   id: string
   google_calendar_id: string
@@ -16,4 +48,11 @@ export interface GoogleCalendar {
   account_email: string
   account_name: string
   needs_reauth: boolean
+  calendar_sync_state?: CalendarSyncState | null
+  google_accounts?: { provider?: CalendarProviderSource | null }
+}
+
+export interface ChronosCalendarInput {
+  name: string
+  color?: string
 }